@@ -1,5 +1,7 @@
 use std::fmt::{self, Display, Formatter};
+use std::num::NonZeroUsize;
 use std::path::PathBuf;
+use std::str::FromStr;
 
 use clap::{ArgAction, Parser, Subcommand, ValueEnum};
 
@@ -60,6 +62,12 @@ pub enum Command {
 
     /// List all discovered fonts in system and custom font paths
     Fonts(FontsCommand),
+
+    /// Formats Typst files in place
+    Fmt(FmtCommand),
+
+    /// Creates a new project from a template package
+    Init(InitCommand),
 }
 
 impl Command {
@@ -68,7 +76,7 @@ impl Command {
         match self {
             Command::Compile(cmd) => Some(cmd),
             Command::Watch(cmd) => Some(cmd),
-            Command::Fonts(_) => None,
+            Command::Fonts(_) | Command::Fmt(_) | Command::Init(_) => None,
         }
     }
 
@@ -78,13 +86,16 @@ impl Command {
     }
 }
 
-/// Compiles the input file into a PDF file
+/// Compiles the input files into a PDF file
 #[derive(Debug, Clone, Parser)]
 pub struct CompileCommand {
-    /// Path to input Typst file
-    pub input: PathBuf,
+    /// Paths to input Typst files, optionally followed by the path to the
+    /// output file. Multiple inputs are merged into a single output in order.
+    #[arg(required = true)]
+    pub inputs: Vec<PathBuf>,
 
     /// Path to output PDF file or PNG file(s)
+    #[arg(short, long)]
     pub output: Option<PathBuf>,
 
     /// Opens the output file after compilation using the default PDF viewer
@@ -95,6 +106,54 @@ pub struct CompileCommand {
     #[arg(long = "ppi")]
     pub ppi: Option<f32>,
 
+    /// Prints statistics about the compiled document
+    #[arg(long = "stats")]
+    pub stats: bool,
+
+    /// Outlines all frames and text runs in the output for debugging
+    #[arg(long = "debug-frames")]
+    pub debug_frames: bool,
+
+    /// Arranges the pages on physical sheets for printing
+    #[arg(long = "impose")]
+    pub impose: Option<ImposeMode>,
+
+    /// Keeps the names of glyphs in embedded fonts for debugging
+    #[arg(long = "keep-glyph-names")]
+    pub keep_glyph_names: bool,
+
+    /// Removes hinting instructions from embedded fonts to reduce file size
+    #[arg(long = "strip-hinting")]
+    pub strip_hinting: bool,
+
+    /// Removes all names except for the font's name and license from
+    /// embedded fonts
+    #[arg(long = "minimize-font-names")]
+    pub minimize_font_names: bool,
+
+    /// Embeds fonts whose license restricts embedding. Only use this if you
+    /// have permission to embed them
+    #[arg(long = "embed-restricted-fonts")]
+    pub embed_restricted_fonts: bool,
+
+    /// Continues the page numbers across multiple inputs instead of
+    /// restarting them for every input
+    #[arg(long = "continuous-page-numbers")]
+    pub continuous_page_numbers: bool,
+
+    /// Which pages to export, e.g. `2-5,9` (all pages by default)
+    #[arg(long = "pages", value_delimiter = ',')]
+    pub pages: Option<Vec<PageRange>>,
+
+    /// Adds a string key-value pair, visible through `sys.inputs`
+    #[arg(
+        long = "input",
+        value_name = "key=value",
+        action = ArgAction::Append,
+        value_parser = parse_input_pair,
+    )]
+    pub sys_inputs: Vec<(String, String)>,
+
     /// In which format to emit diagnostics
     #[clap(
         long,
@@ -106,6 +165,80 @@ pub struct CompileCommand {
     /// Produces a flamegraph of the compilation process
     #[arg(long = "flamegraph", value_name = "OUTPUT_SVG")]
     pub flamegraph: Option<Option<PathBuf>>,
+
+    /// Prints a summary of the time spent in each phase of the compilation
+    #[arg(long = "timings")]
+    pub timings: bool,
+}
+
+/// How to arrange pages on physical sheets.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, ValueEnum)]
+pub enum ImposeMode {
+    /// Two consecutive pages side by side on each sheet
+    TwoUp,
+    /// Pages ordered for folding and stapling into a booklet
+    Booklet,
+}
+
+/// A range of pages to export, e.g. `3`, `2-5`, `-4` or `7-`.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct PageRange {
+    /// The first page in the range (inclusive).
+    pub start: Option<NonZeroUsize>,
+    /// The last page in the range (inclusive).
+    pub end: Option<NonZeroUsize>,
+}
+
+impl PageRange {
+    /// Whether the range contains the given page number.
+    pub fn contains(&self, page: NonZeroUsize) -> bool {
+        self.start.map_or(true, |start| start <= page)
+            && self.end.map_or(true, |end| page <= end)
+    }
+}
+
+impl FromStr for PageRange {
+    type Err = &'static str;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let parse = |s: &str| -> Result<Option<NonZeroUsize>, Self::Err> {
+            let s = s.trim();
+            if s.is_empty() {
+                return Ok(None);
+            }
+            s.parse()
+                .map(Some)
+                .map_err(|_| "page numbers must be positive integers")
+        };
+
+        let range = match value.split_once('-') {
+            Some((start, end)) => Self { start: parse(start)?, end: parse(end)? },
+            None => {
+                let page = parse(value)?.ok_or("page range must not be empty")?;
+                Self { start: Some(page), end: Some(page) }
+            }
+        };
+
+        if let (Some(start), Some(end)) = (range.start, range.end) {
+            if start > end {
+                return Err("page range must not end before it starts");
+            }
+        }
+
+        Ok(range)
+    }
+}
+
+/// Parses a key-value pair of the form `key=value`.
+fn parse_input_pair(raw: &str) -> Result<(String, String), String> {
+    let (key, value) = raw
+        .split_once('=')
+        .ok_or("input must be a key and a value separated by an equal sign")?;
+    let key = key.trim();
+    if key.is_empty() {
+        return Err("input key must not be empty".into());
+    }
+    Ok((key.into(), value.into()))
 }
 
 /// List all discovered fonts in system and custom font paths
@@ -115,3 +248,25 @@ pub struct FontsCommand {
     #[arg(long)]
     pub variants: bool,
 }
+
+/// Formats Typst files in place
+#[derive(Debug, Clone, Parser)]
+pub struct FmtCommand {
+    /// Paths to the Typst files to format
+    #[arg(required = true)]
+    pub inputs: Vec<PathBuf>,
+
+    /// Only check whether the files are formatted instead of rewriting them
+    #[arg(long)]
+    pub check: bool,
+}
+
+/// Creates a new project from a template package
+#[derive(Debug, Clone, Parser)]
+pub struct InitCommand {
+    /// The template package to use, e.g. `@preview/charged-ieee:0.1.0`
+    pub template: String,
+
+    /// The directory to create the project in (the package's name by default)
+    pub dir: Option<PathBuf>,
+}
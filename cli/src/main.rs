@@ -7,6 +7,7 @@ use std::env;
 use std::fs::{self, File};
 use std::hash::Hash;
 use std::io::{self, IsTerminal, Write};
+use std::num::NonZeroUsize;
 use std::path::{Path, PathBuf};
 use std::process::ExitCode;
 
@@ -22,19 +23,25 @@ use siphasher::sip128::{Hasher128, SipHasher13};
 use std::cell::OnceCell;
 use termcolor::{ColorChoice, StandardStream, WriteColor};
 use typst::diag::{
-    bail, FileError, FileResult, PackageError, PackageResult, SourceError, StrResult,
+    bail, FileError, FileResult, PackageError, PackageResult, Severity, SourceError,
+    SourceResult, StrResult,
 };
-use typst::doc::Document;
-use typst::eval::{eco_format, Datetime, Library};
-use typst::file::{FileId, PackageSpec};
+use typst::doc::{Document, Frame};
+use typst::eval::{eco_format, Datetime, Dict, Library, ShapingStats, Tracer, Value};
+use typst::export::{impose, Imposition, PdfOptions};
+use typst::file::{FileId, PackageManifest, PackageSpec};
 use typst::font::{Font, FontBook, FontInfo, FontVariant};
 use typst::geom::Color;
 use typst::syntax::Source;
 use typst::util::{Bytes, PathExt};
 use typst::World;
+use typst_library::layout::PageElem;
 use walkdir::WalkDir;
 
-use crate::args::{CliArguments, Command, CompileCommand, DiagnosticFormat};
+use crate::args::{
+    CliArguments, Command, CompileCommand, DiagnosticFormat, FmtCommand, ImposeMode,
+    InitCommand, PageRange,
+};
 
 type CodespanResult<T> = Result<T, CodespanError>;
 type CodespanError = codespan_reporting::files::Error;
@@ -59,6 +66,8 @@ fn main() -> ExitCode {
             compile(CompileSettings::with_arguments(arguments))
         }
         Command::Fonts(_) => fonts(FontsSettings::with_arguments(arguments)),
+        Command::Fmt(command) => fmt(command),
+        Command::Init(command) => init(command),
     };
 
     if let Err(msg) = res {
@@ -95,8 +104,10 @@ fn typst_version() -> &'static str {
 struct CompileSettings {
     /// The project's root directory.
     root: Option<PathBuf>,
-    /// The path to the input file.
-    input: PathBuf,
+    /// The paths to the input files. Their pages are merged in order.
+    inputs: Vec<PathBuf>,
+    /// Whether the page numbers continue across inputs.
+    continuous_page_numbers: bool,
     /// The path to the output file.
     output: PathBuf,
     /// Whether to watch the input files for changes.
@@ -107,6 +118,18 @@ struct CompileSettings {
     open: Option<Option<String>>,
     /// The PPI to use for PNG export.
     ppi: Option<f32>,
+    /// Which pages to export. All pages if `None`.
+    pages: Option<Vec<PageRange>>,
+    /// Key-value pairs that are visible to documents through `sys.inputs`.
+    sys_inputs: Vec<(String, String)>,
+    /// Whether to outline all frames in the output.
+    debug_frames: bool,
+    /// How to arrange the pages on physical sheets.
+    impose: Option<ImposeMode>,
+    /// Options for PDF export.
+    pdf: PdfOptions,
+    /// Whether to print statistics about the compiled document.
+    stats: bool,
     /// In which format to emit diagnostics.
     diagnostic_format: DiagnosticFormat,
 }
@@ -115,28 +138,49 @@ impl CompileSettings {
     /// Create a new compile settings from the field values.
     #[allow(clippy::too_many_arguments)]
     fn new(
-        input: PathBuf,
+        mut inputs: Vec<PathBuf>,
+        continuous_page_numbers: bool,
         output: Option<PathBuf>,
         root: Option<PathBuf>,
         font_paths: Vec<PathBuf>,
         watch: bool,
         open: Option<Option<String>>,
         ppi: Option<f32>,
+        pages: Option<Vec<PageRange>>,
+        sys_inputs: Vec<(String, String)>,
+        debug_frames: bool,
+        impose: Option<ImposeMode>,
+        pdf: PdfOptions,
+        stats: bool,
         diagnostic_format: DiagnosticFormat,
     ) -> Self {
+        // For compatibility, the last positional path is the output if it is
+        // not a Typst file.
         let output = match output {
             Some(path) => path,
-            None => input.with_extension("pdf"),
+            None if inputs.len() > 1
+                && inputs.last().map_or(false, |path| !is_typst_file(path)) =>
+            {
+                inputs.pop().unwrap()
+            }
+            None => inputs[0].with_extension("pdf"),
         };
         Self {
             root,
-            input,
+            inputs,
+            continuous_page_numbers,
             output,
             watch,
             font_paths,
             open,
             diagnostic_format,
             ppi,
+            pages,
+            sys_inputs,
+            debug_frames,
+            impose,
+            pdf,
+            stats,
         }
     }
 
@@ -146,21 +190,49 @@ impl CompileSettings {
     /// Panics if the command is not a compile or watch command.
     fn with_arguments(args: CliArguments) -> Self {
         let watch = matches!(args.command, Command::Watch(_));
-        let CompileCommand { input, output, open, ppi, diagnostic_format, .. } =
-            match args.command {
-                Command::Compile(command) => command,
-                Command::Watch(command) => command,
-                _ => unreachable!(),
-            };
+        let CompileCommand {
+            inputs,
+            continuous_page_numbers,
+            output,
+            open,
+            ppi,
+            pages,
+            sys_inputs,
+            debug_frames,
+            impose,
+            keep_glyph_names,
+            strip_hinting,
+            minimize_font_names,
+            embed_restricted_fonts,
+            stats,
+            diagnostic_format,
+            ..
+        } = match args.command {
+            Command::Compile(command) => command,
+            Command::Watch(command) => command,
+            _ => unreachable!(),
+        };
 
         Self::new(
-            input,
+            inputs,
+            continuous_page_numbers,
             output,
             args.root,
             args.font_paths,
             watch,
             open,
             ppi,
+            pages,
+            sys_inputs,
+            debug_frames,
+            impose,
+            PdfOptions {
+                glyph_names: keep_glyph_names,
+                strip_hinting,
+                minimize_names: minimize_font_names,
+                embed_restricted_fonts,
+            },
+            stats,
             diagnostic_format,
         )
     }
@@ -216,7 +288,8 @@ fn compile(mut settings: CompileSettings) -> StrResult<()> {
     let mut watcher = RecommendedWatcher::new(tx, notify::Config::default())
         .map_err(|_| "failed to setup file watching")?;
 
-    // Watch all the files that are used by the input file and its dependencies.
+    // Watch all the files that are used by the input files and their
+    // dependencies.
     world.watch(&mut watcher, HashSet::new())?;
 
     // Handle events.
@@ -272,18 +345,27 @@ fn compile_once(world: &mut SystemWorld, settings: &CompileSettings) -> StrResul
     let start = std::time::Instant::now();
     status(settings, Status::Compiling).unwrap();
 
-    // Reset everything and ensure that the main file is still present.
+    // Reset everything and ensure that the input files are still present.
     world.reset();
-    world.source(world.main).map_err(|err| err.to_string())?;
+    for &id in &world.inputs {
+        world.source(id).map_err(|err| err.to_string())?;
+    }
 
-    let result = typst::compile(world);
-    let duration = start.elapsed();
+    let mut tracer = Tracer::default();
+    let result = compile_inputs(world, settings, &mut tracer);
+    let warnings = tracer.warnings().to_vec();
 
     match result {
         // Export the PDF / PNG.
         Ok(document) => {
             export(&document, settings)?;
+            let duration = start.elapsed();
             status(settings, Status::Success(duration)).unwrap();
+            print_diagnostics(world, warnings, settings.diagnostic_format)
+                .map_err(|_| "failed to print diagnostics")?;
+            if settings.stats {
+                print_stats(&document, tracer.shaping_stats());
+            }
             tracing::info!("Compilation succeeded in {duration:?}");
             Ok(true)
         }
@@ -292,22 +374,120 @@ fn compile_once(world: &mut SystemWorld, settings: &CompileSettings) -> StrResul
         Err(errors) => {
             set_failed();
             status(settings, Status::Error).unwrap();
-            print_diagnostics(world, *errors, settings.diagnostic_format)
+            let diagnostics = warnings.into_iter().chain(*errors).collect();
+            print_diagnostics(world, diagnostics, settings.diagnostic_format)
                 .map_err(|_| "failed to print diagnostics")?;
-            tracing::info!("Compilation failed after {duration:?}");
+            tracing::info!("Compilation failed after {:?}", start.elapsed());
             Ok(false)
         }
     }
 }
 
+/// Compile all input files and merge their pages into one document.
+///
+/// The inputs share the world and thus the font book and all loaded files.
+/// Each input is a document of its own, so counters other than the page
+/// number restart for every input. The page number restarts as well unless
+/// continuous page numbers are requested, in which case each input starts
+/// after the last page of the previous one. The locations of all inputs but
+/// the first are rebased so that their links and outline entries don't
+/// collide. The merged document takes its metadata from the first input. The
+/// warnings of all inputs are collected in the `tracer`.
+fn compile_inputs(
+    world: &mut SystemWorld,
+    settings: &CompileSettings,
+    tracer: &mut Tracer,
+) -> SourceResult<Document> {
+    let library = world.library.clone();
+    let mut merged: Option<Document> = None;
+    let mut errors = vec![];
+    let mut pages = 0;
+
+    for (i, id) in world.inputs.clone().into_iter().enumerate() {
+        world.main = id;
+        if settings.continuous_page_numbers {
+            let first = NonZeroUsize::new(1 + pages).unwrap();
+            let mut numbered = (*library).clone();
+            numbered.styles.set(PageElem::set_first_number(first));
+            world.library = Prehashed::new(numbered);
+        }
+
+        match typst::compile_with(world, tracer) {
+            Ok(mut document) => {
+                pages += document.pages.len();
+                if i > 0 {
+                    document.pages.iter_mut().for_each(|page| page.rebase_locations(i));
+                }
+                match &mut merged {
+                    Some(merged) => merged.pages.extend(document.pages),
+                    None => merged = Some(document),
+                }
+            }
+            Err(errs) => errors.extend(*errs),
+        }
+    }
+
+    world.library = library;
+
+    if !errors.is_empty() {
+        return Err(Box::new(errors));
+    }
+
+    Ok(merged.unwrap_or_default())
+}
+
 /// Export into the target format.
 fn export(document: &Document, settings: &CompileSettings) -> StrResult<()> {
+    // Outline all frames if requested.
+    let outlined;
+    let document = if settings.debug_frames {
+        let mut pages = document.pages.clone();
+        pages.iter_mut().for_each(Frame::outline);
+        outlined = Document { pages, ..document.clone() };
+        &outlined
+    } else {
+        document
+    };
+
+    // Determine the pages to export alongside their one-based page numbers.
+    let selected: Vec<_> = document
+        .pages
+        .iter()
+        .zip((1..).filter_map(NonZeroUsize::new))
+        .filter(|&(_, number)| {
+            settings
+                .pages
+                .as_ref()
+                .map_or(true, |ranges| ranges.iter().any(|range| range.contains(number)))
+        })
+        .collect();
+
+    if selected.is_empty() {
+        bail!("no pages selected for export");
+    }
+
+    // Arrange the selected pages on physical sheets if requested. All of the
+    // resulting sheets are exported.
+    let imposed;
+    let (document, selected) = if let Some(mode) = settings.impose {
+        let imposition = match mode {
+            ImposeMode::TwoUp => Imposition::TwoUp,
+            ImposeMode::Booklet => Imposition::Booklet,
+        };
+        let pages = selected.into_iter().map(|(frame, _)| frame.clone()).collect();
+        imposed = impose(&Document { pages, ..document.clone() }, imposition);
+        let sheets = imposed.pages.iter().zip((1..).filter_map(NonZeroUsize::new));
+        (&imposed, sheets.collect())
+    } else {
+        (document, selected)
+    };
+
     match settings.output.extension() {
         Some(ext) if ext.eq_ignore_ascii_case("png") => {
             // Determine whether we have a `{n}` numbering.
             let string = settings.output.to_str().unwrap_or_default();
             let numbered = string.contains("{n}");
-            if !numbered && document.pages.len() > 1 {
+            if !numbered && selected.len() > 1 {
                 bail!("cannot export multiple PNGs without `{{n}}` in output path");
             }
 
@@ -318,10 +498,10 @@ fn export(document: &Document, settings: &CompileSettings) -> StrResult<()> {
             let ppi = settings.ppi.unwrap_or(2.0);
             let mut storage;
 
-            for (i, frame) in document.pages.iter().enumerate() {
+            for (frame, number) in selected {
                 let pixmap = typst::export::render(frame, ppi, Color::WHITE);
                 let path = if numbered {
-                    storage = string.replace("{n}", &format!("{:0width$}", i + 1));
+                    storage = string.replace("{n}", &format!("{number:0width$}"));
                     Path::new(&storage)
                 } else {
                     settings.output.as_path()
@@ -329,8 +509,15 @@ fn export(document: &Document, settings: &CompileSettings) -> StrResult<()> {
                 pixmap.save_png(path).map_err(|_| "failed to write PNG file")?;
             }
         }
+        _ if selected.len() < document.pages.len() => {
+            let pages = selected.into_iter().map(|(frame, _)| frame.clone()).collect();
+            let document = Document { pages, ..document.clone() };
+            let buffer = typst::export::pdf_with(&document, settings.pdf)?;
+            fs::write(&settings.output, buffer)
+                .map_err(|_| "failed to write PDF file")?;
+        }
         _ => {
-            let buffer = typst::export::pdf(document);
+            let buffer = typst::export::pdf_with(document, settings.pdf)?;
             fs::write(&settings.output, buffer)
                 .map_err(|_| "failed to write PDF file")?;
         }
@@ -338,6 +525,17 @@ fn export(document: &Document, settings: &CompileSettings) -> StrResult<()> {
     Ok(())
 }
 
+/// Print statistics about a compiled document.
+fn print_stats(document: &Document, shaping: ShapingStats) {
+    let stats = document.stats();
+    println!("pages: {}", stats.pages);
+    println!("words: {}", stats.words);
+    println!("characters: {}", stats.chars);
+    println!("images: {}", stats.images);
+    println!("fonts: {}", stats.fonts.join(", "));
+    println!("shaped runs: {} ({} distinct)", shaping.runs, shaping.distinct);
+}
+
 /// Clear the terminal and render the status message.
 #[tracing::instrument(skip_all)]
 fn status(settings: &CompileSettings, status: Status) -> io::Result<()> {
@@ -346,7 +544,12 @@ fn status(settings: &CompileSettings, status: Status) -> io::Result<()> {
     }
 
     let esc = 27 as char;
-    let input = settings.input.display();
+    let input = settings
+        .inputs
+        .iter()
+        .map(|path| path.display().to_string())
+        .collect::<Vec<_>>()
+        .join(", ");
     let output = settings.output.display();
     let time = chrono::offset::Local::now();
     let timestamp = time.format("%H:%M:%S");
@@ -413,7 +616,7 @@ impl Status {
 /// Print diagnostic messages to the terminal.
 fn print_diagnostics(
     world: &SystemWorld,
-    errors: Vec<SourceError>,
+    diagnostics: Vec<SourceError>,
     diagnostic_format: DiagnosticFormat,
 ) -> Result<(), codespan_reporting::files::Error> {
     let mut w = match diagnostic_format {
@@ -426,18 +629,27 @@ fn print_diagnostics(
         config.display_style = term::DisplayStyle::Short;
     }
 
-    for error in errors {
+    for error in diagnostics {
         // The main diagnostic.
-        let diag = Diagnostic::error()
-            .with_message(error.message)
-            .with_notes(
-                error
-                    .hints
-                    .iter()
-                    .map(|e| (eco_format!("hint: {e}")).into())
-                    .collect(),
-            )
-            .with_labels(vec![Label::primary(error.span.id(), error.span.range(world))]);
+        let diag = match error.severity {
+            Severity::Error => Diagnostic::error(),
+            Severity::Warning => Diagnostic::warning(),
+        };
+
+        let mut diag = diag.with_message(error.message).with_notes(
+            error
+                .hints
+                .iter()
+                .map(|e| (eco_format!("hint: {e}")).into())
+                .collect(),
+        );
+
+        if !error.span.is_detached() {
+            diag = diag.with_labels(vec![Label::primary(
+                error.span.id(),
+                error.span.range(world),
+            )]);
+        }
 
         term::emit(&mut w, &config, world, &diag)?;
 
@@ -473,12 +685,80 @@ fn fonts(command: FontsSettings) -> StrResult<()> {
     Ok(())
 }
 
+/// Execute a formatting command.
+fn fmt(command: &FmtCommand) -> StrResult<()> {
+    for path in &command.inputs {
+        let text = fs::read_to_string(path)
+            .map_err(|_| eco_format!("failed to read {}", path.display()))?;
+        let Some(formatted) = typst::syntax::format(&typst::syntax::parse(&text)) else {
+            bail!("failed to format {}: file contains syntax errors", path.display());
+        };
+
+        if formatted == text {
+            continue;
+        }
+
+        if command.check {
+            set_failed();
+            println!("{}", path.display());
+        } else {
+            fs::write(path, formatted)
+                .map_err(|_| eco_format!("failed to write {}", path.display()))?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Execute an initialization command.
+fn init(command: &InitCommand) -> StrResult<()> {
+    let spec: PackageSpec = command.template.parse()?;
+    let package_dir = prepare_package(&spec)?;
+
+    let bytes = fs::read(package_dir.join("typst.toml"))
+        .map_err(|_| eco_format!("failed to read manifest of {spec}"))?;
+    let manifest = PackageManifest::parse(&bytes)?;
+    manifest.validate(&spec)?;
+    let Some(template) = &manifest.template else {
+        bail!("package {spec} is not a template");
+    };
+
+    let project_dir = command
+        .dir
+        .clone()
+        .unwrap_or_else(|| PathBuf::from(spec.name.as_str()));
+    if project_dir.exists() {
+        bail!("project directory already exists (at {})", project_dir.display());
+    }
+
+    // Copy the template's starter project.
+    let template_dir = package_dir.join(template.path.as_str());
+    for entry in WalkDir::new(&template_dir) {
+        let entry = entry.map_err(|_| "failed to read template directory")?;
+        let path = entry.path();
+        let target = project_dir.join(path.strip_prefix(&template_dir).unwrap());
+        if entry.file_type().is_dir() {
+            fs::create_dir_all(&target)
+        } else {
+            fs::copy(path, &target).map(|_| ())
+        }
+        .map_err(|_| eco_format!("failed to write {}", target.display()))?;
+    }
+
+    let entrypoint = project_dir.join(template.entrypoint.as_str());
+    println!("Created project from {spec} in {}", project_dir.display());
+    println!("To compile it, run `typst compile {}`", entrypoint.display());
+    Ok(())
+}
+
 /// A world that provides access to the operating system.
 struct SystemWorld {
     /// The root relative to which absolute paths are resolved.
     root: PathBuf,
-    /// The input path.
+    /// The input file that is currently compiled.
     main: FileId,
+    /// All input files, in the order in which their pages are merged.
+    inputs: Vec<FileId>,
     /// Typst's standard library.
     library: Prehashed<Library>,
     /// Metadata about discovered fonts.
@@ -524,33 +804,53 @@ impl SystemWorld {
         let mut searcher = FontSearcher::new();
         searcher.search(&settings.font_paths);
 
-        // Resolve the system-global input path.
-        let system_input = settings.input.canonicalize().map_err(|_| {
-            eco_format!("input file not found (searched at {})", settings.input.display())
-        })?;
+        // Resolve the system-global input paths.
+        let system_inputs = settings
+            .inputs
+            .iter()
+            .map(|input| {
+                input.canonicalize().map_err(|_| {
+                    eco_format!("input file not found (searched at {})", input.display())
+                })
+            })
+            .collect::<StrResult<Vec<_>>>()?;
 
-        // Resolve the system-global root directory.
+        // Resolve the system-global root directory. Without an explicit root,
+        // it is the directory of the first input.
         let root = {
             let path = settings
                 .root
                 .as_deref()
-                .or_else(|| system_input.parent())
+                .or_else(|| system_inputs[0].parent())
                 .unwrap_or(Path::new("."));
             path.canonicalize().map_err(|_| {
                 eco_format!("root directory not found (searched at {})", path.display())
             })?
         };
 
-        // Resolve the input path within the project.
-        let project_input = system_input
-            .strip_prefix(&root)
-            .map(|path| Path::new("/").join(path))
-            .map_err(|_| "input file must be contained in project root")?;
+        // Resolve the input paths within the project.
+        let inputs = system_inputs
+            .iter()
+            .map(|input| {
+                input
+                    .strip_prefix(&root)
+                    .map(|path| FileId::new(None, &Path::new("/").join(path)))
+                    .map_err(|_| "input file must be contained in project root".into())
+            })
+            .collect::<StrResult<Vec<_>>>()?;
+
+        // Collect the inputs that are visible to documents.
+        let sys_inputs: Dict = settings
+            .sys_inputs
+            .iter()
+            .map(|(key, value)| (key.as_str().into(), Value::Str(value.as_str().into())))
+            .collect();
 
         Ok(Self {
             root,
-            main: FileId::new(None, &project_input),
-            library: Prehashed::new(typst_library::build()),
+            main: inputs[0],
+            inputs,
+            library: Prehashed::new(typst_library::build_with_inputs(sys_inputs)),
             book: Prehashed::new(searcher.book),
             fonts: searcher.fonts,
             hashes: RefCell::default(),
@@ -722,6 +1022,11 @@ impl PathHash {
     }
 }
 
+/// Whether a path has the extension of a Typst file.
+fn is_typst_file(path: &Path) -> bool {
+    path.extension().map_or(false, |ext| ext.eq_ignore_ascii_case("typ"))
+}
+
 /// Read a file.
 #[tracing::instrument(skip_all)]
 fn read(path: &Path) -> FileResult<Vec<u8>> {
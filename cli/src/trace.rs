@@ -1,25 +1,41 @@
+use std::collections::HashMap;
 use std::fs::File;
 use std::io::{BufReader, BufWriter, Error, ErrorKind, Seek, SeekFrom};
 use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
 use inferno::flamegraph::Options;
 use tracing::metadata::LevelFilter;
+use tracing::span::Id;
+use tracing::Subscriber;
 use tracing_error::ErrorLayer;
 use tracing_flame::{FlameLayer, FlushGuard};
 use tracing_subscriber::fmt;
+use tracing_subscriber::layer::{Context, Layer};
 use tracing_subscriber::prelude::*;
+use tracing_subscriber::registry::LookupSpan;
 
 use crate::args::CliArguments;
 
-/// Will flush the flamegraph to disk when dropped.
+/// Will flush the flamegraph to disk and print the timings when dropped.
 pub struct TracingGuard {
     flush_guard: Option<FlushGuard<BufWriter<File>>>,
-    temp_file: File,
+    temp_file: Option<File>,
     output_svg: PathBuf,
+    timings: Option<TimingsLayer>,
 }
 
 impl TracingGuard {
     pub fn finish(&mut self) -> Result<(), Error> {
+        if let Some(timings) = self.timings.take() {
+            timings.print();
+        }
+
+        let Some(temp_file) = &mut self.temp_file else {
+            return Ok(());
+        };
+
         if self.flush_guard.is_none() {
             return Ok(());
         }
@@ -32,10 +48,10 @@ impl TracingGuard {
         drop(self.flush_guard.take());
 
         // Reset the file pointer to the beginning.
-        self.temp_file.seek(SeekFrom::Start(0))?;
+        temp_file.seek(SeekFrom::Start(0))?;
 
         // Create the readers and writers.
-        let reader = BufReader::new(&mut self.temp_file);
+        let reader = BufReader::new(temp_file);
         let output = BufWriter::new(File::create(&self.output_svg)?);
 
         // Create the options: default in flame chart mode
@@ -62,9 +78,10 @@ impl Drop for TracingGuard {
 }
 
 /// Initializes the tracing system and returns a guard that will flush the
-/// flamegraph to disk when dropped.
+/// flamegraph to disk and print the timings when dropped.
 pub fn init_tracing(args: &CliArguments) -> Result<Option<TracingGuard>, Error> {
     let flamegraph = args.command.as_compile().and_then(|c| c.flamegraph.as_ref());
+    let timings = args.command.as_compile().map_or(false, |c| c.timings);
 
     if flamegraph.is_some() && args.command.is_watch() {
         return Err(Error::new(
@@ -73,8 +90,16 @@ pub fn init_tracing(args: &CliArguments) -> Result<Option<TracingGuard>, Error>
         ));
     }
 
-    // Short circuit if we don't need to initialize flamegraph or debugging.
-    if flamegraph.is_none() && args.verbosity == 0 {
+    if timings && args.command.is_watch() {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            "cannot use --timings with watch command",
+        ));
+    }
+
+    // Short circuit if we don't need to initialize flamegraph, timings or
+    // debugging.
+    if flamegraph.is_none() && !timings && args.verbosity == 0 {
         tracing_subscriber::fmt()
             .without_time()
             .with_max_level(level_filter(args))
@@ -89,12 +114,23 @@ pub fn init_tracing(args: &CliArguments) -> Result<Option<TracingGuard>, Error>
     // Error layer for building backtraces
     let error_layer = ErrorLayer::default();
 
+    // Timings layer for the summary table.
+    let timings_layer = timings.then(TimingsLayer::default);
+
     // Build the registry.
-    let registry = tracing_subscriber::registry().with(fmt_layer).with(error_layer);
+    let registry = tracing_subscriber::registry()
+        .with(fmt_layer)
+        .with(error_layer)
+        .with(timings_layer.clone());
 
     let Some(path) = flamegraph else {
         registry.init();
-        return Ok(None);
+        return Ok(timings_layer.map(|timings| TracingGuard {
+            flush_guard: None,
+            temp_file: None,
+            output_svg: PathBuf::new(),
+            timings: Some(timings),
+        }));
     };
 
     // Create a temporary file to store the flamegraph data.
@@ -119,11 +155,55 @@ pub fn init_tracing(args: &CliArguments) -> Result<Option<TracingGuard>, Error>
 
     Ok(Some(TracingGuard {
         flush_guard: Some(flush_guard),
-        temp_file,
+        temp_file: Some(temp_file),
         output_svg: path.clone().unwrap_or_else(|| "flamegraph.svg".into()),
+        timings: timings_layer,
     }))
 }
 
+/// Accumulates the time spent in spans, grouped by span name.
+///
+/// Times are inclusive: a span's time also contains the time spent in the
+/// spans nested within it.
+#[derive(Clone, Default)]
+struct TimingsLayer {
+    totals: Arc<Mutex<HashMap<&'static str, (Duration, usize)>>>,
+}
+
+impl TimingsLayer {
+    /// Print the spans that took the longest as a table.
+    fn print(&self) {
+        let totals = self.totals.lock().unwrap();
+        let mut rows: Vec<_> = totals.iter().collect();
+        rows.sort_by(|a, b| b.1 .0.cmp(&a.1 .0));
+
+        eprintln!("{:>12} {:>8}  span", "time", "calls");
+        for (name, (time, calls)) in rows.into_iter().take(25) {
+            eprintln!("{:>12} {calls:>8}  {name}", format!("{time:.2?}"));
+        }
+    }
+}
+
+impl<S> Layer<S> for TimingsLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_enter(&self, id: &Id, ctx: Context<'_, S>) {
+        if let Some(span) = ctx.span(id) {
+            span.extensions_mut().replace(Instant::now());
+        }
+    }
+
+    fn on_exit(&self, id: &Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else { return };
+        let Some(start) = span.extensions_mut().remove::<Instant>() else { return };
+        let mut totals = self.totals.lock().unwrap();
+        let entry = totals.entry(span.name()).or_default();
+        entry.0 += start.elapsed();
+        entry.1 += 1;
+    }
+}
+
 /// Returns the log level filter for the given verbosity level.
 fn level_filter(args: &CliArguments) -> LevelFilter {
     match args.verbosity {
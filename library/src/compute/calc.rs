@@ -44,6 +44,8 @@ pub fn module() -> Module {
     scope.define("odd", odd_func());
     scope.define("rem", rem_func());
     scope.define("quo", quo_func());
+    scope.define("random", random_func());
+    scope.define("uuid", uuid_func());
     scope.define("inf", f64::INFINITY);
     scope.define("nan", f64::NAN);
     scope.define("pi", std::f64::consts::PI);
@@ -782,30 +784,54 @@ pub fn round(
     }
 }
 
-/// Clamps a number between a minimum and maximum value.
+/// Clamps a value between a minimum and maximum value.
+///
+/// Besides numbers, this works with all values that can be compared, for
+/// instance lengths.
 ///
 /// ## Example { #example }
 /// ```example
 /// #assert(calc.clamp(5, 0, 10) == 5)
 /// #assert(calc.clamp(5, 6, 10) == 6)
-/// #calc.clamp(5, 0, 4)
+/// #calc.clamp(5, 0, 4) \
+/// #calc.clamp(12cm, 2cm, 8cm)
 /// ```
 ///
 /// Display: Clamp
 /// Category: calculate
 #[func]
 pub fn clamp(
-    /// The number to clamp.
-    value: Num,
+    /// The value to clamp.
+    value: Spanned<Value>,
     /// The inclusive minimum value.
-    min: Num,
+    min: Value,
     /// The inclusive maximum value.
-    max: Spanned<Num>,
-) -> SourceResult<Num> {
-    if max.v.float() < min.float() {
+    max: Spanned<Value>,
+) -> SourceResult<Value> {
+    // Mixed integers and floats are clamped as floats.
+    if let (Ok(v), Ok(lo), Ok(hi)) = (
+        value.v.clone().cast::<Num>(),
+        min.clone().cast::<Num>(),
+        max.v.clone().cast::<Num>(),
+    ) {
+        if hi.float() < lo.float() {
+            bail!(max.span, "max must be greater than or equal to min")
+        }
+        return Ok(v.apply3(lo, hi, i64::clamp, f64::clamp).into_value());
+    }
+
+    let compare = typst::eval::ops::compare;
+    if compare(&max.v, &min).at(max.span)? == Ordering::Less {
         bail!(max.span, "max must be greater than or equal to min")
     }
-    Ok(value.apply3(min, max.v, i64::clamp, f64::clamp))
+
+    Ok(if compare(&value.v, &min).at(value.span)? == Ordering::Less {
+        min
+    } else if compare(&value.v, &max.v).at(value.span)? == Ordering::Greater {
+        max.v
+    } else {
+        value.v
+    })
 }
 
 /// Determines the minimum of a sequence of values.
@@ -958,6 +984,120 @@ pub fn quo(
     Ok(floor(dividend.apply2(divisor.v, Div::div, Div::div)))
 }
 
+/// Generates a pseudo-random number between zero (inclusive) and one
+/// (exclusive).
+///
+/// Compiling a document twice always yields the same result, so the number is
+/// not drawn from a changing source of randomness. Instead, it is derived from
+/// a document-level seed and, if given, a key: The same key always yields the
+/// same number. Without a key, every call yields a new number, also within a
+/// loop. Inside of a function, the numbers additionally depend on the
+/// function's arguments, so a function that is called twice with the same
+/// arguments yields the same numbers.
+///
+/// The document-level seed is taken from the `seed` entry of `sys.inputs` if
+/// it exists (for instance, via `--input seed=42` on the command line) and
+/// derived from the content of the main file otherwise. This keeps generated
+/// exercises or placeholder data stable across compilations, while a new seed
+/// yields a new set of values.
+///
+/// ## Example { #example }
+/// ```example
+/// #for i in range(5) [
+///   #calc.floor(calc.random() * 100)
+/// ] \
+/// #calc.random("a", seed: 1)
+/// ```
+///
+/// Display: Random
+/// Category: calculate
+#[func]
+pub fn random(
+    /// The value from which the number is derived. Can be any value, for
+    /// instance a loop counter or a piece of content. Values that look the same
+    /// yield the same number, regardless of where they occur in the source.
+    #[default]
+    key: Value,
+    /// The seed that is mixed into the document-level seed.
+    #[named]
+    #[default(0)]
+    seed: i64,
+    /// The virtual machine.
+    vm: &mut Vm,
+) -> f64 {
+    // Use the upper 53 bits, which a float's mantissa represents exactly.
+    let bits = draw(vm, key, seed) >> 75;
+    bits as f64 / (1u64 << 53) as f64
+}
+
+/// Generates a version 4 UUID.
+///
+/// Like [`random`]($func/calc.random), the UUID is derived from the
+/// document-level seed and an optional key and thus stays the same across
+/// compilations.
+///
+/// ## Example { #example }
+/// ```example
+/// #calc.uuid("chapter-1")
+/// ```
+///
+/// Display: UUID
+/// Category: calculate
+#[func]
+pub fn uuid(
+    /// The value from which the UUID is derived.
+    #[default]
+    key: Value,
+    /// The seed that is mixed into the document-level seed.
+    #[named]
+    #[default(0)]
+    seed: i64,
+    /// The virtual machine.
+    vm: &mut Vm,
+) -> Str {
+    let mut bytes = draw(vm, key, seed).to_be_bytes();
+    bytes[6] = (bytes[6] & 0x0f) | 0x40;
+    bytes[8] = (bytes[8] & 0x3f) | 0x80;
+
+    let hex: String = bytes.iter().map(|byte| format!("{byte:02x}")).collect();
+    eco_format!(
+        "{}-{}-{}-{}-{}",
+        &hex[..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..],
+    )
+    .into()
+}
+
+/// Derive the hash for a pseudo-random value from the document-level seed, the
+/// given seed and the key or, without a key, the call site.
+fn draw(vm: &mut Vm, key: Value, seed: i64) -> u128 {
+    let world = vm.world();
+    let sys = world.library().global.get("sys").ok();
+    let inputs = match sys {
+        Some(Value::Module(sys)) => sys.get("inputs").ok(),
+        _ => None,
+    };
+
+    let document = match inputs {
+        Some(Value::Dict(inputs)) if inputs.contains("seed") => {
+            typst::util::hash128(&inputs.at("seed", None).unwrap().repr())
+        }
+        _ => typst::util::hash128(world.main().text()),
+    };
+
+    // Hash the key's representation instead of the key itself, as content
+    // carries spans that change when the source is edited.
+    let key = match key {
+        Value::None => vm.draw(),
+        key => typst::util::hash128(&key.repr()),
+    };
+
+    typst::util::hash128(&(document, seed, key))
+}
+
 /// A value which can be passed to functions that work with integers and floats.
 #[derive(Debug, Copy, Clone)]
 pub enum Num {
@@ -7,7 +7,8 @@ use crate::text::TextElem;
 /// multiple columns. It will not equalize the height of the columns, instead,
 /// the columns will take up the height of their container or the remaining
 /// height on the page. The columns function can break across pages if
-/// necessary.
+/// necessary. To equalize the columns' heights instead, enable
+/// [`balance`]($func/columns.balance).
 ///
 /// ## Example { #example }
 /// ```example
@@ -44,6 +45,25 @@ pub struct ColumnsElem {
     #[default(Ratio::new(0.04).into())]
     pub gutter: Rel<Length>,
 
+    /// Whether to equalize the heights of the columns.
+    ///
+    /// Typst searches for the smallest height at which the content still fits
+    /// into the columns and sets all columns to that height instead of
+    /// filling the first column completely. When the content breaks across
+    /// pages, the columns of the earlier pages are filled as usual and only
+    /// the columns of the last page are balanced.
+    ///
+    /// ```example
+    /// #columns(2, balance: true)[
+    ///   This text is distributed
+    ///   evenly over both columns
+    ///   instead of filling the
+    ///   first one completely.
+    /// ]
+    /// ```
+    #[default(false)]
+    pub balance: bool,
+
     /// The content that should be layouted into the columns.
     #[required]
     pub body: Content,
@@ -86,8 +106,21 @@ impl Layout for ColumnsElem {
             root: regions.root,
         };
 
+        // Determine the balanced heights with measurements only, so that just
+        // the final layout advances the locator.
+        let balanced = if self.balance(styles) && columns > 1 {
+            balance(vt, styles, &body, columns, pod)?
+        } else {
+            None
+        };
+
         // Layout the children.
-        let mut frames = body.layout(vt, styles, pod)?.into_iter();
+        let frames = match &balanced {
+            Some(heights) => body.layout(vt, styles, column_regions(pod, heights))?,
+            None => body.layout(vt, styles, pod)?,
+        };
+
+        let mut frames = frames.into_iter();
         let mut finished = vec![];
 
         let dir = TextElem::dir_in(styles);
@@ -127,6 +160,76 @@ impl Layout for ColumnsElem {
     }
 }
 
+/// Determine the column heights at which the body's last columns are
+/// distributed evenly.
+///
+/// The body first flows through the columns as usual. Then, the columns of the
+/// region the body ends in are shortened to the smallest height at which the
+/// body still fits into them. Returns the heights of all columns or `None` if
+/// balancing is not possible because the available height is infinite.
+fn balance(
+    vt: &mut Vt,
+    styles: StyleChain,
+    body: &Content,
+    columns: usize,
+    pod: Regions,
+) -> SourceResult<Option<Vec<Abs>>> {
+    if !pod.size.y.is_finite() {
+        return Ok(None);
+    }
+
+    // The columns the body needs without balancing, rounded up to full
+    // regions.
+    let needed = body.measure(vt, styles, pod)?.len();
+    let used = (needed.max(1) + columns - 1) / columns * columns;
+    let mut heights: Vec<Abs> = pod.iter().take(used).map(|size| size.y).collect();
+    let start = used - columns;
+    if heights.len() < used || !heights[start].is_finite() {
+        return Ok(None);
+    }
+
+    let max = heights[start];
+
+    let mut fits = |height: Abs| -> SourceResult<bool> {
+        heights[start..].fill(height);
+        let regions = column_regions(pod, &heights);
+        Ok(body.measure(vt, styles, regions)?.len() <= used)
+    };
+
+    // Binary search for the smallest fitting height with a precision of a
+    // tenth of a point.
+    let mut lo = Abs::zero();
+    let mut hi = max;
+    if !fits(hi)? {
+        return Ok(None);
+    }
+
+    while hi - lo > Abs::pt(0.1) {
+        let mid = (lo + hi) / 2.0;
+        if fits(mid)? {
+            hi = mid;
+        } else {
+            lo = mid;
+        }
+    }
+
+    heights[start..].fill(hi);
+    Ok(Some(heights))
+}
+
+/// The regions for columns with the given heights. The last height repeats
+/// for content that doesn't fit.
+fn column_regions<'a>(pod: Regions, heights: &'a [Abs]) -> Regions<'a> {
+    Regions {
+        size: Size::new(pod.size.x, heights[0]),
+        full: if heights.len() > 1 { pod.full } else { heights[0] },
+        backlog: &heights[1..],
+        last: heights.last().copied(),
+        expand: Axes::new(true, false),
+        root: pod.root,
+    }
+}
+
 /// Forces a column break.
 ///
 /// The function will behave like a [page break]($func/pagebreak) when used in a
@@ -1,8 +1,9 @@
 use typst::eval::AutoValue;
 
 use super::VElem;
-use crate::layout::Spacing;
+use crate::layout::{MinMax, Spacing};
 use crate::prelude::*;
+use crate::text::{TextElem, TextSize};
 
 /// An inline-level container that sizes content.
 ///
@@ -37,6 +38,12 @@ pub struct BoxElem {
     /// ```example
     /// Line in #box(width: 1fr, line(length: 100%)) between.
     /// ```
+    #[parse(match args.named::<Spanned<Sizing>>("width")? {
+        Some(Spanned { v: Sizing::MinMax(_), span }) => {
+            bail!(span, "minmax sizing is only supported for grid tracks")
+        }
+        sizing => sizing.map(|sizing| sizing.v),
+    })]
     pub width: Sizing,
 
     /// The height of the box.
@@ -95,6 +102,40 @@ pub struct BoxElem {
     #[default(false)]
     pub clip: bool,
 
+    /// What to do with content that doesn't fit into the box's fixed width or
+    /// height.
+    ///
+    /// By default, such content overflows the box. It can also be scaled down
+    /// until it fits, be set in a smaller text size or trigger an error. To
+    /// cut it off at the box's edges, use [`clip`]($func/box.clip) instead.
+    ///
+    /// - `{"visible"}`: The content extends beyond the box.
+    /// - `{"shrink"}`: The content is scaled down uniformly until it fits.
+    /// - `{"shrink-text"}`: The text size is reduced until the content fits,
+    ///   down to a quarter of the original size. This reflows the text, which
+    ///   makes it useful for labels and fixed-size slide templates.
+    /// - `{"error"}`: The content produces an error.
+    ///
+    /// ```example
+    /// #box(
+    ///   width: 2cm,
+    ///   height: 1cm,
+    ///   stroke: 0.5pt,
+    ///   overflow: "shrink",
+    ///   lorem(8),
+    /// )
+    ///
+    /// #box(
+    ///   width: 2cm,
+    ///   height: 1cm,
+    ///   stroke: 0.5pt,
+    ///   overflow: "shrink-text",
+    ///   lorem(8),
+    /// )
+    /// ```
+    #[default(Overflow::Visible)]
+    pub overflow: Overflow,
+
     /// The contents of the box.
     #[positional]
     pub body: Option<Content>,
@@ -109,7 +150,8 @@ impl Layout for BoxElem {
         regions: Regions,
     ) -> SourceResult<Fragment> {
         let width = match self.width(styles) {
-            Sizing::Auto => Smart::Auto,
+            // Minmax sizing is rejected when the box is constructed.
+            Sizing::Auto | Sizing::MinMax(_) => Smart::Auto,
             Sizing::Rel(rel) => Smart::Custom(rel),
             Sizing::Fr(_) => Smart::Custom(Ratio::one().into()),
         };
@@ -133,7 +175,39 @@ impl Layout for BoxElem {
         // Select the appropriate base and expansion for the child depending
         // on whether it is automatically or relatively sized.
         let pod = Regions::one(size, expand);
-        let mut frame = body.layout(vt, styles, pod)?.into_frame();
+        let overflow = self.overflow(styles);
+        let scale = match overflow {
+            Overflow::Visible => 1.0,
+            _ => fit(vt, styles, &body, size, expand)?,
+        };
+
+        let mut frame = if scale >= 1.0 {
+            body.layout(vt, styles, pod)?.into_frame()
+        } else {
+            match overflow {
+                Overflow::Error => {
+                    bail!(self.span(), "content does not fit into the box")
+                }
+                Overflow::ShrinkText => {
+                    let body = shrink_text(vt, styles, body, size, expand)?;
+                    body.layout(vt, styles, pod)?.into_frame()
+                }
+                _ => {
+                    // Along axes without a fixed size, the box takes the size
+                    // of the scaled content.
+                    let inner = body.layout(vt, styles, free(size, expand))?.into_frame();
+                    let baseline = inner.baseline() * scale;
+                    let mut frame = Frame::new(expand.select(size, inner.size() * scale));
+                    frame.set_baseline(baseline);
+
+                    let mut group = GroupItem::new(inner);
+                    group.transform =
+                        Transform::scale(Ratio::new(scale), Ratio::new(scale));
+                    frame.push(Point::zero(), FrameItem::Group(group));
+                    frame
+                }
+            }
+        };
 
         // Enforce correct size.
         *frame.size_mut() = expand.select(size, frame.size());
@@ -167,6 +241,82 @@ impl Layout for BoxElem {
     }
 }
 
+/// The regions in which to measure how much space content needs at the width
+/// of a box.
+fn free(size: Size, expand: Axes<bool>) -> Regions<'static> {
+    Regions::one(Size::new(size.x, Abs::inf()), Axes::new(expand.x, false))
+}
+
+/// By how much content must be scaled down to fit into a box's size along
+/// the axes on which that size is fixed.
+fn fit(
+    vt: &mut Vt,
+    styles: StyleChain,
+    body: &Content,
+    size: Size,
+    expand: Axes<bool>,
+) -> SourceResult<f64> {
+    let needed = body.measure(vt, styles, free(size, expand))?.into_frame().size();
+    let factor = |fixed: bool, space: Abs, needed: Abs| {
+        if fixed && !space.fits(needed) {
+            space / needed
+        } else {
+            1.0
+        }
+    };
+    Ok(factor(expand.x, size.x, needed.x).min(factor(expand.y, size.y, needed.y)))
+}
+
+/// The smallest fraction of its original size that `"shrink-text"` reduces
+/// the text size to.
+const MIN_TEXT_SCALE: f64 = 0.25;
+
+/// How many steps the search for the largest fitting text size takes.
+const TEXT_SCALE_STEPS: usize = 8;
+
+/// Reduce the text size of content until it fits into a box's size.
+fn shrink_text(
+    vt: &mut Vt,
+    styles: StyleChain,
+    body: Content,
+    size: Size,
+    expand: Axes<bool>,
+) -> SourceResult<Content> {
+    let scaled = |factor: f64| {
+        body.clone()
+            .styled(TextElem::set_size(TextSize(Em::new(factor).into())))
+    };
+
+    let (mut lo, mut hi) = (MIN_TEXT_SCALE, 1.0);
+    if fit(vt, styles, &scaled(lo), size, expand)? >= 1.0 {
+        for _ in 0..TEXT_SCALE_STEPS {
+            let mid = (lo + hi) / 2.0;
+            if fit(vt, styles, &scaled(mid), size, expand)? >= 1.0 {
+                lo = mid;
+            } else {
+                hi = mid;
+            }
+        }
+    }
+
+    Ok(scaled(lo))
+}
+
+/// What to do with content that doesn't fit into its container.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Cast)]
+pub enum Overflow {
+    /// The content extends beyond the container.
+    Visible,
+    /// The content is scaled down uniformly until it fits.
+    Shrink,
+    /// The text size is reduced until the content fits, down to a quarter of
+    /// its original size. Text with an absolute size set within the content
+    /// and non-text content keep their size.
+    ShrinkText,
+    /// The content produces an error.
+    Error,
+}
+
 /// A block-level container.
 ///
 /// Such a container can be used to separate content, size it, and give it a
@@ -460,6 +610,9 @@ pub enum Sizing {
     /// A track size specified as a fraction of the remaining free space in the
     /// parent.
     Fr(Fr),
+    /// A track size with a minimum and a maximum. Only supported for grid
+    /// tracks.
+    MinMax(MinMax),
 }
 
 impl Sizing {
@@ -490,8 +643,10 @@ cast! {
         Self::Auto => Value::Auto,
         Self::Rel(rel) => rel.into_value(),
         Self::Fr(fr) => fr.into_value(),
+        Self::MinMax(v) => v.into_value(),
     },
     _: AutoValue => Self::Auto,
     v: Rel<Length> => Self::Rel(v),
     v: Fr => Self::Fr(v),
+    v: MinMax => Self::MinMax(v),
 }
@@ -251,7 +251,11 @@ impl Layout for EnumElem {
             cells.push(Content::empty());
             cells.push(resolved);
             cells.push(Content::empty());
-            cells.push(item.body().styled(Self::set_parents(Parent(number))));
+            cells.push(
+                item.body()
+                    .role(Role::EnumItem)
+                    .styled(Self::set_parents(Parent(number))),
+            );
             number = number.saturating_add(1);
         }
 
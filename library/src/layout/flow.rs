@@ -1,7 +1,8 @@
 use std::mem;
 
 use super::{
-    AlignElem, BlockElem, ColbreakElem, ColumnsElem, ParElem, PlaceElem, Spacing, VElem,
+    AlignElem, BlockElem, ColbreakElem, ColumnsElem, Exclusion, PageElem, ParElem,
+    PauseElem, PlaceElem, Spacing, VElem,
 };
 use crate::meta::{FootnoteElem, FootnoteEntry};
 use crate::prelude::*;
@@ -68,6 +69,8 @@ impl Layout for FlowElem {
                 });
             } else if child.can::<dyn Layout>() {
                 layouter.layout_multiple(vt, child, styles)?;
+            } else if child.is::<PauseElem>() {
+                // Pauses are resolved by the page.
             } else if child.is::<ColbreakElem>() {
                 if !layouter.regions.backlog.is_empty() || layouter.regions.last.is_some()
                 {
@@ -105,6 +108,12 @@ struct FlowLayouter<'a> {
     footnote_config: FootnoteConfig,
     /// Finished frames for previous regions.
     finished: Vec<Frame>,
+    /// Space taken away by placed content that text wraps around, together
+    /// with the remaining region height at which it ends.
+    exclusion: Option<(Exclusion, Abs)>,
+    /// The distance between the lines of the baseline grid that lines of
+    /// text snap to. Only set for the root flow.
+    grid: Option<Abs>,
 }
 
 /// Cached footnote configuration.
@@ -166,6 +175,8 @@ impl<'a> FlowLayouter<'a> {
                 gap: FootnoteEntry::gap_in(styles),
             },
             finished: vec![],
+            exclusion: None,
+            grid: if root { PageElem::baseline_grid_in(styles) } else { None },
         }
     }
 
@@ -200,8 +211,19 @@ impl<'a> FlowLayouter<'a> {
         let aligns = AlignElem::alignment_in(styles).resolve(styles);
         let leading = ParElem::leading_in(styles);
         let consecutive = self.last_was_par;
+        let exclusion = self.exclusion.and_then(|(exclusion, end)| {
+            let height = self.regions.size.y - end;
+            (height > Abs::zero()).then_some(Exclusion { height, ..exclusion })
+        });
         let lines = par
-            .layout(vt, styles, consecutive, self.regions.base(), self.regions.expand.x)?
+            .layout(
+                vt,
+                styles,
+                consecutive,
+                self.regions.base(),
+                self.regions.expand.x,
+                exclusion,
+            )?
             .into_frames();
 
         let mut sticky = self.items.len();
@@ -228,6 +250,7 @@ impl<'a> FlowLayouter<'a> {
                 self.layout_item(vt, FlowItem::Absolute(leading, true))?;
             }
 
+            self.snap_to_grid(vt, &frame)?;
             self.layout_item(
                 vt,
                 FlowItem::Frame { frame, aligns, sticky: false, movable: true },
@@ -238,6 +261,38 @@ impl<'a> FlowLayouter<'a> {
         Ok(())
     }
 
+    /// Insert spacing such that the baseline of the given line lies on the
+    /// next line of the baseline grid.
+    fn snap_to_grid(&mut self, vt: &mut Vt, frame: &Frame) -> SourceResult<()> {
+        let Some(grid) = self.grid else { return Ok(()) };
+        if grid <= Abs::zero() || !self.initial.y.is_finite() {
+            return Ok(());
+        }
+
+        let mut pad = self.grid_offset(grid, frame);
+        if !self.regions.size.y.fits(pad + frame.height()) && !self.regions.in_last() {
+            self.finish_region()?;
+            pad = self.grid_offset(grid, frame);
+        }
+
+        if pad > Abs::zero() {
+            self.layout_item(vt, FlowItem::Absolute(pad, false))?;
+        }
+
+        Ok(())
+    }
+
+    /// How far a line would need to be moved down to put its baseline onto
+    /// the baseline grid.
+    fn grid_offset(&self, grid: Abs, frame: &Frame) -> Abs {
+        let baseline = self.initial.y - self.regions.size.y + frame.baseline();
+        let mut snapped = grid * (baseline / grid).ceil();
+        if (snapped - grid).approx_eq(baseline) {
+            snapped -= grid;
+        }
+        snapped - baseline
+    }
+
     /// Layout into a single region.
     #[tracing::instrument(name = "FlowLayouter::layout_single", skip_all)]
     fn layout_single(
@@ -270,6 +325,10 @@ impl<'a> FlowLayouter<'a> {
                 self.layout_item(vt, FlowItem::Placed(frame))?;
                 return Ok(());
             }
+
+            if placed.wrap(styles) && self.regions.size.y.is_finite() {
+                self.exclusion = self.measure_exclusion(vt, placed, styles)?;
+            }
         } else if self.regions.is_full() {
             // Skip directly if region is already full.
             self.finish_region()?;
@@ -456,9 +515,36 @@ impl<'a> FlowLayouter<'a> {
         self.regions.next();
         self.initial = self.regions.size;
         self.has_footnotes = false;
+        self.exclusion = None;
         Ok(())
     }
 
+    /// Determine the space that wrapping placed content takes away from the
+    /// following paragraphs.
+    fn measure_exclusion(
+        &self,
+        vt: &mut Vt,
+        placed: &PlaceElem,
+        styles: StyleChain,
+    ) -> SourceResult<Option<(Exclusion, Abs)>> {
+        let side = match placed.alignment(styles).x.map(|align| align.resolve(styles)) {
+            Some(Align::Left) => Align::Left,
+            Some(Align::Right) => Align::Right,
+            _ => return Ok(None),
+        };
+
+        let pod = Regions::one(self.regions.base(), Axes::splat(false));
+        let size = placed.body().measure(vt, styles, pod)?.into_frame().size();
+        let clearance = placed.clearance(styles);
+        let exclusion = Exclusion {
+            side,
+            width: size.x + clearance,
+            height: Abs::zero(),
+        };
+
+        Ok(Some((exclusion, self.regions.size.y - size.y - clearance)))
+    }
+
     /// Finish layouting and return the resulting fragment.
     fn finish(mut self) -> SourceResult<Fragment> {
         if self.expand.y {
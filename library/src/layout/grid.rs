@@ -1,7 +1,7 @@
 use crate::prelude::*;
 use crate::text::TextElem;
 
-use super::Sizing;
+use super::{Sizing, Spacing};
 
 /// Arranges content in a grid.
 ///
@@ -29,11 +29,34 @@ use super::Sizing;
 ///   with a fraction of `{1fr}`, they will each take up half of the remaining
 ///   space.
 ///
+/// - A [`minmax`]($func/minmax) track (e.g. `{minmax(3cm, 1fr)}`): The track
+///   will be at least as large as the minimum and otherwise behave like its
+///   maximum.
+///
 /// To specify a single track, the array can be omitted in favor of a single
 /// value. To specify multiple `{auto}` tracks, enter the number of tracks
 /// instead of an array. For example, `columns:` `{3}` is equivalent to
 /// `columns:` `{(auto, auto, auto)}`.
 ///
+/// ## Track sizing { #track-sizing }
+/// Columns are sized in four steps:
+///
+/// 1. Relative tracks are resolved and `minmax` tracks start out at their
+///    minimum. This space is reserved first.
+/// 2. `{auto}` tracks and `minmax` tracks with an `{auto}` or relative
+///    maximum are sized to fit their cells. A `minmax` track never becomes
+///    smaller than its minimum or larger than a relative maximum.
+/// 3. If the tracks from the previous step claim more than the remaining
+///    space, the `minmax` tracks fall back to their minimum and the `{auto}`
+///    tracks share the remaining space fairly.
+/// 4. Any space that is left is divided among the fractional tracks and the
+///    `minmax` tracks with a fractional maximum. If the share of a `minmax`
+///    track would be smaller than its minimum, it keeps its minimum and the
+///    rest is divided among the other tracks.
+///
+/// Rows are sized the same way, except that a `minmax` row is always sized
+/// to fit its cells within its bounds.
+///
 /// ## Example { #example }
 /// ```example
 /// #set text(10pt, style: "italic")
@@ -71,6 +94,24 @@ pub struct GridElem {
     /// column.
     pub columns: TrackSizings,
 
+    /// Whether to repeat the column tracks as often as they fit into the
+    /// available width.
+    ///
+    /// The width of each track is its relative size or the minimum of a
+    /// `minmax` track. Together with fractional maxima, this makes a grid
+    /// adapt the number of its columns to the page.
+    ///
+    /// ```example
+    /// #grid(
+    ///   columns: minmax(2cm, 1fr),
+    ///   auto-fill: true,
+    ///   gutter: 4pt,
+    ///   ..range(9).map(i => rect(width: 100%)[#i]),
+    /// )
+    /// ```
+    #[default(false)]
+    pub auto_fill: bool,
+
     /// The row sizes.
     ///
     /// If there are more cells than fit the defined rows, the last row is
@@ -94,6 +135,28 @@ pub struct GridElem {
     #[parse(args.named("row-gutter")?.or_else(|| gutter.clone()))]
     pub row_gutter: TrackSizings,
 
+    /// How many rows at the start of the grid form its header.
+    ///
+    /// When the grid breaks across pages or columns, the header rows are
+    /// repeated at the top of each continuation.
+    #[default(0)]
+    pub header_rows: usize,
+
+    /// How many rows at the end of the grid form its footer.
+    ///
+    /// When the grid breaks across pages or columns, the footer rows are
+    /// repeated at the bottom of each part of the grid.
+    #[default(0)]
+    pub footer_rows: usize,
+
+    /// Whether rows may break across pages or columns.
+    ///
+    /// If disabled, a row that does not fit into the remaining space is moved
+    /// to the next page as a whole. Rows that are taller than a full page are
+    /// still split.
+    #[default(true)]
+    pub breakable: bool,
+
     /// The contents of the grid cells.
     ///
     /// The cells are populated in row-major order.
@@ -109,15 +172,26 @@ impl Layout for GridElem {
         styles: StyleChain,
         regions: Regions,
     ) -> SourceResult<Fragment> {
+        let mut columns = self.columns(styles).0;
+        let column_gutter = self.column_gutter(styles).0;
+        if self.auto_fill(styles) {
+            columns =
+                auto_fill(&columns, &column_gutter, regions, styles).at(self.span())?;
+        }
+
         // Prepare grid layout by unifying content and gutter tracks.
         let cells = self.children();
         let layouter = GridLayouter::new(
-            Axes::new(&self.columns(styles).0, &self.rows(styles).0),
-            Axes::new(&self.column_gutter(styles).0, &self.row_gutter(styles).0),
+            Axes::new(&columns, &self.rows(styles).0),
+            Axes::new(&column_gutter, &self.row_gutter(styles).0),
             &cells,
             regions,
             styles,
-        );
+        )
+        .with_header(self.header_rows(styles))
+        .with_footer(self.footer_rows(styles))
+        .with_breakable(self.breakable(styles))
+        .with_span(self.span());
 
         // Measure the columns and layout the grid row-by-row.
         Ok(layouter.layout(vt)?.fragment)
@@ -136,6 +210,133 @@ cast! {
     values: Array => Self(values.into_iter().map(Value::cast).collect::<StrResult<_>>()?),
 }
 
+/// Creates a grid track with a minimum and a maximum size.
+///
+/// The track is at least as large as the minimum. Beyond that, it behaves
+/// like its maximum: With an `{auto}` or relative maximum, it is sized to fit
+/// its cells, but not larger than a relative maximum. With a fractional
+/// maximum, it takes its share of the remaining space. See the [grid
+/// documentation]($func/grid.track-sizing) for details.
+///
+/// ## Example { #example }
+/// ```example
+/// #table(
+///   columns: (minmax(3cm, auto), minmax(2cm, 1fr)),
+///   [Name], [Description],
+///   [Typst], [A markup-based typesetting system],
+/// )
+/// ```
+///
+/// Display: Min-Max
+/// Category: layout
+#[func]
+pub fn minmax(
+    /// The minimum size of the track.
+    min: Rel<Length>,
+    /// The maximum size of the track.
+    max: Smart<Spacing>,
+) -> MinMax {
+    MinMax { min, max }
+}
+
+/// A track size that is bounded from below.
+#[derive(Copy, Clone, Eq, PartialEq, Hash)]
+pub struct MinMax {
+    /// The minimum size.
+    pub min: Rel<Length>,
+    /// The maximum size.
+    pub max: Smart<Spacing>,
+}
+
+impl MinMax {
+    /// The fraction of the maximum, if it is fractional.
+    fn fr(self) -> Option<Fr> {
+        match self.max {
+            Smart::Custom(Spacing::Fr(v)) => Some(v),
+            _ => None,
+        }
+    }
+
+    /// Resolve the minimum and maximum size relative to a base size.
+    fn bounds(self, styles: StyleChain, base: Abs) -> (Abs, Abs) {
+        let min = self.min.resolve(styles).relative_to(base);
+        let max = match self.max {
+            Smart::Custom(Spacing::Rel(v)) => {
+                v.resolve(styles).relative_to(base).max(min)
+            }
+            _ => Abs::inf(),
+        };
+        (min, max)
+    }
+}
+
+impl Debug for MinMax {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "minmax({:?}, {:?})", self.min, self.max.into_value())
+    }
+}
+
+cast! {
+    type MinMax: "minmax",
+}
+
+/// Repeat column tracks as often as they fit into the available width.
+pub(super) fn auto_fill(
+    tracks: &[Sizing],
+    gutter: &[Sizing],
+    regions: Regions,
+    styles: StyleChain,
+) -> StrResult<Vec<Sizing>> {
+    let base = regions.base().x;
+    let width = |sizing: Sizing| match sizing {
+        Sizing::Rel(v) => Some(v.resolve(styles).relative_to(base)),
+        Sizing::MinMax(v) => Some(v.bounds(styles, base).0),
+        Sizing::Auto | Sizing::Fr(_) => None,
+    };
+
+    let widths = tracks
+        .iter()
+        .map(|&track| width(track))
+        .collect::<Option<Vec<_>>>()
+        .ok_or("auto-fill requires all columns to have a fixed or minimum size")?;
+
+    let available = regions.size.x;
+    if tracks.is_empty() || !available.is_finite() {
+        return Ok(tracks.to_vec());
+    }
+
+    // Add repetitions until the next one would overflow.
+    let gap = |x: usize| {
+        gutter
+            .get(x)
+            .or(gutter.last())
+            .copied()
+            .and_then(width)
+            .unwrap_or_default()
+    };
+    let mut used = Abs::zero();
+    let mut filled = vec![];
+    loop {
+        let mut next = used;
+        for (i, &w) in widths.iter().enumerate() {
+            let x = filled.len() + i;
+            if x > 0 {
+                next += gap(x - 1);
+            }
+            next += w;
+        }
+
+        if !filled.is_empty() && (next > available || next <= used) {
+            break;
+        }
+
+        filled.extend_from_slice(tracks);
+        used = next;
+    }
+
+    Ok(filled)
+}
+
 /// Performs grid layout.
 pub struct GridLayouter<'a> {
     /// The grid cells.
@@ -164,6 +365,18 @@ pub struct GridLayouter<'a> {
     initial: Size,
     /// Frames for finished regions.
     finished: Vec<Frame>,
+    /// The number of tracks at the start that form the header.
+    header: usize,
+    /// The laid out header rows, which are repeated in each region.
+    header_rows: Vec<(Frame, usize)>,
+    /// The number of tracks at the end that form the footer.
+    footer: usize,
+    /// The laid out footer rows, which are repeated in each region.
+    footer_rows: Vec<(Frame, usize)>,
+    /// Whether rows may break across regions.
+    breakable: bool,
+    /// The span of the grid, used for warnings about the grid as a whole.
+    span: Span,
 }
 
 /// The resulting sizes of columns and rows in a grid.
@@ -275,22 +488,58 @@ impl<'a> GridLayouter<'a> {
             lrows: vec![],
             initial: regions.size,
             finished: vec![],
+            header: 0,
+            header_rows: vec![],
+            footer: 0,
+            footer_rows: vec![],
+            breakable: true,
+            span: Span::detached(),
         }
     }
 
+    /// Set the span of the grid element.
+    pub fn with_span(mut self, span: Span) -> Self {
+        self.span = span;
+        self
+    }
+
+    /// Set whether rows may break across regions.
+    pub fn with_breakable(mut self, breakable: bool) -> Self {
+        self.breakable = breakable;
+        self
+    }
+
+    /// Repeat the first `rows` content rows at the top of each region.
+    pub fn with_header(mut self, rows: usize) -> Self {
+        let tracks = if self.has_gutter { 2 * rows } else { rows };
+        self.header = tracks.min(self.rows.len());
+        self
+    }
+
+    /// Repeat the last `rows` content rows at the bottom of each region.
+    ///
+    /// Must be called after [`with_header`](Self::with_header).
+    pub fn with_footer(mut self, rows: usize) -> Self {
+        // Including the gutter track in front of the footer.
+        let tracks = if self.has_gutter { 2 * rows } else { rows };
+        self.footer = tracks.min(self.rows.len() - self.header);
+        self
+    }
+
     /// Determines the columns sizes and then layouts the grid row-by-row.
     pub fn layout(mut self, vt: &mut Vt) -> SourceResult<GridLayout> {
         self.measure_columns(vt)?;
+        self.layout_footer(vt)?;
 
-        for y in 0..self.rows.len() {
+        for y in 0..self.rows.len() - self.footer {
             // Skip to next region if current one is full, but only for content
             // rows, not for gutter rows.
             if self.regions.is_full() && (!self.has_gutter || y % 2 == 0) {
-                self.finish_region(vt)?;
+                self.next_region(vt, y)?;
             }
 
             match self.rows[y] {
-                Sizing::Auto => self.layout_auto_row(vt, y)?,
+                Sizing::Auto | Sizing::MinMax(_) => self.layout_auto_row(vt, y)?,
                 Sizing::Rel(v) => self.layout_relative_row(vt, v, y)?,
                 Sizing::Fr(v) => self.lrows.push(Row::Fr(v, y)),
             }
@@ -325,6 +574,14 @@ impl<'a> GridLayouter<'a> {
                     *rcol = resolved;
                     rel += resolved;
                 }
+                Sizing::MinMax(v) => {
+                    let (min, _) = v.bounds(self.styles, self.regions.base().x);
+                    *rcol = min;
+                    rel += min;
+                    if let Some(v) = v.fr() {
+                        fr += v;
+                    }
+                }
                 Sizing::Fr(v) => fr += v,
             }
         }
@@ -335,9 +592,15 @@ impl<'a> GridLayouter<'a> {
             // Determine size of auto columns.
             let (auto, count) = self.measure_auto_columns(vt, available)?;
 
+            // If the auto columns don't fit, minmax columns give up the space
+            // they claimed beyond their minimum first.
+            let mut remaining = available - auto;
+            if remaining < Abs::zero() {
+                remaining += self.collapse_minmax_columns();
+            }
+
             // If there is remaining space, distribute it to fractional columns,
             // otherwise shrink auto columns.
-            let remaining = available - auto;
             if remaining >= Abs::zero() {
                 self.grow_fractional_columns(remaining, fr);
             } else {
@@ -352,6 +615,10 @@ impl<'a> GridLayouter<'a> {
     }
 
     /// Measure the size that is available to auto columns.
+    ///
+    /// Also sizes minmax columns without a fractional maximum. For those, only
+    /// the size beyond their minimum counts towards the returned size, while
+    /// the count only includes true auto columns.
     fn measure_auto_columns(
         &mut self,
         vt: &mut Vt,
@@ -363,9 +630,13 @@ impl<'a> GridLayouter<'a> {
         // Determine size of auto columns by laying out all cells in those
         // columns, measuring them and finding the largest one.
         for (x, &col) in self.cols.iter().enumerate() {
-            if col != Sizing::Auto {
-                continue;
-            }
+            let bounds = match col {
+                Sizing::Auto => None,
+                Sizing::MinMax(v) if v.fr().is_none() => {
+                    Some(v.bounds(self.styles, self.regions.base().x))
+                }
+                _ => continue,
+            };
 
             let mut resolved = Abs::zero();
             for y in 0..self.rows.len() {
@@ -386,25 +657,82 @@ impl<'a> GridLayouter<'a> {
                 }
             }
 
-            self.rcols[x] = resolved;
-            auto += resolved;
-            count += 1;
+            if let Some((min, max)) = bounds {
+                resolved = resolved.min(max).max(min);
+                self.rcols[x] = resolved;
+                auto += resolved - min;
+            } else {
+                self.rcols[x] = resolved;
+                auto += resolved;
+                count += 1;
+            }
         }
 
         Ok((auto, count))
     }
 
+    /// Reset content-sized minmax columns to their minimum and return how much
+    /// space that freed.
+    fn collapse_minmax_columns(&mut self) -> Abs {
+        let mut freed = Abs::zero();
+        for (&col, rcol) in self.cols.iter().zip(&mut self.rcols) {
+            if let Sizing::MinMax(v) = col {
+                if v.fr().is_none() {
+                    let (min, _) = v.bounds(self.styles, self.regions.base().x);
+                    freed += *rcol - min;
+                    *rcol = min;
+                }
+            }
+        }
+        freed
+    }
+
     /// Distribute remaining space to fractional columns.
+    ///
+    /// Minmax columns with a fractional maximum currently hold their minimum,
+    /// which they give back into the shared space. Those whose share is
+    /// smaller than their minimum keep it and drop out of the distribution.
     fn grow_fractional_columns(&mut self, remaining: Abs, fr: Fr) {
         if fr.is_zero() {
             return;
         }
 
-        for (&col, rcol) in self.cols.iter().zip(&mut self.rcols) {
-            if let Sizing::Fr(v) = col {
-                *rcol = v.share(fr, remaining);
+        let mut fr = fr;
+        let mut remaining = remaining;
+        let mut fixed = vec![false; self.cols.len()];
+        for (&col, &rcol) in self.cols.iter().zip(&self.rcols) {
+            if matches!(col, Sizing::MinMax(v) if v.fr().is_some()) {
+                remaining += rcol;
             }
         }
+
+        // Iteratively fix columns whose share doesn't reach their minimum.
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for (x, (&col, &rcol)) in self.cols.iter().zip(&self.rcols).enumerate() {
+                let Sizing::MinMax(v) = col else { continue };
+                let Some(v) = v.fr() else { continue };
+                if !fixed[x] && v.share(fr, remaining) < rcol {
+                    fixed[x] = true;
+                    fr -= v;
+                    remaining -= rcol;
+                    changed = true;
+                }
+            }
+        }
+
+        for (x, (&col, rcol)) in self.cols.iter().zip(&mut self.rcols).enumerate() {
+            let v = match col {
+                Sizing::Fr(v) => v,
+                Sizing::MinMax(v) if !fixed[x] => match v.fr() {
+                    Some(v) => v,
+                    None => continue,
+                },
+                _ => continue,
+            };
+            *rcol = v.share(fr, remaining);
+        }
     }
 
     /// Redistribute space to auto columns so that each gets a fair share.
@@ -448,16 +776,56 @@ impl<'a> GridLayouter<'a> {
         let mut resolved = match self.measure_auto_row(vt, y, true)? {
             Some(resolved) => resolved,
             None => {
-                self.finish_region(vt)?;
+                self.next_region(vt, y)?;
                 self.measure_auto_row(vt, y, false)?.unwrap()
             }
         };
 
+        // Keep minmax rows within their bounds. Rows that break across
+        // regions are only kept from being smaller than their minimum.
+        if let Sizing::MinMax(v) = self.rows[y] {
+            let (min, max) = v.bounds(self.styles, self.regions.base().y);
+            if let [single] = resolved.as_mut_slice() {
+                *single = single.min(max).max(min);
+            } else if let Some(last) = resolved.last_mut() {
+                last.set_max(min);
+            } else {
+                resolved.push(min);
+            }
+        }
+
         // Nothing to layout.
         if resolved.is_empty() {
             return Ok(());
         }
 
+        // Keep unbreakable rows together by moving them to the next region.
+        // Only rows that don't even fit into a full region are still split.
+        if !self.breakable && resolved.len() > 1 {
+            if self.lrows.len() > self.header_rows.len() {
+                self.next_region(vt, y)?;
+                resolved = self.measure_auto_row(vt, y, false)?.unwrap();
+            }
+
+            if resolved.len() > 1 {
+                // Point to the first cell of the row or, if that's generated,
+                // to the grid itself.
+                let span = (0..self.cols.len())
+                    .filter_map(|x| self.cell(x, y))
+                    .map(Content::span)
+                    .find(|span| !span.is_detached())
+                    .unwrap_or(self.span);
+
+                if !span.is_detached() {
+                    let message = "row is too tall to fit into one region";
+                    let hint = "it is split although the grid is not breakable";
+                    vt.warn(
+                        SourceError::warning(span, message).with_hints([hint.into()]),
+                    );
+                }
+            }
+        }
+
         // Layout into a single region.
         if let &[first] = resolved.as_slice() {
             let frame = self.layout_single_row(vt, first, y)?;
@@ -465,16 +833,20 @@ impl<'a> GridLayouter<'a> {
             return Ok(());
         }
 
-        // Expand all but the last region.
+        // Expand all but the last region. Following regions lose the space of
+        // the repeated rows.
         // Skip the first region if the space is eaten up by an fr row.
         let len = resolved.len();
-        for (region, target) in self
+        let repeated = self.repeated_height(y);
+        for (i, (region, target)) in self
             .regions
             .iter()
             .zip(&mut resolved[..len - 1])
+            .enumerate()
             .skip(self.lrows.iter().any(|row| matches!(row, Row::Fr(..))) as usize)
         {
-            target.set_max(region.y);
+            let height = if i == 0 { region.y } else { region.y - repeated };
+            target.set_max(height);
         }
 
         // Layout into multiple regions.
@@ -483,7 +855,7 @@ impl<'a> GridLayouter<'a> {
         for (i, frame) in fragment.into_iter().enumerate() {
             self.push_row(frame, y);
             if i + 1 < len {
-                self.finish_region(vt)?;
+                self.next_region(vt, y)?;
             }
         }
 
@@ -500,9 +872,19 @@ impl<'a> GridLayouter<'a> {
     ) -> SourceResult<Option<Vec<Abs>>> {
         let mut resolved: Vec<Abs> = vec![];
 
+        // Following regions lose the space of the repeated rows.
+        let repeated = self.repeated_height(y);
+        let mut backlog = vec![];
+        let mut regions = self
+            .regions
+            .map(&mut backlog, |size| Size::new(size.x, size.y - repeated));
+        regions.size = self.regions.size;
+        regions.full = self.regions.full;
+        regions.root = self.regions.root;
+
         for (x, &rcol) in self.rcols.iter().enumerate() {
             if let Some(cell) = self.cell(x, y) {
-                let mut pod = self.regions;
+                let mut pod = regions;
                 pod.size.x = rcol;
 
                 let frames = cell.measure(vt, self.styles, pod)?.into_frames();
@@ -546,7 +928,7 @@ impl<'a> GridLayouter<'a> {
         // Skip to fitting region.
         let height = frame.height();
         while !self.regions.size.y.fits(height) && !self.regions.in_last() {
-            self.finish_region(vt)?;
+            self.next_region(vt, y)?;
 
             // Don't skip multiple regions for gutter and don't push a row.
             if self.has_gutter && y % 2 == 1 {
@@ -573,7 +955,7 @@ impl<'a> GridLayouter<'a> {
             if let Some(cell) = self.cell(x, y) {
                 let size = Size::new(rcol, height);
                 let mut pod = Regions::one(size, Axes::splat(true));
-                if self.rows[y] == Sizing::Auto {
+                if matches!(self.rows[y], Sizing::Auto | Sizing::MinMax(_)) {
                     pod.full = self.regions.full;
                 }
                 let frame = cell.layout(vt, self.styles, pod)?.into_frame();
@@ -624,14 +1006,91 @@ impl<'a> GridLayouter<'a> {
         Ok(Fragment::frames(outputs))
     }
 
+    /// Lay out the footer rows once, so that they can be repeated in each
+    /// region.
+    fn layout_footer(&mut self, vt: &mut Vt) -> SourceResult<()> {
+        for y in self.rows.len() - self.footer..self.rows.len() {
+            let base = self.regions.base().y;
+            let height = match self.rows[y] {
+                Sizing::Rel(v) => v.resolve(self.styles).relative_to(base),
+                sizing => {
+                    let mut height = Abs::zero();
+                    for (x, &rcol) in self.rcols.iter().enumerate() {
+                        if let Some(cell) = self.cell(x, y) {
+                            let size = Size::new(rcol, base);
+                            let pod = Regions::one(size, Axes::splat(false));
+                            let frame = cell.measure(vt, self.styles, pod)?.into_frame();
+                            height.set_max(frame.height());
+                        }
+                    }
+                    if let Sizing::MinMax(v) = sizing {
+                        let (min, max) = v.bounds(self.styles, base);
+                        height = height.min(max).max(min);
+                    }
+                    height
+                }
+            };
+
+            let frame = self.layout_single_row(vt, height, y)?;
+            self.regions.size.y -= frame.height();
+            self.footer_rows.push((frame, y));
+        }
+
+        Ok(())
+    }
+
+    /// The height of the rows that are repeated in each region following the
+    /// one that row `y` starts in.
+    fn repeated_height(&self, y: usize) -> Abs {
+        let header = if y >= self.header { self.header_rows.as_slice() } else { &[] };
+        header
+            .iter()
+            .chain(&self.footer_rows)
+            .map(|(frame, _)| frame.height())
+            .sum()
+    }
+
+    /// Finish the current region and prepare the next one for row `y`.
+    ///
+    /// Unless row `y` is itself part of the header, the header is repeated at
+    /// the top of the new region.
+    fn next_region(&mut self, vt: &mut Vt, y: usize) -> SourceResult<()> {
+        self.finish_region(vt)?;
+        if y >= self.header {
+            self.repeat_header();
+        }
+        Ok(())
+    }
+
+    /// Push the header rows into a new region.
+    fn repeat_header(&mut self) {
+        if self.finished.is_empty() {
+            return;
+        }
+
+        for (frame, y) in self.header_rows.clone() {
+            self.regions.size.y -= frame.height();
+            self.lrows.push(Row::Frame(frame, y));
+        }
+    }
+
     /// Push a row frame into the current region.
     fn push_row(&mut self, frame: Frame, y: usize) {
+        if y < self.header {
+            self.header_rows.push((frame.clone(), y));
+        }
+
         self.regions.size.y -= frame.height();
         self.lrows.push(Row::Frame(frame, y));
     }
 
     /// Finish rows for one region.
     fn finish_region(&mut self, vt: &mut Vt) -> SourceResult<()> {
+        // Close the region with the footer.
+        for (frame, y) in self.footer_rows.clone() {
+            self.lrows.push(Row::Frame(frame, y));
+        }
+
         // Determine the height of existing rows in the region.
         let mut used = Abs::zero();
         let mut fr = Fr::zero();
@@ -675,6 +1134,9 @@ impl<'a> GridLayouter<'a> {
         self.rrows.push(rrows);
         self.regions.next();
         self.initial = self.regions.size;
+        for (frame, _) in &self.footer_rows {
+            self.regions.size.y -= frame.height();
+        }
 
         Ok(())
     }
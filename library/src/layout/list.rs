@@ -147,7 +147,7 @@ impl Layout for ListElem {
             cells.push(Content::empty());
             cells.push(marker.clone());
             cells.push(Content::empty());
-            cells.push(item.body().styled(Self::set_depth(Depth)));
+            cells.push(item.body().role(Role::ListItem).styled(Self::set_depth(Depth)));
         }
 
         let layouter = GridLayouter::new(
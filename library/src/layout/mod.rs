@@ -54,7 +54,7 @@ use typst::model::DelayedErrors;
 use typst::model::{applicable, realize, StyleVecBuilder};
 
 use crate::math::{EquationElem, LayoutMath};
-use crate::meta::DocumentElem;
+use crate::meta::{detect_links, DocumentElem, LinkElem};
 use crate::prelude::*;
 use crate::shared::BehavedBuilder;
 use crate::text::{LinebreakElem, SmartQuoteElem, SpaceElem, TextElem};
@@ -67,9 +67,11 @@ use crate::visualize::{
 pub(super) fn define(global: &mut Scope) {
     global.define("page", PageElem::func());
     global.define("pagebreak", PagebreakElem::func());
+    global.define("pause", PauseElem::func());
     global.define("v", VElem::func());
     global.define("par", ParElem::func());
     global.define("parbreak", ParbreakElem::func());
+    global.define("dropcap", DropCapElem::func());
     global.define("h", HElem::func());
     global.define("box", BoxElem::func());
     global.define("block", BlockElem::func());
@@ -79,6 +81,7 @@ pub(super) fn define(global: &mut Scope) {
     global.define("table", TableElem::func());
     global.define("stack", StackElem::func());
     global.define("grid", GridElem::func());
+    global.define("minmax", minmax_func());
     global.define("columns", ColumnsElem::func());
     global.define("colbreak", ColbreakElem::func());
     global.define("place", PlaceElem::func());
@@ -153,6 +156,49 @@ impl LayoutRoot for Content {
 }
 
 /// Layout into regions.
+///
+/// This is the extension point for custom layouters. An element defined in a
+/// downstream crate with `#[element(Layout)]` and registered in the
+/// [library](typst::eval::Library) participates in block-level layout like any
+/// built-in element: The flow hands it the remaining [`Regions`] and expects
+/// one [`Frame`] per region it occupies back.
+///
+/// A custom layouter typically
+/// - layouts its children with [`Layout::layout`] into a [`Regions::one`] pod
+///   (or [`Layout::measure`] them first when it needs to probe sizes),
+/// - positions the resulting frames in a new [`Frame`] with
+///   [`push_frame`](Frame::push_frame), and
+/// - moves on to the next region with [`Regions::next`] once the current one
+///   is full.
+///
+/// ```ignore
+/// impl Layout for MasonryElem {
+///     fn layout(
+///         &self,
+///         vt: &mut Vt,
+///         styles: StyleChain,
+///         regions: Regions,
+///     ) -> SourceResult<Fragment> {
+///         let width = regions.size.x / 2.0;
+///         let pod = Regions::one(Size::new(width, Abs::inf()), Axes::splat(false));
+///         let mut heights = [Abs::zero(); 2];
+///         let mut frames = vec![];
+///         for child in self.children() {
+///             let column = if heights[0] <= heights[1] { 0 } else { 1 };
+///             let sub = child.layout(vt, styles, pod)?.into_frame();
+///             let pos = Point::new(width * column as f64, heights[column]);
+///             heights[column] += sub.height();
+///             frames.push((pos, sub));
+///         }
+///         let height = heights[0].max(heights[1]);
+///         let mut frame = Frame::new(Size::new(regions.size.x, height));
+///         for (pos, sub) in frames {
+///             frame.push_frame(pos, sub);
+///         }
+///         Ok(Fragment::frame(frame))
+///     }
+/// }
+/// ```
 pub trait Layout {
     /// Layout into one frame per region.
     fn layout(
@@ -165,7 +211,8 @@ pub trait Layout {
     /// Layout without side effects.
     ///
     /// This element must be layouted again in the same order for the results to
-    /// be valid.
+    /// be valid. Warnings raised while measuring are discarded as the real
+    /// layout raises them again.
     #[tracing::instrument(name = "Layout::measure", skip_all)]
     fn measure(
         &self,
@@ -174,14 +221,22 @@ pub trait Layout {
         regions: Regions,
     ) -> SourceResult<Fragment> {
         let mut locator = Locator::chained(vt.locator.track());
-        let mut vt = Vt {
-            world: vt.world,
-            introspector: vt.introspector,
-            locator: &mut locator,
-            tracer: TrackedMut::reborrow_mut(&mut vt.tracer),
-            delayed: TrackedMut::reborrow_mut(&mut vt.delayed),
-        };
-        self.layout(&mut vt, styles, regions)
+        let mut delayed = DelayedErrors::default();
+        let result = self.layout(
+            &mut Vt {
+                world: vt.world,
+                introspector: vt.introspector,
+                locator: &mut locator,
+                tracer: TrackedMut::reborrow_mut(&mut vt.tracer),
+                delayed: delayed.track_mut(),
+            },
+            styles,
+            regions,
+        );
+        for error in delayed.into_errors() {
+            vt.delayed.push(error);
+        }
+        result
     }
 }
 
@@ -339,11 +394,25 @@ impl<'a, 'v, 't> Builder<'a, 'v, 't> {
             return self.accept(stored, styles);
         }
 
+        if let Some(text) = content.to::<TextElem>() {
+            if LinkElem::detect_in(styles) {
+                if let Some(linked) = detect_links(&text.text(), content.span()) {
+                    let stored = self.scratch.content.alloc(linked);
+                    return self.accept(stored, styles);
+                }
+            }
+        }
+
         if let Some((elem, local)) = content.to_styled() {
             return self.styled(elem, local, styles);
         }
 
         if let Some(children) = content.to_sequence() {
+            if let Some(joined) = join_text(content, styles) {
+                let stored = self.scratch.content.alloc(joined);
+                return self.accept(stored, styles);
+            }
+
             for elem in children {
                 self.accept(elem, styles)?;
             }
@@ -471,7 +540,9 @@ impl<'a, 'v, 't> Builder<'a, 'v, 't> {
             } else {
                 shared
             };
-            let page = PageElem::new(FlowElem::new(flow.to_vec()).pack());
+            // Only the document's first pages start a run with a first page.
+            let mut page = PageElem::new(FlowElem::new(flow.to_vec()).pack());
+            page.push_continued(!doc.pages.is_empty());
             let stored = self.scratch.content.alloc(page.pack());
             self.accept(stored, styles)?;
         }
@@ -479,6 +550,121 @@ impl<'a, 'v, 't> Builder<'a, 'v, 't> {
     }
 }
 
+/// Join runs of text elements in a sequence into single text elements where
+/// this is needed for link detection or text show rules.
+///
+/// Markup creates separate text elements around escapes and for words that
+/// are separated by a line break or multiple spaces in the source. Only
+/// pristine and unlabeled text and space elements are joined, in two cases:
+/// - When link detection is enabled, directly adjacent text elements are
+///   joined, so that an email address written as `hello\@typst.app` is
+///   detected even though it consists of three elements.
+/// - When a regex show rule matches across several elements, the whole run
+///   of words and spaces is joined, so that a rule like
+///   `{show "Typst Pro": ..}` also matches if the words end up on different
+///   lines in the source.
+///
+/// A joined element only carries the span of its first word, so
+/// click-to-source and errors point at that word. Returns `None` if nothing
+/// needs to be joined.
+fn join_text(seq: &Content, styles: StyleChain) -> Option<Content> {
+    let regexes: Vec<_> = styles
+        .recipes()
+        .filter_map(|recipe| match &recipe.selector {
+            Some(Selector::Regex(regex)) => Some(regex),
+            _ => None,
+        })
+        .collect();
+
+    let links = LinkElem::detect_in(styles);
+    if regexes.is_empty() && !links {
+        return None;
+    }
+
+    // Only join elements that no show rule was applied to yet.
+    let joinable = |child: &Content| {
+        (child.is::<TextElem>() || child.is::<SpaceElem>())
+            && child.is_pristine()
+            && child.label().is_none()
+    };
+
+    let mut children = vec![];
+    let mut run = vec![];
+    let mut joined = false;
+    let mut flush = |run: &mut Vec<&Content>, children: &mut Vec<Content>| {
+        // The text of the run alongside the start offset of each child.
+        let mut text = EcoString::new();
+        let mut starts = vec![];
+        for child in run.iter() {
+            starts.push(text.len());
+            match child.to::<TextElem>() {
+                Some(elem) => text.push_str(&elem.text()),
+                None => text.push(' '),
+            }
+        }
+
+        // Whether a match starts in one child and ends in another.
+        let crosses = |start: usize, end: usize| {
+            starts.iter().any(|&offset| start < offset && offset < end)
+        };
+
+        if regexes
+            .iter()
+            .any(|regex| regex.find_iter(&text).any(|m| crosses(m.start(), m.end())))
+        {
+            children.push(join(run));
+            joined = true;
+        } else if links {
+            for words in run.split_inclusive(|child| child.is::<SpaceElem>()) {
+                let (words, space) = match words.split_last() {
+                    Some((last, rest)) if last.is::<SpaceElem>() => (rest, Some(last)),
+                    _ => (words, None),
+                };
+
+                if words.len() > 1 {
+                    children.push(join(words));
+                    joined = true;
+                } else {
+                    children.extend(words.iter().map(|&child| child.clone()));
+                }
+
+                children.extend(space.map(|&child| child.clone()));
+            }
+        } else {
+            children.extend(run.iter().map(|&child| child.clone()));
+        }
+
+        run.clear();
+    };
+
+    for child in seq.to_sequence()? {
+        if joinable(child) {
+            run.push(child);
+        } else {
+            flush(&mut run, &mut children);
+            children.push(child.clone());
+        }
+    }
+
+    flush(&mut run, &mut children);
+    joined.then(|| Content::sequence(children))
+}
+
+/// Join text and space elements into a single text element with the span of
+/// the first element that has one.
+fn join(run: &[&Content]) -> Content {
+    let mut text = EcoString::new();
+    for child in run {
+        match child.to::<TextElem>() {
+            Some(elem) => text.push_str(&elem.text()),
+            None => text.push(' '),
+        }
+    }
+
+    let span = run.iter().map(|child| child.span()).find(|span| !span.is_detached());
+    TextElem::packed(text).spanned(span.unwrap_or_else(Span::detached))
+}
+
 /// Accepts pagebreaks and pages.
 struct DocBuilder<'a> {
     /// The page runs built so far.
@@ -542,6 +728,7 @@ impl<'a> FlowBuilder<'a> {
         if content.is::<VElem>()
             || content.is::<ColbreakElem>()
             || content.is::<MetaElem>()
+            || content.is::<PauseElem>()
         {
             self.0.push(content.clone(), styles);
             return true;
@@ -586,7 +773,7 @@ struct ParBuilder<'a>(BehavedBuilder<'a>);
 
 impl<'a> ParBuilder<'a> {
     fn accept(&mut self, content: &'a Content, styles: StyleChain<'a>) -> bool {
-        if content.is::<MetaElem>() {
+        if content.is::<MetaElem>() || content.is::<PauseElem>() {
             if !self.0.is_basically_empty() {
                 self.0.push(content.clone(), styles);
                 return true;
@@ -598,6 +785,7 @@ impl<'a> ParBuilder<'a> {
             || content.is::<SmartQuoteElem>()
             || content.to::<EquationElem>().map_or(false, |elem| !elem.block(styles))
             || content.is::<BoxElem>()
+            || content.is::<DropCapElem>()
         {
             self.0.push(content.clone(), styles);
             return true;
@@ -1,6 +1,8 @@
 use std::ptr;
 use std::str::FromStr;
 
+use typst::eval::Arg;
+
 use super::{AlignElem, ColumnsElem};
 use crate::meta::{Counter, CounterKey, Numbering};
 use crate::prelude::*;
@@ -154,6 +156,33 @@ pub struct PageElem {
     #[default(NonZeroUsize::ONE)]
     pub columns: NonZeroUsize,
 
+    /// The distance between the lines of the page's baseline grid.
+    ///
+    /// If set, each line of text is moved down such that its baseline lies on
+    /// the next line of the grid. This keeps lines in adjacent columns aligned
+    /// even if there are headings or spacing between them. The grid starts at
+    /// the top of the page's body and works best if it matches the distance
+    /// between two lines of text.
+    ///
+    /// ```example:single
+    /// #set page(
+    ///   columns: 2,
+    ///   height: 4.8cm,
+    ///   baseline-grid: 10.5pt,
+    /// )
+    /// #set par(leading: 3.5pt)
+    /// = Climate
+    /// Climate change is one of the most
+    /// pressing issues of our time.
+    ///
+    /// #v(6pt)
+    /// It's clear that we need to take
+    /// urgent action to reduce our carbon
+    /// emissions.
+    /// ```
+    #[resolve]
+    pub baseline_grid: Option<Length>,
+
     /// The page's background color.
     ///
     /// This instructs the printer to color the complete page with the given
@@ -280,6 +309,70 @@ pub struct PageElem {
     /// ```
     pub foreground: Option<Content>,
 
+    /// Page properties that only apply to the first page of this run of
+    /// pages, for example to omit the header on a title page.
+    ///
+    /// The dictionary can contain the page's `margin`, `fill`, `numbering`,
+    /// `number-align`, `header`, `header-ascent`, `footer`, `footer-descent`,
+    /// `background` and `foreground`. The first page's margins must leave as
+    /// much horizontal space for the content as the other pages' margins.
+    ///
+    /// The first page of a run is the first page of the document or of a
+    /// [page]($func/page) call's body. Thus, a chapter that is wrapped in a
+    /// page call can also have a different first page. Pages that follow an
+    /// explicit [pagebreak]($func/pagebreak) or a change of the page's set
+    /// rules continue the run and use the regular properties.
+    ///
+    /// ```example
+    /// #set page(
+    ///   height: 100pt,
+    ///   numbering: "1",
+    ///   first: (
+    ///     numbering: none,
+    ///     margin: (top: 40pt),
+    ///   ),
+    /// )
+    ///
+    /// = Title
+    /// #lorem(30)
+    /// ```
+    #[parse(match args.named::<Spanned<Option<Dict>>>("first")? {
+        Some(Spanned { v: Some(dict), span }) => {
+            Some(Some(first_page_properties(dict, span)?))
+        }
+        Some(Spanned { v: None, .. }) => Some(None),
+        None => None,
+    })]
+    pub first: Option<Dict>,
+
+    /// How far the page's fill extends beyond its trimmed size on each side.
+    ///
+    /// When a document is professionally printed, it is printed on larger
+    /// sheets that are trimmed afterwards. To avoid white edges after
+    /// trimming, colored areas that touch the page edge must extend beyond it
+    /// into the bleed. The exported PDF pages grow by the bleed and mark the
+    /// trimmed and bleed areas with `TrimBox` and `BleedBox` entries.
+    ///
+    /// ```example
+    /// #set page(fill: aqua, bleed: 3mm)
+    /// This page is printed on a
+    /// slightly larger sheet.
+    /// ```
+    #[resolve]
+    pub bleed: Length,
+
+    /// Whether to draw crop marks around the page.
+    ///
+    /// The marks are drawn in an additional area outside of the bleed and
+    /// show the printer where to trim the page.
+    ///
+    /// ```example
+    /// #set page(bleed: 3mm, marks: true)
+    /// Trim me!
+    /// ```
+    #[default(false)]
+    pub marks: bool,
+
     /// The contents of the page(s).
     ///
     /// Multiple pages will be created if the content does not fit on a single
@@ -292,6 +385,19 @@ pub struct PageElem {
     /// Not part of the public API for now.
     #[internal]
     pub clear_to: Option<Parity>,
+
+    /// Whether this run of pages continues the document's pages after a
+    /// pagebreak or a change of the page's set rules, so that it has no first
+    /// page. Not part of the public API for now.
+    #[internal]
+    pub continued: bool,
+
+    /// The number of the document's first page. Embedders can set this in
+    /// the library's styles to continue the page numbers of another document.
+    /// Not part of the public API for now.
+    #[internal]
+    #[default(NonZeroUsize::ONE)]
+    pub first_number: NonZeroUsize,
 }
 
 impl PageElem {
@@ -324,15 +430,12 @@ impl PageElem {
             min = Paper::A4.width();
         }
 
-        // Determine the margins.
-        let default = Rel::<Length>::from(0.1190 * min);
-        let margin = self.margin(styles);
-        let two_sided = margin.two_sided.unwrap_or(false);
-        let margin = margin
-            .sides
-            .map(|side| side.and_then(Smart::as_custom).unwrap_or(default))
-            .resolve(styles)
-            .relative_to(size);
+        // Determine the setup of the first and the other pages.
+        let rest = self.setup(styles, size, min);
+        let first = self
+            .first(styles)
+            .filter(|_| !self.continued(styles))
+            .map(|properties| self.with_properties(properties).setup(styles, size, min));
 
         // Determine the binding.
         let binding =
@@ -349,84 +452,116 @@ impl PageElem {
             child = ColumnsElem::new(child).with_count(columns).pack();
         }
 
-        let area = size - margin.sum_by_axis();
+        // Align the child to the pagebreak's parity. The blank page is the
+        // first page of the run then.
+        let blank = self.clear_to(styles).is_some_and(|p| !p.matches(number.get()));
+
+        let area = size - rest.margin.sum_by_axis();
         let mut regions = Regions::repeat(area, area.map(Abs::is_finite));
         regions.root = true;
 
-        // Layout the child.
-        let mut frames = child.layout(vt, styles, regions)?.into_frames();
+        // The first page's margins determine the first region. All regions
+        // share the same width.
+        let first_area = first.as_ref().map(|first| size - first.margin.sum_by_axis());
+        if let Some(first_area) = first_area.filter(|_| !blank) {
+            if first_area.x != area.x {
+                bail!(
+                    self.span(),
+                    "the first page must leave the same width for its content as the \
+                     other pages"
+                );
+            }
+            regions.size = first_area;
+            regions.full = first_area.y;
+            regions.last = Some(area.y);
+        }
+
+        // Layout the child, once more for each pause it contains. Only the
+        // last step is introspected, so that the elements of the page are
+        // found just once.
+        let mut frames = vec![];
+        let steps = count_pauses(&child);
+        for step in 0..steps {
+            let revealed = reveal(&child, step, &mut 0);
+            for mut frame in revealed.measure(vt, styles, regions)?.into_frames() {
+                frame.strip_elems();
+                frames.push(frame);
+            }
+        }
+        frames.extend(child.layout(vt, styles, regions)?.into_frames());
 
-        // Align the child to the pagebreak's parity.
-        if self.clear_to(styles).is_some_and(|p| !p.matches(number.get())) {
+        if blank {
+            let area = first_area.unwrap_or(area);
             let size = area.map(Abs::is_finite).select(area, Size::zero());
             frames.insert(0, Frame::new(size));
         }
 
-        let fill = self.fill(styles);
-        let foreground = self.foreground(styles);
-        let background = self.background(styles);
-        let header = self.header(styles);
-        let header_ascent = self.header_ascent(styles);
-        let footer = self.footer(styles).or_else(|| {
-            self.numbering(styles).map(|numbering| {
-                let both = match &numbering {
-                    Numbering::Pattern(pattern) => pattern.pieces() >= 2,
-                    Numbering::Func(_) => true,
-                };
-                Counter::new(CounterKey::Page)
-                    .display(Some(numbering), both)
-                    .aligned(self.number_align(styles))
-            })
-        });
-        let footer_descent = self.footer_descent(styles);
-
-        let numbering_meta = FrameItem::Meta(
-            Meta::PageNumbering(self.numbering(styles).into_value()),
-            Size::zero(),
-        );
+        let bleed = self.bleed(styles);
+        let marks = self.marks(styles);
 
         // Post-process pages.
-        for frame in frames.iter_mut() {
+        for (i, frame) in frames.iter_mut().enumerate() {
             tracing::info!("Layouting page #{number}");
 
+            let setup = match &first {
+                Some(first) if i == 0 => first,
+                _ => &rest,
+            };
+
             // The padded width of the page's content without margins.
             let pw = frame.width();
 
             // If two sided, left becomes inside and right becomes outside.
             // Thus, for left-bound pages, we want to swap on even pages and
             // for right-bound pages, we want to swap on odd pages.
-            let mut margin = margin;
-            if two_sided && binding.swap(number) {
+            let mut margin = setup.margin;
+            if setup.two_sided && binding.swap(number) {
                 std::mem::swap(&mut margin.left, &mut margin.right);
             }
 
             // Realize margins.
             frame.set_size(frame.size() + margin.sum_by_axis());
             frame.translate(Point::new(margin.left, margin.top));
-            frame.push(Point::zero(), numbering_meta.clone());
+            frame.push(
+                Point::zero(),
+                FrameItem::Meta(
+                    Meta::PageNumbering(setup.numbering.clone().into_value()),
+                    Size::zero(),
+                ),
+            );
+
+            // Record the formatted page number.
+            if let Some(numbering) = &setup.numbering {
+                let label = page_label(vt, numbering, number)?;
+                frame.push(
+                    Point::zero(),
+                    FrameItem::Meta(Meta::PageLabel(label), Size::zero()),
+                );
+            }
 
             // The page size with margins.
             let size = frame.size();
 
             // Realize overlays.
+            let PageSetup { header, footer, background, foreground, .. } = setup;
             for (name, marginal) in [
-                ("header", &header),
-                ("footer", &footer),
-                ("background", &background),
-                ("foreground", &foreground),
+                ("header", header),
+                ("footer", footer),
+                ("background", background),
+                ("foreground", foreground),
             ] {
                 tracing::info!("Layouting {name}");
 
                 let Some(content) = marginal else { continue };
 
                 let (pos, area, align);
-                if ptr::eq(marginal, &header) {
-                    let ascent = header_ascent.relative_to(margin.top);
+                if ptr::eq(marginal, header) {
+                    let ascent = setup.header_ascent.relative_to(margin.top);
                     pos = Point::with_x(margin.left);
                     area = Size::new(pw, margin.top - ascent);
                     align = Align::Bottom.into();
-                } else if ptr::eq(marginal, &footer) {
-                    let descent = footer_descent.relative_to(margin.bottom);
+                } else if ptr::eq(marginal, footer) {
+                    let descent = setup.footer_descent.relative_to(margin.bottom);
                     pos = Point::new(margin.left, size.y - margin.bottom + descent);
                     area = Size::new(pw, margin.bottom - descent);
                     align = Align::Top.into();
@@ -443,14 +578,16 @@ impl PageElem {
                     .layout(vt, styles, pod)?
                     .into_frame();
 
-                if ptr::eq(marginal, &header) || ptr::eq(marginal, &background) {
+                if ptr::eq(marginal, header) || ptr::eq(marginal, background) {
                     frame.prepend_frame(pos, sub);
                 } else {
                     frame.push_frame(pos, sub);
                 }
             }
 
-            if let Some(fill) = &fill {
+            if !bleed.is_zero() || marks {
+                extend_into_bleed(frame, setup.fill.as_ref(), bleed, marks);
+            } else if let Some(fill) = &setup.fill {
                 frame.fill(fill.clone());
             }
 
@@ -459,6 +596,168 @@ impl PageElem {
 
         Ok(Fragment::frames(frames))
     }
+
+    /// Determine the properties that can differ between the pages of a run.
+    fn setup(&self, styles: StyleChain, size: Size, min: Abs) -> PageSetup {
+        let default = Rel::<Length>::from(0.1190 * min);
+        let margin = self.margin(styles);
+        let two_sided = margin.two_sided.unwrap_or(false);
+        let margin = margin
+            .sides
+            .map(|side| side.and_then(Smart::as_custom).unwrap_or(default))
+            .resolve(styles)
+            .relative_to(size);
+
+        let numbering = self.numbering(styles);
+        let footer = self.footer(styles).or_else(|| {
+            numbering.clone().map(|numbering| {
+                let both = match &numbering {
+                    Numbering::Pattern(pattern) => pattern.pieces() >= 2,
+                    Numbering::Func(_) => true,
+                };
+                Counter::new(CounterKey::Page)
+                    .display(Some(numbering), both)
+                    .aligned(self.number_align(styles))
+            })
+        });
+
+        PageSetup {
+            margin,
+            two_sided,
+            fill: self.fill(styles),
+            header: self.header(styles),
+            header_ascent: self.header_ascent(styles),
+            footer,
+            footer_descent: self.footer_descent(styles),
+            background: self.background(styles),
+            foreground: self.foreground(styles),
+            numbering,
+        }
+    }
+
+    /// A copy of this page with the given properties set explicitly.
+    fn with_properties(&self, properties: Dict) -> Self {
+        let mut elem = self.clone().pack();
+        for (name, value) in properties {
+            elem.push_field(name, value);
+        }
+        elem.to::<Self>().unwrap().clone()
+    }
+}
+
+/// Determine the label of the physical page with the given number.
+fn page_label(
+    vt: &mut Vt,
+    numbering: &Numbering,
+    page: NonZeroUsize,
+) -> SourceResult<PageLabel> {
+    let number = Counter::new(CounterKey::Page).at_page(vt, page)?.first();
+    Ok(match numbering {
+        Numbering::Pattern(pattern) => pattern.page_label(number),
+        Numbering::Func(func) => PageLabel {
+            prefix: func.call_vt(vt, [number])?.display().plain_text(),
+            style: None,
+            number,
+        },
+    })
+}
+
+/// The properties of a page that can differ between the first and the other
+/// pages of a run of pages.
+struct PageSetup {
+    margin: Sides<Abs>,
+    two_sided: bool,
+    fill: Option<Paint>,
+    header: Option<Content>,
+    header_ascent: Rel<Abs>,
+    footer: Option<Content>,
+    footer_descent: Rel<Abs>,
+    background: Option<Content>,
+    foreground: Option<Content>,
+    numbering: Option<Numbering>,
+}
+
+/// The page properties that can differ on the first page of a run.
+const FIRST_PAGE_PROPERTIES: &[&str] = &[
+    "margin",
+    "fill",
+    "numbering",
+    "number-align",
+    "header",
+    "header-ascent",
+    "footer",
+    "footer-descent",
+    "background",
+    "foreground",
+];
+
+/// Check page properties for the first page of a run.
+fn first_page_properties(dict: Dict, span: Span) -> SourceResult<Dict> {
+    if let Some((name, _)) = dict
+        .iter()
+        .find(|(name, _)| !FIRST_PAGE_PROPERTIES.contains(&name.as_str()))
+    {
+        bail!(span, "page property `{name}` cannot differ on the first page");
+    }
+
+    // Make sure that all values have the right type.
+    let items = dict
+        .iter()
+        .map(|(name, value)| Arg {
+            span,
+            name: Some(name.clone()),
+            value: Spanned::new(value.clone(), span),
+        })
+        .collect();
+    PageElem::func().set(Args { span, items })?;
+
+    Ok(dict)
+}
+
+/// How much space crop marks take up outside of the bleed.
+const MARKS_AREA: Abs = Abs::raw(18.0);
+
+/// Grow a finished page by its bleed and the area for crop marks.
+fn extend_into_bleed(frame: &mut Frame, fill: Option<&Paint>, bleed: Abs, marks: bool) {
+    let trim = frame.size();
+    let slug = if marks { MARKS_AREA } else { Abs::zero() };
+    let outset = bleed + slug;
+
+    frame.set_size(trim + Size::splat(2.0 * outset));
+    frame.translate(Point::splat(outset));
+    frame.push(Point::splat(outset), FrameItem::Meta(Meta::PageTrim(bleed), trim));
+
+    // The fill covers the bleed, but not the area for the marks.
+    if let Some(fill) = fill {
+        let shape = Geometry::Rect(trim + Size::splat(2.0 * bleed)).filled(fill.clone());
+        frame.prepend(Point::splat(slug), FrameItem::Shape(shape, Span::detached()));
+    }
+
+    if !marks {
+        return;
+    }
+
+    // Draw two marks at each corner that extend the trim edges into the
+    // area outside of the bleed.
+    let stroke = Stroke {
+        paint: Color::BLACK.into(),
+        thickness: Abs::pt(0.25),
+        ..Stroke::default()
+    };
+    let len = slug - Abs::pt(3.0);
+    let size = frame.size();
+    for x in [outset, outset + trim.x] {
+        for y in [Abs::zero(), size.y - len] {
+            let line = Geometry::Line(Point::with_y(len)).stroked(stroke.clone());
+            frame.push(Point::new(x, y), FrameItem::Shape(line, Span::detached()));
+        }
+    }
+    for y in [outset, outset + trim.y] {
+        for x in [Abs::zero(), size.x - len] {
+            let line = Geometry::Line(Point::with_x(len)).stroked(stroke.clone());
+            frame.push(Point::new(x, y), FrameItem::Shape(line, Span::detached()));
+        }
+    }
 }
 
 /// Specification of the page's margins.
@@ -664,6 +963,102 @@ pub struct PagebreakElem {
     pub to: Option<Parity>,
 }
 
+/// Reveals the content behind it only on the next page.
+///
+/// A page that contains pauses is exported several times: Once for each pause
+/// and once more in full. The first version shows everything up to the first
+/// pause, the second one everything up to the second pause and so on. Hidden
+/// content still takes up its space, so that the visible content stays in
+/// place across the versions. This is useful for presentation slides that
+/// reveal their content step by step.
+///
+/// ## Example { #example }
+/// ```example
+/// #set page(width: 120pt, height: 50pt)
+/// First, there is this.
+/// #pause
+/// Then, there is that.
+/// ```
+///
+/// Each version is a page of its own with its own page number. Pauses within
+/// content that is produced by [`locate`]($func/locate),
+/// [`style`]($func/style) or [`layout`]($func/layout) have no effect.
+///
+/// Display: Pause
+/// Category: layout
+#[element(Behave)]
+pub struct PauseElem {}
+
+impl Behave for PauseElem {
+    fn behaviour(&self) -> Behaviour {
+        Behaviour::Ignorant
+    }
+}
+
+/// Count the pauses in content.
+fn count_pauses(content: &Content) -> usize {
+    if content.is::<PauseElem>() {
+        1
+    } else if let Some(children) = content.to_sequence() {
+        children.map(count_pauses).sum()
+    } else if let Some((child, _)) = content.to_styled() {
+        count_pauses(child)
+    } else {
+        content.fields_ref().map(|(_, value)| count_pauses_in(value)).sum()
+    }
+}
+
+/// Count the pauses in the content within a field value.
+fn count_pauses_in(value: &Value) -> usize {
+    match value {
+        Value::Content(content) => count_pauses(content),
+        Value::Array(array) => array.iter().map(count_pauses_in).sum(),
+        _ => 0,
+    }
+}
+
+/// Hide the content behind the pause with the zero-based index `step`.
+/// `seen` counts the pauses that precede the content.
+fn reveal(content: &Content, step: usize, seen: &mut usize) -> Content {
+    if content.is::<PauseElem>() {
+        *seen += 1;
+        return content.clone();
+    }
+
+    if count_pauses(content) == 0 {
+        return if *seen > step {
+            content.clone().styled(MetaElem::set_data(vec![Meta::Hide]))
+        } else {
+            content.clone()
+        };
+    }
+
+    if let Some(children) = content.to_sequence() {
+        return Content::sequence(children.map(|child| reveal(child, step, seen)));
+    }
+
+    if let Some((child, map)) = content.to_styled() {
+        return reveal(child, step, seen).styled_with_map(map.clone());
+    }
+
+    let mut revealed = content.clone();
+    for (name, value) in content.fields_ref() {
+        revealed.push_field(name.clone(), reveal_in(value, step, seen));
+    }
+    revealed
+}
+
+/// Hide the content within a field value that lies behind a pause.
+fn reveal_in(value: &Value, step: usize, seen: &mut usize) -> Value {
+    match value {
+        Value::Content(content) => Value::Content(reveal(content, step, seen)),
+        Value::Array(array) => {
+            Value::Array(array.iter().map(|v| reveal_in(v, step, seen)).collect())
+        }
+        _ => value.clone(),
+    }
+}
+
 /// Whether something should be even or odd.
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Cast)]
 pub enum Parity {
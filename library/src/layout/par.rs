@@ -4,18 +4,18 @@ use icu_provider_adapters::fork::ForkByKeyProvider;
 use icu_provider_blob::BlobDataProvider;
 use icu_segmenter::{LineBreakIteratorUtf8, LineSegmenter};
 use once_cell::sync::Lazy;
-use typst::eval::Tracer;
+use typst::eval::{Arg, Tracer};
 use typst::model::DelayedErrors;
 use unicode_bidi::{BidiInfo, Level as BidiLevel};
 use unicode_script::{Script, UnicodeScript};
 
-use super::{BoxElem, HElem, Sizing, Spacing};
+use super::{BoxElem, HElem, PauseElem, Sizing, Spacing};
 use crate::layout::AlignElem;
 use crate::math::EquationElem;
 use crate::prelude::*;
 use crate::text::{
     is_gb_style, shape, LinebreakElem, Quoter, Quotes, ShapedText, SmartQuoteElem,
-    SpaceElem, TextElem,
+    SpaceElem, TextDir, TextElem, TextSize, Whitespace,
 };
 
 /// Arranges text, spacing and inline-level elements into a paragraph.
@@ -107,6 +107,77 @@ pub struct ParElem {
     #[resolve]
     pub hanging_indent: Length,
 
+    /// The minimum number of lines at the start of a paragraph that are kept
+    /// together when the paragraph breaks across pages or columns.
+    ///
+    /// Since headings stick to the paragraph that follows them, this also
+    /// determines how many lines of that paragraph must fit below a heading.
+    /// If fewer lines fit, the heading moves to the next page together with
+    /// its paragraph instead of ending up alone at the bottom of the page.
+    ///
+    /// ```example
+    /// #set page(height: 120pt)
+    /// #set par(orphans: 3)
+    /// #v(50pt)
+    ///
+    /// = Heading
+    /// #lorem(20)
+    /// ```
+    #[default(NonZeroUsize::new(2).unwrap())]
+    pub orphans: NonZeroUsize,
+
+    /// The positions of tab stops in the paragraph.
+    ///
+    /// A tab character advances the text to the next tab stop, measured from
+    /// the start of its line. When given a length, tab stops are placed at
+    /// regular intervals of that length. When given an array of lengths, tab
+    /// stops are placed at exactly these positions and tabs behind the last
+    /// stop don't add any space. When set to `{none}`, tabs are shaped like
+    /// any other character. This is the default.
+    ///
+    /// ```example
+    /// #set par(tab-stops: (3em, 7em))
+    /// Name#"\t"Typst#"\t"(tool) \
+    /// Kind#"\t"Software
+    /// ```
+    pub tab_stops: Option<TabStops>,
+
+    /// The base direction of the paragraph.
+    ///
+    /// When `{auto}`, the paragraph takes its direction from the [text
+    /// direction]($func/text.dir). Otherwise, it determines the order of
+    /// mixed-direction runs within the lines, which side the lines start on and
+    /// on which side indents and drop caps are placed, without changing the
+    /// text direction outside of the paragraph.
+    ///
+    /// ```example
+    /// #set par(dir: rtl)
+    /// Typst is written in Rust: "Typst هو مكتوب في Rust".
+    /// ```
+    #[default]
+    pub dir: TextDir,
+
+    /// Text properties that only apply to the first line of the paragraph.
+    ///
+    /// The dictionary can contain any of the [text]($func/text) function's
+    /// properties. Since they may change the width of the text, the paragraph
+    /// is broken into lines again after applying them.
+    ///
+    /// ```example
+    /// #set page(width: 200pt)
+    /// #set par(first-line: (
+    ///   weight: "bold",
+    ///   fill: eastern,
+    /// ))
+    /// #lorem(20)
+    /// ```
+    #[parse(match args.named::<Spanned<Option<Dict>>>("first-line")? {
+        Some(Spanned { v: Some(dict), span }) => Some(Some(text_styles(dict, span)?)),
+        Some(Spanned { v: None, .. }) => Some(None),
+        None => None,
+    })]
+    pub first_line: Option<Styles>,
+
     /// The contents of the paragraph.
     #[external]
     #[required]
@@ -143,6 +214,7 @@ impl ParElem {
         consecutive: bool,
         region: Size,
         expand: bool,
+        exclusion: Option<Exclusion>,
     ) -> SourceResult<Fragment> {
         #[comemo::memoize]
         #[allow(clippy::too_many_arguments)]
@@ -157,6 +229,7 @@ impl ParElem {
             consecutive: bool,
             region: Size,
             expand: bool,
+            exclusion: Option<Exclusion>,
         ) -> SourceResult<Fragment> {
             let mut locator = Locator::chained(locator);
             let mut vt = Vt {
@@ -167,14 +240,78 @@ impl ParElem {
                 tracer,
             };
             let children = par.children();
+            let first_line = ParElem::first_line_in(styles);
+
+            // Let a paragraph direction override the text direction.
+            let local = match ParElem::dir_in(styles) {
+                TextDir(Smart::Auto) => Styles::new(),
+                dir => TextElem::set_dir(dir).into(),
+            };
+            let styles = styles.chain(&local);
 
             // Collect all text into one string for BiDi analysis.
-            let (text, segments, spans) = collect(&children, &styles, consecutive)?;
+            let (text, segments, spans, dropcap) =
+                collect(&children, &styles, consecutive)?;
 
             // Perform BiDi analysis and then prepare paragraph layout by building a
             // representation on which we can do line breaking without layouting
             // each and every line from scratch.
-            let p = prepare(&mut vt, &children, &text, segments, spans, styles, region)?;
+            let mut p = prepare(
+                &mut vt,
+                &children,
+                &text,
+                segments.clone(),
+                spans.clone(),
+                dropcap,
+                styles,
+                region,
+            )?;
+
+            // Determine which lines need to make room for the exclusion.
+            if let Some(exclusion) = exclusion {
+                wrap(&mut vt, &mut p, exclusion, region)?;
+            }
+
+            // Style the first line and break again until its end is stable.
+            // If the end keeps changing, for example because the styled text
+            // doesn't fit but the unstyled one does, we settle on the
+            // shortest end seen so far. Then, only text that is certainly on
+            // the first line is styled.
+            if let Some(local) = &first_line {
+                let mut end = None;
+                let mut seen = vec![];
+                loop {
+                    let next =
+                        linebreak(&vt, &p, region.x - p.hang).first().map(|l| l.end);
+                    if next == end {
+                        break;
+                    }
+
+                    let settle =
+                        seen.contains(&next) || seen.len() + 1 >= FIRST_LINE_ATTEMPTS;
+                    seen.push(next);
+                    end =
+                        if settle { seen.iter().flatten().min().copied() } else { next };
+                    p = prepare(
+                        &mut vt,
+                        &children,
+                        &text,
+                        restyle(&segments, end.unwrap_or(0), local),
+                        spans.clone(),
+                        dropcap,
+                        styles,
+                        region,
+                    )?;
+
+                    if let Some(exclusion) = exclusion {
+                        wrap(&mut vt, &mut p, exclusion, region)?;
+                    }
+
+                    if settle {
+                        break;
+                    }
+                }
+            }
 
             // Break the paragraph into lines.
             let lines = linebreak(&vt, &p, region.x - p.hang);
@@ -194,6 +331,7 @@ impl ParElem {
             consecutive,
             region,
             expand,
+            exclusion,
         )?;
 
         vt.locator.visit_frames(&fragment);
@@ -201,6 +339,35 @@ impl ParElem {
     }
 }
 
+/// How often to break a paragraph into lines again when looking for the end of
+/// its styled first line before settling on the shortest end.
+const FIRST_LINE_ATTEMPTS: usize = 3;
+
+/// Turn a dictionary of text properties into styles.
+fn text_styles(dict: Dict, span: Span) -> SourceResult<Styles> {
+    let items = dict
+        .into_iter()
+        .map(|(name, value)| Arg {
+            span,
+            name: Some(name),
+            value: Spanned::new(value, span),
+        })
+        .collect();
+    TextElem::func().set(Args { span, items })
+}
+
+/// Horizontal space next to the top of a paragraph that the paragraph's lines
+/// must leave free, for example for placed content that text wraps around.
+#[derive(Debug, Copy, Clone, PartialEq, Hash)]
+pub struct Exclusion {
+    /// The side on which the space is taken away, either left or right.
+    pub side: Align,
+    /// How much horizontal space is taken away.
+    pub width: Abs,
+    /// How far the space extends down from the top of the paragraph.
+    pub height: Abs,
+}
+
 /// How to determine line breaks in a paragraph.
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Cast)]
 pub enum Linebreaks {
@@ -213,6 +380,41 @@ pub enum Linebreaks {
     Optimized,
 }
 
+/// Where tab characters in a paragraph advance to.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub enum TabStops {
+    /// Tab stops at a regular interval.
+    Interval(Length),
+    /// Tab stops at explicit positions.
+    Positions(Vec<Length>),
+}
+
+impl TabStops {
+    /// The position of the first tab stop behind the horizontal position `x`.
+    fn next_after(&self, x: Abs, styles: StyleChain) -> Option<Abs> {
+        match self {
+            Self::Interval(interval) => {
+                let interval = interval.resolve(styles);
+                (interval > Abs::zero())
+                    .then(|| interval * ((x / interval).floor() + 1.0))
+            }
+            Self::Positions(stops) => {
+                stops.iter().map(|stop| stop.resolve(styles)).find(|&stop| stop > x)
+            }
+        }
+    }
+}
+
+cast! {
+    TabStops,
+    self => match self {
+        Self::Interval(interval) => interval.into_value(),
+        Self::Positions(stops) => stops.into_value(),
+    },
+    v: Length => Self::Interval(v),
+    v: Vec<Length> => Self::Positions(v),
+}
+
 /// A paragraph break.
 ///
 /// This starts a new paragraph. Especially useful when used within code like
@@ -239,6 +441,46 @@ pub struct ParbreakElem {}
 
 impl Unlabellable for ParbreakElem {}
 
+/// An enlarged initial letter that spans multiple lines of a paragraph.
+///
+/// The drop cap must be placed at the very start of a paragraph. It is scaled
+/// such that its top aligns with the first line and its baseline with the last
+/// line it spans. The lines next to it are shortened to make room for it.
+///
+/// ## Example { #example }
+/// ```example
+/// #set page(width: 220pt)
+/// #dropcap[T]his is a paragraph
+/// that starts with a large initial
+/// letter. The first few lines wrap
+/// around it. #lorem(20)
+/// ```
+///
+/// Display: Drop Cap
+/// Category: layout
+#[element]
+pub struct DropCapElem {
+    /// How many lines the drop cap spans.
+    ///
+    /// ```example
+    /// #set page(width: 220pt)
+    /// #dropcap(lines: 2)[O]nce upon a
+    /// time, there was a paragraph
+    /// with a small drop cap. #lorem(10)
+    /// ```
+    #[default(NonZeroUsize::new(3).unwrap())]
+    pub lines: NonZeroUsize,
+
+    /// The space between the drop cap and the text next to it.
+    #[resolve]
+    #[default(Em::new(0.5).into())]
+    pub gap: Length,
+
+    /// The content of the drop cap, typically a single letter.
+    #[required]
+    pub body: Content,
+}
+
 /// Range of a substring of text.
 type Range = std::ops::Range<usize>;
 
@@ -272,6 +514,14 @@ struct Preparation<'a> {
     justify: bool,
     /// The paragraph's hanging indent.
     hang: Abs,
+    /// The paragraph's tab stops.
+    tabs: Option<TabStops>,
+    /// The paragraph's drop cap.
+    dropcap: Option<DropCap>,
+    /// Space that the paragraph's first lines leave free.
+    exclusion: Option<Exclusion>,
+    /// How many lines lie next to the exclusion.
+    wrapped: usize,
 }
 
 impl<'a> Preparation<'a> {
@@ -316,6 +566,62 @@ impl<'a> Preparation<'a> {
 
         (expanded, &self.items[start..end])
     }
+
+    /// How far a tab at the horizontal position `x` advances to reach the
+    /// next tab stop.
+    fn tab_advance(&self, x: Abs) -> Abs {
+        self.tabs
+            .as_ref()
+            .and_then(|tabs| tabs.next_after(x, self.styles))
+            .map_or(Abs::zero(), |stop| stop - x)
+    }
+
+    /// By how much the line with the given index is shortened in total.
+    fn inset(&self, line: usize) -> Abs {
+        let (left, right) = self.insets(line);
+        left + right
+    }
+
+    /// By how much the line with the given index is shortened on the left
+    /// and on the right to make room for a drop cap or an exclusion.
+    fn insets(&self, line: usize) -> (Abs, Abs) {
+        let mut left = Abs::zero();
+        let mut right = Abs::zero();
+
+        match &self.dropcap {
+            Some(cap) if line < cap.lines => {
+                if TextElem::dir_in(self.styles).is_positive() {
+                    left += cap.inset;
+                } else {
+                    right += cap.inset;
+                }
+            }
+            _ => {}
+        }
+
+        match &self.exclusion {
+            Some(exclusion) if line < self.wrapped => {
+                if exclusion.side == Align::Right {
+                    right.set_max(exclusion.width);
+                } else {
+                    left.set_max(exclusion.width);
+                }
+            }
+            _ => {}
+        }
+
+        (left, right)
+    }
+}
+
+/// A laid out drop cap.
+struct DropCap {
+    /// The drop cap's frame.
+    frame: Frame,
+    /// How many lines are shortened next to the drop cap.
+    lines: usize,
+    /// By how much these lines are shortened.
+    inset: Abs,
 }
 
 /// A segment of one or multiple collapsed children.
@@ -358,6 +664,8 @@ enum Item<'a> {
     Fractional(Fr, Option<(&'a BoxElem, StyleChain<'a>)>),
     /// Layouted inline-level content.
     Frame(Frame),
+    /// A tab at the given text offset that advances to the next tab stop.
+    Tab(usize),
     /// Metadata.
     Meta(Frame),
 }
@@ -384,6 +692,7 @@ impl<'a> Item<'a> {
             Self::Text(shaped) => shaped.text.len(),
             Self::Absolute(_) | Self::Fractional(_, _) => SPACING_REPLACE.len_utf8(),
             Self::Frame(_) => OBJ_REPLACE.len_utf8(),
+            Self::Tab(_) => '\t'.len_utf8(),
             Self::Meta(_) => 0,
         }
     }
@@ -394,14 +703,14 @@ impl<'a> Item<'a> {
             Self::Text(shaped) => shaped.width,
             Self::Absolute(v) => *v,
             Self::Frame(frame) => frame.width(),
-            Self::Fractional(_, _) | Self::Meta(_) => Abs::zero(),
+            Self::Fractional(_, _) | Self::Tab(_) | Self::Meta(_) => Abs::zero(),
         }
     }
 }
 
 /// Maps byte offsets back to spans.
-#[derive(Default)]
-pub struct SpanMapper(Vec<(usize, Span)>);
+#[derive(Default, Clone)]
+pub struct SpanMapper(Vec<(usize, Span, usize)>);
 
 impl SpanMapper {
     /// Create a new span mapper.
@@ -411,7 +720,13 @@ impl SpanMapper {
 
     /// Push a span for a segment with the given length.
     pub fn push(&mut self, len: usize, span: Span) {
-        self.0.push((len, span));
+        self.push_at(len, span, 0);
+    }
+
+    /// Push a span for a segment with the given length that starts at the
+    /// given byte offset within the span's text.
+    pub fn push_at(&mut self, len: usize, span: Span, offset: usize) {
+        self.0.push((len, span, offset));
     }
 
     /// Determine the span at the given byte offset.
@@ -419,14 +734,30 @@ impl SpanMapper {
     /// May return a detached span.
     pub fn span_at(&self, offset: usize) -> (Span, u16) {
         let mut cursor = 0;
-        for &(len, span) in &self.0 {
+        for &(len, span, start) in &self.0 {
             if (cursor..=cursor + len).contains(&offset) {
-                return (span, u16::try_from(offset - cursor).unwrap_or(0));
+                return (span, u16::try_from(start + offset - cursor).unwrap_or(0));
             }
             cursor += len;
         }
         (Span::detached(), 0)
     }
+
+    /// Determine the span of the segment that starts at or contains the given
+    /// byte offset.
+    ///
+    /// In contrast to [`span_at`](Self::span_at), an offset at the boundary
+    /// of two segments belongs to the later one. May return a detached span.
+    pub fn span_from(&self, offset: usize) -> Span {
+        let mut cursor = 0;
+        for &(len, span, _) in &self.0 {
+            if (cursor..cursor + len).contains(&offset) {
+                return span;
+            }
+            cursor += len;
+        }
+        Span::detached()
+    }
 }
 
 /// A layouted line, consisting of a sequence of layouted paragraph items that
@@ -454,6 +785,8 @@ struct Line<'a> {
     last: Option<Item<'a>>,
     /// The width of the line.
     width: Abs,
+    /// How far the line's tabs advance, keyed by their text offset.
+    tabs: Vec<(usize, Abs)>,
     /// Whether the line should be justified.
     justify: bool,
     /// Whether the line ends with a hyphen or dash, either naturally or through
@@ -462,6 +795,14 @@ struct Line<'a> {
 }
 
 impl<'a> Line<'a> {
+    /// How far the tab at the given text offset advances.
+    fn tab_advance(&self, at: usize) -> Abs {
+        self.tabs
+            .iter()
+            .find(|&&(offset, _)| offset == at)
+            .map_or(Abs::zero(), |&(_, advance)| advance)
+    }
+
     /// Iterate over the line's items.
     fn items(&self) -> impl Iterator<Item = &Item<'a>> {
         self.first.iter().chain(self.inner).chain(&self.last)
@@ -540,11 +881,18 @@ fn collect<'a>(
     children: &'a [Content],
     styles: &'a StyleChain<'a>,
     consecutive: bool,
-) -> SourceResult<(String, Vec<(Segment<'a>, StyleChain<'a>)>, SpanMapper)> {
+) -> SourceResult<(
+    String,
+    Vec<(Segment<'a>, StyleChain<'a>)>,
+    SpanMapper,
+    Option<(&'a DropCapElem, StyleChain<'a>)>,
+)> {
     let mut full = String::new();
     let mut quoter = Quoter::new();
     let mut segments = vec![];
     let mut spans = SpanMapper::new();
+    let mut dropcap = None;
+    let mut first = true;
     let mut iter = children.iter().peekable();
 
     let first_line_indent = ParElem::first_line_indent_in(*styles);
@@ -564,6 +912,7 @@ fn collect<'a>(
     }
 
     while let Some(mut child) = iter.next() {
+        let at_start = std::mem::replace(&mut first, false);
         let outer = styles;
         let mut styles = *styles;
         if let Some((elem, local)) = child.to_styled() {
@@ -571,16 +920,30 @@ fn collect<'a>(
             styles = outer.chain(local);
         }
 
-        let segment = if child.is::<SpaceElem>() {
-            full.push(' ');
-            Segment::Text(1)
+        // The pieces of text whose offsets within the child differ from their
+        // offsets in the full text because whitespace was collapsed.
+        let mut pieces = None;
+        let segment = if let Some(elem) = child.to::<DropCapElem>() {
+            if !at_start {
+                bail!(child.span(), "drop cap must be at the start of a paragraph");
+            }
+            dropcap = Some((elem, styles));
+            continue;
+        } else if child.is::<SpaceElem>() {
+            let prev = full.len();
+            TextElem::whitespace_in(styles).apply(&mut full, " ");
+            if full.len() == prev {
+                continue;
+            }
+            Segment::Text(full.len() - prev)
         } else if let Some(elem) = child.to::<TextElem>() {
             let prev = full.len();
-            if let Some(case) = TextElem::case_in(styles) {
-                full.push_str(&case.apply(&elem.text()));
+            let whitespace = TextElem::whitespace_in(styles);
+            pieces = Some(if let Some(case) = TextElem::case_in(styles) {
+                whitespace.apply(&mut full, &case.apply(&elem.text()))
             } else {
-                full.push_str(&elem.text());
-            }
+                whitespace.apply(&mut full, &elem.text())
+            });
             Segment::Text(full.len() - prev)
         } else if let Some(elem) = child.to::<HElem>() {
             if elem.amount().is_zero() {
@@ -633,6 +996,9 @@ fn collect<'a>(
             Segment::Box(elem, frac)
         } else if child.is::<MetaElem>() {
             Segment::Meta
+        } else if child.is::<PauseElem>() {
+            // Pauses are resolved by the page.
+            continue;
         } else {
             bail!(child.span(), "unexpected paragraph child");
         };
@@ -641,7 +1007,14 @@ fn collect<'a>(
             quoter.last(last);
         }
 
-        spans.push(segment.len(), child.span());
+        match pieces {
+            Some(pieces) => {
+                for (len, offset) in pieces {
+                    spans.push_at(len, child.span(), offset);
+                }
+            }
+            None => spans.push(segment.len(), child.span()),
+        }
 
         if let (Some((Segment::Text(last_len), last_styles)), Segment::Text(len)) =
             (segments.last_mut(), segment)
@@ -655,17 +1028,43 @@ fn collect<'a>(
         segments.push((segment, styles));
     }
 
-    Ok((full, segments, spans))
+    Ok((full, segments, spans, dropcap))
+}
+
+/// Apply additional styles to the segments that lie before the text offset
+/// `end`, splitting a text segment if necessary.
+fn restyle<'a>(
+    segments: &[(Segment<'a>, StyleChain<'a>)],
+    end: usize,
+    local: &'a Styles,
+) -> Vec<(Segment<'a>, StyleChain<'a>)> {
+    let mut restyled = vec![];
+    let mut cursor = 0;
+    for &(segment, styles) in segments {
+        let len = segment.len();
+        if cursor >= end {
+            restyled.push((segment, styles));
+        } else if cursor + len <= end || !matches!(segment, Segment::Text(_)) {
+            restyled.push((segment, styles.chain(local)));
+        } else {
+            restyled.push((Segment::Text(end - cursor), styles.chain(local)));
+            restyled.push((Segment::Text(cursor + len - end), styles));
+        }
+        cursor += len;
+    }
+    restyled
 }
 
 /// Prepare paragraph layout by shaping the whole paragraph and layouting all
 /// contained inline-level content.
+#[allow(clippy::too_many_arguments)]
 fn prepare<'a>(
     vt: &mut Vt,
     children: &'a [Content],
     text: &'a str,
     segments: Vec<(Segment<'a>, StyleChain<'a>)>,
     spans: SpanMapper,
+    dropcap: Option<(&DropCapElem, StyleChain)>,
     styles: StyleChain<'a>,
     region: Size,
 ) -> SourceResult<Preparation<'a>> {
@@ -678,6 +1077,7 @@ fn prepare<'a>(
         },
     );
 
+    let tabs = ParElem::tab_stops_in(styles);
     let mut cursor = 0;
     let mut items = vec![];
 
@@ -686,7 +1086,22 @@ fn prepare<'a>(
         let end = cursor + segment.len();
         match segment {
             Segment::Text(_) => {
-                shape_range(&mut items, vt, &bidi, cursor..end, &spans, styles);
+                // With tab stops, tabs are split off from the shaped runs
+                // because their width depends on their position in the line.
+                let mut start = cursor;
+                let splits =
+                    text[cursor..end].match_indices('\t').filter(|_| tabs.is_some());
+                for (i, _) in splits {
+                    let tab = cursor + i;
+                    if start < tab {
+                        shape_range(&mut items, vt, &bidi, start..tab, &spans, styles);
+                    }
+                    items.push(Item::Tab(tab));
+                    start = tab + 1;
+                }
+                if start < end || start == cursor {
+                    shape_range(&mut items, vt, &bidi, start..end, &spans, styles);
+                }
             }
             Segment::Spacing(spacing) => match spacing {
                 Spacing::Rel(v) => {
@@ -733,14 +1148,47 @@ fn prepare<'a>(
         align: AlignElem::alignment_in(styles).x.resolve(styles),
         justify: ParElem::justify_in(styles),
         hang: ParElem::hanging_indent_in(styles),
+        tabs,
+        dropcap: match dropcap {
+            Some((elem, styles)) => Some(layout_dropcap(vt, elem, styles, region)?),
+            None => None,
+        },
+        exclusion: None,
+        wrapped: 0,
     })
 }
 
+/// Layout a drop cap such that it spans the requested number of lines.
+fn layout_dropcap(
+    vt: &mut Vt,
+    elem: &DropCapElem,
+    styles: StyleChain,
+    region: Size,
+) -> SourceResult<DropCap> {
+    let lines = elem.lines(styles).get();
+    let leading = ParElem::leading_in(styles);
+    let pod = Regions::one(region, Axes::splat(false));
+
+    // At its natural size, the drop cap is as high as a regular line of text.
+    // From that, we can determine how much it needs to be scaled up.
+    let mut frame = elem.body().layout(vt, styles, pod)?.into_frame();
+    let natural = frame.height();
+    if natural > Abs::zero() {
+        let target = lines as f64 * natural + (lines - 1) as f64 * leading;
+        let size = TextElem::size_in(styles) * (target / natural);
+        let body = elem.body().styled(TextElem::set_size(TextSize(size.into())));
+        frame = body.layout(vt, styles, pod)?.into_frame();
+    }
+
+    let inset = frame.width() + elem.gap(styles);
+    Ok(DropCap { frame, lines, inset })
+}
+
 /// Group a range of text by BiDi level and script, shape the runs and generate
 /// items for them.
 fn shape_range<'a>(
     items: &mut Vec<Item<'a>>,
-    vt: &Vt,
+    vt: &mut Vt,
     bidi: &BidiInfo<'a>,
     range: Range,
     spans: &SpanMapper,
@@ -750,8 +1198,14 @@ fn shape_range<'a>(
     let region = TextElem::region_in(styles);
     let mut process = |range: Range, level: BidiLevel| {
         let dir = if level.is_ltr() { Dir::LTR } else { Dir::RTL };
-        let shaped =
+        let mut shaped =
             shape(vt, range.start, &bidi.text[range], spans, styles, dir, lang, region);
+        for warning in shaped.warnings.drain(..) {
+            vt.warn(warning);
+        }
+        for run in shaped.runs.drain(..) {
+            vt.tracer.shaped(run);
+        }
         items.push(Item::Text(shaped));
     };
 
@@ -809,6 +1263,46 @@ fn shared_get<T: PartialEq>(
         .then_some(value)
 }
 
+/// Determine how many lines of the paragraph lie next to the exclusion.
+///
+/// This depends on the heights of the lines, which in turn depend on where
+/// the lines are broken. We thus start by assuming that all lines are
+/// affected and then refine the count a few times.
+fn wrap(
+    vt: &mut Vt,
+    p: &mut Preparation,
+    exclusion: Exclusion,
+    region: Size,
+) -> SourceResult<()> {
+    let leading = ParElem::leading_in(p.styles);
+    p.exclusion = Some(exclusion);
+    p.wrapped = usize::MAX;
+
+    for _ in 0..3 {
+        let mut count = 0;
+        {
+            let lines = linebreak(vt, p, region.x - p.hang);
+            let mut y = Abs::zero();
+            for (i, line) in lines.iter().enumerate() {
+                if y >= exclusion.height {
+                    break;
+                }
+                let (frame, _) = commit(vt, p, line, region.x, region.y, p.insets(i))?;
+                y += frame.height() + leading;
+                count += 1;
+            }
+        }
+
+        if count == p.wrapped {
+            break;
+        }
+
+        p.wrapped = count;
+    }
+
+    Ok(())
+}
+
 /// Find suitable linebreaks.
 fn linebreak<'a>(vt: &Vt, p: &'a Preparation<'a>, width: Abs) -> Vec<Line<'a>> {
     let linebreaks = ParElem::linebreaks_in(p.styles).unwrap_or_else(|| {
@@ -836,22 +1330,24 @@ fn linebreak_simple<'a>(vt: &Vt, p: &'a Preparation<'a>, width: Abs) -> Vec<Line
     for (end, mandatory, hyphen) in breakpoints(p) {
         // Compute the line and its size.
         let mut attempt = line(vt, p, start..end, mandatory, hyphen);
+        let mut available = width - p.inset(lines.len());
 
         // If the line doesn't fit anymore, we push the last fitting attempt
         // into the stack and rebuild the line from the attempt's end. The
         // resulting line cannot be broken up further.
-        if !width.fits(attempt.width) {
+        if !available.fits(attempt.width) {
             if let Some((last_attempt, last_end)) = last.take() {
                 lines.push(last_attempt);
                 start = last_end;
                 attempt = line(vt, p, start..end, mandatory, hyphen);
+                available = width - p.inset(lines.len());
             }
         }
 
         // Finish the current line if there is a mandatory line break (i.e.
         // due to "\n") or if the line doesn't fit horizontally already
         // since then no shorter line will be possible.
-        if mandatory || !width.fits(attempt.width) {
+        if mandatory || !available.fits(attempt.width) {
             lines.push(attempt);
             start = end;
             last = None;
@@ -893,6 +1389,7 @@ fn linebreak_optimized<'a>(vt: &Vt, p: &'a Preparation<'a>, width: Abs) -> Vec<L
         pred: usize,
         total: Cost,
         line: Line<'a>,
+        lines: usize,
     }
 
     // Cost parameters.
@@ -907,6 +1404,7 @@ fn linebreak_optimized<'a>(vt: &Vt, p: &'a Preparation<'a>, width: Abs) -> Vec<L
         pred: 0,
         total: 0.0,
         line: line(vt, p, 0..0, false, false),
+        lines: 0,
     }];
 
     let em = TextElem::size_in(p.styles);
@@ -923,8 +1421,9 @@ fn linebreak_optimized<'a>(vt: &Vt, p: &'a Preparation<'a>, width: Abs) -> Vec<L
             let attempt = line(vt, p, start..end, mandatory, hyphen);
 
             // Determine how much the line's spaces would need to be stretched
-            // to make it the desired width.
-            let delta = width - attempt.width;
+            // to make it the desired width. Lines next to a drop cap are
+            // shorter.
+            let delta = width - p.inset(pred.lines) - attempt.width;
             // Determine how much stretch are permitted.
             let adjust = if delta >= Abs::zero() {
                 attempt.stretchability()
@@ -994,7 +1493,12 @@ fn linebreak_optimized<'a>(vt: &Vt, p: &'a Preparation<'a>, width: Abs) -> Vec<L
 
             // If this attempt is better than what we had before, take it!
             if best.as_ref().map_or(true, |best| best.total >= total) {
-                best = Some(Entry { pred: i, total, line: attempt });
+                best = Some(Entry {
+                    pred: i,
+                    total,
+                    line: attempt,
+                    lines: pred.lines + 1,
+                });
             }
         }
 
@@ -1114,7 +1618,7 @@ impl Iterator for Breakpoints<'_> {
             // Filter out hyphenation opportunities where hyphenation was
             // actually disabled.
             let hyphen = self.offset < self.end;
-            if hyphen && !self.hyphenate(self.offset) {
+            if hyphen && (!self.hyphenate(self.offset) || self.preserved(self.offset)) {
                 return self.next();
             }
 
@@ -1150,6 +1654,12 @@ impl Iterator for Breakpoints<'_> {
         }
 
         self.offset = self.end;
+
+        // Text with preserved whitespace only breaks at newlines.
+        if !self.mandatory && self.preserved(self.end) {
+            return self.next();
+        }
+
         Some((self.end, self.mandatory, false))
     }
 }
@@ -1166,6 +1676,16 @@ impl Breakpoints<'_> {
             .unwrap_or(false)
     }
 
+    /// Whether the text before the given offset preserves its whitespace.
+    fn preserved(&self, offset: usize) -> bool {
+        offset
+            .checked_sub(1)
+            .and_then(|prev| self.p.find(prev)?.text())
+            .map_or(false, |shaped| {
+                TextElem::whitespace_in(shaped.styles) == Whitespace::Preserve
+            })
+    }
+
     /// The text language at the given offset.
     fn lang(&self, offset: usize) -> Option<hypher::Lang> {
         let lang = self.p.lang.or_else(|| {
@@ -1198,6 +1718,7 @@ fn line<'a>(
             inner: &[],
             last: None,
             width: Abs::zero(),
+            tabs: vec![],
             justify,
             dash: false,
         };
@@ -1307,6 +1828,21 @@ fn line<'a>(
         width += item.width();
     }
 
+    // Advance tabs to their stops. This happens once in logical order and
+    // the results are reused when the line is committed.
+    let mut tabs = vec![];
+    let mut x = Abs::zero();
+    for item in first.iter().chain(inner).chain(&last) {
+        if let Item::Tab(at) = item {
+            let advance = p.tab_advance(x);
+            tabs.push((*at, advance));
+            width += advance;
+            x += advance;
+        } else {
+            x += item.width();
+        }
+    }
+
     Line {
         bidi: &p.bidi,
         trimmed: range,
@@ -1315,6 +1851,7 @@ fn line<'a>(
         inner,
         last,
         width,
+        tabs,
         justify,
         dash,
     }
@@ -1333,23 +1870,48 @@ fn finalize(
     let width = if !region.x.is_finite()
         || (!expand && lines.iter().all(|line| line.fr().is_zero()))
     {
-        p.hang + lines.iter().map(|line| line.width).max().unwrap_or_default()
+        p.hang
+            + lines
+                .iter()
+                .enumerate()
+                .map(|(i, line)| line.width + p.inset(i))
+                .max()
+                .unwrap_or_default()
     } else {
         region.x
     };
 
-    // Stack the lines into one frame per region.
-    let mut frames: Vec<Frame> = lines
-        .iter()
-        .map(|line| commit(vt, p, line, width, region.y))
-        .collect::<SourceResult<_>>()?;
+    // Stack the lines into one frame per region. Only the final layout of a
+    // line reports how well it fills the available width, so that trial
+    // layouts don't produce warnings.
+    let mut frames = Vec::with_capacity(lines.len());
+    for (i, line) in lines.iter().enumerate() {
+        let (frame, fill) = commit(vt, p, line, width, region.y, p.insets(i))?;
+        report(vt, p, line, fill);
+        frames.push(frame);
+    }
+
+    // Place the drop cap next to the first lines, aligned with the top of the
+    // first line.
+    if let (Some(cap), Some(first)) = (&p.dropcap, frames.first_mut()) {
+        let x = if TextElem::dir_in(p.styles).is_positive() {
+            Abs::zero()
+        } else {
+            width - cap.frame.width()
+        };
+        first.push_frame(Point::with_x(x), cap.frame.clone());
+    }
 
-    // Prevent orphans.
+    // Prevent orphans by keeping the paragraph's first lines together.
     let leading = ParElem::leading_in(p.styles);
-    if frames.len() >= 2 && !frames[1].is_empty() {
-        let second = frames.remove(1);
+    let orphans = ParElem::orphans_in(p.styles).get().min(frames.len());
+    let kept = (1..orphans).take_while(|&i| !frames[i].is_empty()).count();
+    if kept > 0 {
+        let rest: Vec<_> = frames.drain(1..=kept).collect();
         let first = &mut frames[0];
-        merge(first, second, leading);
+        for frame in rest {
+            merge(first, frame, leading);
+        }
     }
 
     // Prevent widows.
@@ -1371,6 +1933,46 @@ fn merge(first: &mut Frame, second: Frame, leading: Abs) {
     first.size_mut().y = total;
 }
 
+/// By how much a line may exceed the available width before it is reported as
+/// overfull (in points).
+const OVERFULL_TOLERANCE: Abs = Abs::raw(0.1);
+
+/// How well a committed line fills the available width.
+#[derive(Debug, Copy, Clone)]
+enum Fill {
+    /// The line fits.
+    Fits,
+    /// The line is wider than the available space by the given amount.
+    Overfull(Abs),
+    /// The spaces of the line are stretched by the given amount each.
+    Underfull(Abs),
+}
+
+/// Report lines that stick out of the paragraph or whose spaces had to be
+/// stretched far beyond their natural width.
+///
+/// Lines of generated text are skipped as they can't be pointed to.
+fn report(vt: &mut Vt, p: &Preparation, line: &Line, fill: Fill) {
+    let span = p.spans.span_from(line.trimmed.start);
+    if span.is_detached() {
+        return;
+    }
+
+    match fill {
+        Fill::Fits => {}
+        Fill::Overfull(amount) => {
+            vt.warn(SourceError::warning(span, "line is overfull").with_hints([
+                eco_format!("it is {amount:?} wider than the available space"),
+            ]));
+        }
+        Fill::Underfull(amount) => {
+            vt.warn(SourceError::warning(span, "line is underfull").with_hints([
+                eco_format!("its spaces are stretched by {amount:?} each"),
+            ]));
+        }
+    }
+}
+
 /// Commit to a line and build its frame.
 fn commit(
     vt: &mut Vt,
@@ -1378,8 +1980,9 @@ fn commit(
     line: &Line,
     width: Abs,
     full: Abs,
-) -> SourceResult<Frame> {
-    let mut remaining = width - line.width - p.hang;
+    (left, right): (Abs, Abs),
+) -> SourceResult<(Frame, Fill)> {
+    let mut remaining = width - line.width - p.hang - left - right;
     let mut offset = Abs::zero();
 
     // Reorder the line from logical to visual order.
@@ -1388,14 +1991,23 @@ fn commit(
         offset += p.hang;
     }
 
-    // Handle hanging punctuation to the left.
+    // Make room for a drop cap or an exclusion.
+    offset += left;
+
+    // Handle hanging punctuation to the left. In left-to-right text, this is
+    // the start of the line, in right-to-left text its end.
     if let Some(Item::Text(text)) = reordered.first() {
         if let Some(glyph) = text.glyphs.first() {
-            if !text.dir.is_positive()
-                && TextElem::overhang_in(text.styles)
+            if TextElem::overhang_in(text.styles)
                 && (reordered.len() > 1 || text.glyphs.len() > 1)
             {
-                let amount = overhang(glyph.c) * glyph.x_advance.at(text.size);
+                let quotes = TextElem::overhang_quotes_in(text.styles);
+                let ratio = if text.dir.is_positive() {
+                    overhang_start(glyph.c, quotes)
+                } else {
+                    overhang(glyph.c, quotes)
+                };
+                let amount = ratio * glyph.x_advance.at(text.size);
                 offset -= amount;
                 remaining += amount;
             }
@@ -1405,11 +2017,16 @@ fn commit(
     // Handle hanging punctuation to the right.
     if let Some(Item::Text(text)) = reordered.last() {
         if let Some(glyph) = text.glyphs.last() {
-            if text.dir.is_positive()
-                && TextElem::overhang_in(text.styles)
+            if TextElem::overhang_in(text.styles)
                 && (reordered.len() > 1 || text.glyphs.len() > 1)
             {
-                let amount = overhang(glyph.c) * glyph.x_advance.at(text.size);
+                let quotes = TextElem::overhang_quotes_in(text.styles);
+                let ratio = if text.dir.is_positive() {
+                    overhang(glyph.c, quotes)
+                } else {
+                    overhang_start(glyph.c, quotes)
+                };
+                let amount = ratio * glyph.x_advance.at(text.size);
                 remaining += amount;
             }
         }
@@ -1445,6 +2062,14 @@ fn commit(
         }
     }
 
+    let fill = if remaining < -OVERFULL_TOLERANCE {
+        Fill::Overfull(-remaining)
+    } else if extra_justification > Em::one().at(TextElem::size_in(p.styles)) {
+        Fill::Underfull(extra_justification)
+    } else {
+        Fill::Fits
+    };
+
     let mut top = Abs::zero();
     let mut bottom = Abs::zero();
 
@@ -1479,6 +2104,9 @@ fn commit(
                 let frame = shaped.build(vt, justification_ratio, extra_justification);
                 push(&mut offset, frame);
             }
+            Item::Tab(at) => {
+                offset += line.tab_advance(*at);
+            }
             Item::Frame(frame) | Item::Meta(frame) => {
                 push(&mut offset, frame.clone());
             }
@@ -1501,7 +2129,7 @@ fn commit(
         output.push_frame(Point::new(x, y), frame);
     }
 
-    Ok(output)
+    Ok((output, fill))
 }
 
 /// Return a line's items in visual order.
@@ -1544,11 +2172,12 @@ fn reorder<'a>(line: &'a Line<'a>) -> (Vec<&Item<'a>>, bool) {
     (reordered, starts_rtl)
 }
 
-/// How much a character should hang into the end margin.
+/// How much a character should hang into the end margin. Closing quotes only
+/// hang if `quotes` is enabled.
 ///
 /// For more discussion, see:
 /// https://recoveringphysicist.com/21/
-fn overhang(c: char) -> f64 {
+fn overhang(c: char, quotes: bool) -> f64 {
     match c {
         // Dashes.
         '–' | '—' => 0.2,
@@ -1558,9 +2187,25 @@ fn overhang(c: char) -> f64 {
         '.' | ',' => 0.8,
         ':' | ';' => 0.3,
 
+        // Closing quotes.
+        '”' | '’' | '"' | '\'' if quotes => 0.6,
+        '»' | '›' if quotes => 0.2,
+
         // Arabic
         '\u{60C}' | '\u{6D4}' => 0.4,
 
         _ => 0.0,
     }
 }
+
+/// How much a character should hang into the start margin. Only opening
+/// quotes hang, and only if `quotes` is enabled.
+fn overhang_start(c: char, quotes: bool) -> f64 {
+    match c {
+        // Opening quotes.
+        '“' | '‘' | '„' | '‚' if quotes => 0.6,
+        '«' | '‹' if quotes => 0.2,
+
+        _ => 0.0,
+    }
+}
@@ -48,6 +48,30 @@ pub struct PlaceElem {
     /// The vertical displacement of the placed content.
     pub dy: Rel<Length>,
 
+    /// Whether the text of the following paragraphs should wrap around the
+    /// placed content.
+    ///
+    /// This only has an effect if the content is placed at the current
+    /// position in the flow (that is, without a vertical alignment) and
+    /// aligned to the left or right.
+    ///
+    /// ```example
+    /// #set page(width: 200pt)
+    /// #place(right, wrap: true, rect(
+    ///   width: 60pt,
+    ///   height: 40pt,
+    ///   fill: aqua,
+    /// ))
+    /// #lorem(30)
+    /// ```
+    #[default(false)]
+    pub wrap: bool,
+
+    /// The space between wrapping content and the text that flows around it.
+    #[resolve]
+    #[default(Em::new(1.0).into())]
+    pub clearance: Length,
+
     /// The content to place.
     #[required]
     pub body: Content,
@@ -24,6 +24,15 @@ use super::AlignElem;
 /// ]
 /// ```
 ///
+/// Combined with a fractional box, this produces leaders between an entry
+/// and its page number:
+///
+/// ```example
+/// Introduction
+/// #box(width: 1fr, repeat(gap: 2pt)[.])
+/// 7
+/// ```
+///
 /// Display: Repeat
 /// Category: layout
 #[element(Layout)]
@@ -31,6 +40,24 @@ pub struct RepeatElem {
     /// The content to repeat.
     #[required]
     pub body: Content,
+
+    /// The gap between each instance of the body.
+    ///
+    /// ```example
+    /// #box(width: 1fr, repeat(gap: 4pt)[-])
+    /// ```
+    #[resolve]
+    pub gap: Length,
+
+    /// Whether to increase the gap between instances to completely fill the
+    /// available space. If this is `{false}`, the instances are aligned
+    /// according to the current [alignment]($func/align) instead.
+    ///
+    /// ```example
+    /// #box(width: 1fr, repeat(justify: false)[--])
+    /// ```
+    #[default(true)]
+    pub justify: bool,
 }
 
 impl Layout for RepeatElem {
@@ -45,11 +72,14 @@ impl Layout for RepeatElem {
         let piece = self.body().layout(vt, styles, pod)?.into_frame();
         let align = AlignElem::alignment_in(styles).x.resolve(styles);
 
+        let gap = self.gap(styles);
+        let justify = self.justify(styles);
+
         let fill = regions.size.x;
         let width = piece.width();
-        let count = (fill / width).floor();
-        let remaining = fill % width;
-        let apart = remaining / (count - 1.0);
+        let count = ((fill + gap) / (width + gap)).floor();
+        let remaining = (fill + gap) % (width + gap);
+        let apart = if justify { gap + remaining / (count - 1.0) } else { gap };
 
         let size = Size::new(regions.size.x, piece.height());
 
@@ -63,7 +93,7 @@ impl Layout for RepeatElem {
         }
 
         let mut offset = Abs::zero();
-        if count == 1.0 {
+        if count == 1.0 || !justify {
             offset += align.position(remaining);
         }
 
@@ -1,6 +1,6 @@
 use typst::eval::{CastInfo, Reflect};
 
-use crate::layout::{AlignElem, GridLayouter, TrackSizings};
+use crate::layout::{auto_fill, AlignElem, GridLayouter, RowPiece, TrackSizings};
 use crate::meta::{Figurable, LocalName};
 use crate::prelude::*;
 
@@ -43,6 +43,12 @@ pub struct TableElem {
     /// information on track sizing.
     pub columns: TrackSizings,
 
+    /// Whether to repeat the column tracks as often as they fit into the
+    /// available width. See the [grid documentation]($func/grid.auto-fill) for
+    /// more information.
+    #[default(false)]
+    pub auto_fill: bool,
+
     /// The row sizes. See the [grid documentation]($func/grid) for more
     /// information on track sizing.
     pub rows: TrackSizings,
@@ -109,18 +115,71 @@ pub struct TableElem {
     /// See the [line's documentation]($func/line.stroke) for more details.
     /// Strokes can be disabled by setting this to `{none}`.
     ///
-    /// _Note:_ Richer stroke customization for individual cells is not yet
-    /// implemented, but will be in the future. In the meantime, you can use
-    /// the third-party [tablex library](https://github.com/PgBiel/typst-tablex/).
-    #[resolve]
+    /// Like the fill, this can also be an array of strokes (corresponding to
+    /// each column) or a function that returns a stroke. The function is
+    /// passed the cells' column and row index, starting at zero. Adjacent
+    /// cells share their borders: Where two cells with different strokes
+    /// meet, only the thicker stroke is drawn.
+    ///
+    /// ```example
+    /// #table(
+    ///   columns: 3,
+    ///   stroke: (col, row) => if row == 0 { 1.5pt } else { 0.5pt + gray },
+    ///   [*A*], [*B*], [*C*],
+    ///   [1], [2], [3],
+    ///   [4], [5], [6],
+    /// )
+    /// ```
     #[fold]
-    #[default(Some(PartialStroke::default()))]
-    pub stroke: Option<PartialStroke>,
+    #[default(Celled::Value(Some(PartialStroke::default())))]
+    pub stroke: Celled<Option<PartialStroke>>,
 
     /// How much to pad the cells' content.
     #[default(Abs::pt(5.0).into())]
     pub inset: Rel<Length>,
 
+    /// How many rows at the start of the table form its header.
+    ///
+    /// When the table breaks across pages, the header rows are repeated at the
+    /// top of each page.
+    ///
+    /// ```example
+    /// #set page(height: 100pt)
+    /// #table(
+    ///   columns: 2,
+    ///   header-rows: 1,
+    ///   [*Name*], [*Value*],
+    ///   ..range(8).map(n => ([Item #n], [#(n * n)])).flatten(),
+    /// )
+    /// ```
+    #[default(0)]
+    pub header_rows: usize,
+
+    /// How many rows at the end of the table form its footer.
+    ///
+    /// When the table breaks across pages, the footer rows are repeated at the
+    /// bottom of each page.
+    ///
+    /// ```example
+    /// #set page(height: 100pt)
+    /// #table(
+    ///   columns: 2,
+    ///   footer-rows: 1,
+    ///   ..range(8).map(n => ([Item #n], [#(n * n)])).flatten(),
+    ///   [*Name*], [*Value*],
+    /// )
+    /// ```
+    #[default(0)]
+    pub footer_rows: usize,
+
+    /// Whether rows may break across pages.
+    ///
+    /// If disabled, a row that does not fit into the remaining space on a page
+    /// is moved to the next page as a whole. Rows that are taller than a full
+    /// page are still split.
+    #[default(true)]
+    pub breakable: bool,
+
     /// The contents of the table cells.
     #[variadic]
     pub children: Vec<Content>,
@@ -137,18 +196,22 @@ impl Layout for TableElem {
         let inset = self.inset(styles);
         let align = self.align(styles);
 
-        let tracks = Axes::new(self.columns(styles).0, self.rows(styles).0);
+        let mut tracks = Axes::new(self.columns(styles).0, self.rows(styles).0);
         let gutter = Axes::new(self.column_gutter(styles).0, self.row_gutter(styles).0);
+        if self.auto_fill(styles) {
+            tracks.x = auto_fill(&tracks.x, &gutter.x, regions, styles).at(self.span())?;
+        }
         let cols = tracks.x.len().max(1);
         let cells: Vec<_> = self
             .children()
             .into_iter()
             .enumerate()
             .map(|(i, child)| {
-                let mut child = child.padded(Sides::splat(inset));
-
                 let x = i % cols;
                 let y = i / cols;
+                let mut child =
+                    child.padded(Sides::splat(inset)).role(Role::TableCell { x, y });
+
                 if let Smart::Custom(alignment) = align.resolve(vt, x, y)? {
                     child = child.styled(AlignElem::set_alignment(alignment));
                 }
@@ -158,7 +221,7 @@ impl Layout for TableElem {
             .collect::<SourceResult<_>>()?;
 
         let fill = self.fill(styles);
-        let stroke = self.stroke(styles).map(PartialStroke::unwrap_or_default);
+        let stroke = self.stroke(styles);
 
         // Prepare grid layout by unifying content and gutter tracks.
         let layouter = GridLayouter::new(
@@ -167,7 +230,11 @@ impl Layout for TableElem {
             &cells,
             regions,
             styles,
-        );
+        )
+        .with_header(self.header_rows(styles))
+        .with_footer(self.footer_rows(styles))
+        .with_breakable(self.breakable(styles))
+        .with_span(self.span());
 
         // Measure the columns and layout the grid row-by-row.
         let mut layout = layouter.layout(vt)?;
@@ -179,7 +246,8 @@ impl Layout for TableElem {
             }
 
             // Render table lines.
-            if let Some(stroke) = &stroke {
+            if let Celled::Value(Some(stroke)) = &stroke {
+                let stroke = stroke.clone().resolve(styles).unwrap_or_default();
                 let thickness = stroke.thickness;
                 let half = thickness / 2.0;
 
@@ -202,6 +270,16 @@ impl Layout for TableElem {
                         FrameItem::Shape(vline, self.span()),
                     );
                 }
+            } else if !matches!(stroke, Celled::Value(None)) {
+                stroke_cells(
+                    vt,
+                    frame,
+                    &stroke,
+                    &layout.cols,
+                    rows,
+                    styles,
+                    self.span(),
+                )?;
             }
 
             // Render cell backgrounds.
@@ -225,6 +303,83 @@ impl Layout for TableElem {
     }
 }
 
+/// Render the borders of each cell individually.
+///
+/// Borders shared by two cells are only drawn once, with the thicker of the
+/// two cells' strokes.
+fn stroke_cells(
+    vt: &mut Vt,
+    frame: &mut Frame,
+    stroke: &Celled<Option<PartialStroke>>,
+    cols: &[Abs],
+    rows: &[RowPiece],
+    styles: StyleChain,
+    span: Span,
+) -> SourceResult<()> {
+    // Resolve the strokes of all cells in this region.
+    let mut strokes = vec![];
+    for row in rows {
+        let mut line = vec![];
+        for x in 0..cols.len() {
+            let stroke = stroke.resolve(vt, x, row.y)?;
+            line.push(stroke.map(|s| s.resolve(styles).unwrap_or_default()));
+        }
+        strokes.push(line);
+    }
+
+    let get = |y: Option<usize>, x: Option<usize>| -> Option<&Stroke> {
+        strokes.get(y?)?.get(x?)?.as_ref()
+    };
+
+    let mut push = |pos: Point, target: Point, stroke: &Stroke| {
+        let line = Geometry::Line(target).stroked(stroke.clone());
+        frame.prepend(pos, FrameItem::Shape(line, span));
+    };
+
+    // Render horizontal borders, including the ones above and below.
+    for (y, dy) in points(rows.iter().map(|piece| piece.height)).enumerate() {
+        for (x, dx) in points(cols.iter().copied()).take(cols.len()).enumerate() {
+            let Some(stroke) =
+                collapse(get(y.checked_sub(1), Some(x)), get(Some(y), Some(x)))
+            else {
+                continue;
+            };
+            let half = stroke.thickness / 2.0;
+            push(Point::new(dx - half, dy), Point::with_x(cols[x] + 2.0 * half), stroke);
+        }
+    }
+
+    // Render vertical borders, including the ones left and right.
+    for (x, dx) in points(cols.iter().copied()).enumerate() {
+        for (y, dy) in points(rows.iter().map(|piece| piece.height))
+            .take(rows.len())
+            .enumerate()
+        {
+            let Some(stroke) =
+                collapse(get(Some(y), x.checked_sub(1)), get(Some(y), Some(x)))
+            else {
+                continue;
+            };
+            let half = stroke.thickness / 2.0;
+            push(
+                Point::new(dx, dy - half),
+                Point::with_y(rows[y].height + 2.0 * half),
+                stroke,
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// Select the thicker of two strokes that meet at a shared border.
+fn collapse<'a>(a: Option<&'a Stroke>, b: Option<&'a Stroke>) -> Option<&'a Stroke> {
+    match (a, b) {
+        (Some(a), Some(b)) => Some(if b.thickness > a.thickness { b } else { a }),
+        (a, b) => a.or(b),
+    }
+}
+
 /// Turn an iterator of extents into an iterator of offsets before, in between,
 /// and after the extents, e.g. [10mm, 5mm] -> [0mm, 10mm, 15mm].
 fn points(extents: impl IntoIterator<Item = Abs>) -> impl Iterator<Item = Abs> {
@@ -263,6 +418,17 @@ impl<T: Default + Clone + FromValue> Celled<T> {
     }
 }
 
+impl<T: Fold<Output = T>> Fold for Celled<T> {
+    type Output = Self;
+
+    fn fold(self, outer: Self::Output) -> Self::Output {
+        match (self, outer) {
+            (Self::Value(inner), Self::Value(outer)) => Self::Value(inner.fold(outer)),
+            (inner, _) => inner,
+        }
+    }
+}
+
 impl<T: Default> Default for Celled<T> {
     fn default() -> Self {
         Self::Value(T::default())
@@ -316,6 +482,7 @@ impl LocalName for TableElem {
             Lang::FRENCH => "Tableau",
             Lang::GERMAN => "Tabelle",
             Lang::ITALIAN => "Tabella",
+            Lang::JAPANESE => "表",
             Lang::NYNORSK => "Tabell",
             Lang::POLISH => "Tabela",
             Lang::PORTUGUESE => "Tabela",
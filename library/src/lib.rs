@@ -15,7 +15,7 @@ pub mod text;
 pub mod visualize;
 
 use typst::diag::At;
-use typst::eval::{LangItems, Library, Module, Scope};
+use typst::eval::{Dict, LangItems, Library, Module, Scope};
 use typst::geom::Smart;
 use typst::model::{Element, Styles};
 
@@ -23,14 +23,20 @@ use self::layout::LayoutRoot;
 
 /// Construct the standard library.
 pub fn build() -> Library {
+    build_with_inputs(Dict::new())
+}
+
+/// Construct the standard library with inputs that documents can read from
+/// the `sys.inputs` dictionary.
+pub fn build_with_inputs(inputs: Dict) -> Library {
     let math = math::module();
-    let global = global(math.clone());
+    let global = global(math.clone(), inputs);
     Library { global, math, styles: styles(), items: items() }
 }
 
 /// Construct the module with global definitions.
 #[tracing::instrument(skip_all)]
-fn global(math: Module) -> Module {
+fn global(math: Module, inputs: Dict) -> Module {
     let mut global = Scope::deduplicating();
 
     // Categories.
@@ -41,10 +47,18 @@ fn global(math: Module) -> Module {
     compute::define(&mut global);
     symbols::define(&mut global);
     global.define("math", math);
+    global.define("sys", sys(inputs));
 
     Module::new("global").with_scope(global)
 }
 
+/// Construct the module with information about the compilation environment.
+fn sys(inputs: Dict) -> Module {
+    let mut scope = Scope::new();
+    scope.define("inputs", inputs);
+    Module::new("sys").with_scope(scope)
+}
+
 /// Construct the standard style map.
 fn styles() -> Styles {
     Styles::new()
@@ -86,6 +100,7 @@ fn items() -> LangItems {
         bibliography_keys: meta::BibliographyElem::keys,
         heading: |level, title| meta::HeadingElem::new(title).with_level(level).pack(),
         heading_func: meta::HeadingElem::func(),
+        embed_func: meta::EmbedElem::func(),
         list_item: |body| layout::ListItem::new(body).pack(),
         enum_item: |number, body| {
             let mut elem = layout::EnumItem::new(body);
@@ -168,6 +168,18 @@ pub struct EquationElem {
     /// ```
     pub numbering: Option<Numbering>,
 
+    /// The alignment of the equation numbers.
+    ///
+    /// Can be `{start}`, `{end}`, `{left}`, or `{right}`.
+    ///
+    /// ```example
+    /// #set math.equation(numbering: "(1)", number-align: start)
+    ///
+    /// $ a^2 + b^2 = c^2 $
+    /// ```
+    #[default(HorizontalAlign(GenAlign::End))]
+    pub number_align: HorizontalAlign,
+
     /// A supplement for the equation.
     ///
     /// For references to equations, this is added before the referenced number.
@@ -276,10 +288,10 @@ impl Layout for EquationElem {
                 let height = frame.height().max(counter.height());
                 frame.resize(Size::new(width, height), Align::CENTER_HORIZON);
 
-                let x = if TextElem::dir_in(styles).is_positive() {
-                    frame.width() - counter.width()
-                } else {
-                    Abs::zero()
+                let x = match self.number_align(styles).0.resolve(styles) {
+                    Align::Left => Abs::zero(),
+                    Align::Right => frame.width() - counter.width(),
+                    _ => bail!(self.span(), "equation numbers cannot be centered"),
                 };
                 let y = (frame.height() - counter.height()) / 2.0;
 
@@ -327,6 +339,7 @@ impl LocalName for EquationElem {
             Lang::FRENCH => "Équation",
             Lang::GERMAN => "Gleichung",
             Lang::ITALIAN => "Equazione",
+            Lang::JAPANESE => "式",
             Lang::NYNORSK => "Likning",
             Lang::POLISH => "Równanie",
             Lang::PORTUGUESE => "Equação",
@@ -236,6 +236,7 @@ impl LocalName for BibliographyElem {
             Lang::FRENCH => "Bibliographie",
             Lang::GERMAN => "Bibliographie",
             Lang::ITALIAN => "Bibliografia",
+            Lang::JAPANESE => "参考文献",
             Lang::NYNORSK => "Bibliografi",
             Lang::POLISH => "Bibliografia",
             Lang::PORTUGUESE => "Bibliografia",
@@ -352,6 +352,21 @@ impl Counter {
         Ok(state)
     }
 
+    /// Get the value of the state at the end of the given page.
+    pub fn at_page(&self, vt: &mut Vt, page: NonZeroUsize) -> SourceResult<CounterState> {
+        let sequence = self.sequence(vt)?;
+        let (mut state, at) = sequence
+            .iter()
+            .rev()
+            .find(|(_, at)| *at <= page)
+            .unwrap_or(&sequence[0])
+            .clone();
+        if self.is_page() {
+            state.step(NonZeroUsize::ONE, page.get().saturating_sub(at.get()));
+        }
+        Ok(state)
+    }
+
     /// Get the value of the state at the final location.
     pub fn final_(&self, vt: &mut Vt, _: Location) -> SourceResult<CounterState> {
         let sequence = self.sequence(vt)?;
@@ -424,8 +439,12 @@ impl Counter {
             tracer,
         };
         let mut state = CounterState(match &self.0 {
-            // special case, because pages always start at one.
-            CounterKey::Page => smallvec![1],
+            // special case, because pages start at one or where the embedder
+            // continues the numbering of another document.
+            CounterKey::Page => {
+                let styles = StyleChain::new(&world.library().styles);
+                smallvec![PageElem::first_number_in(styles).get()]
+            }
             _ => smallvec![0],
         });
         let mut page = NonZeroUsize::ONE;
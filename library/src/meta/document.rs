@@ -1,3 +1,5 @@
+use typst::eval::Datetime;
+
 use crate::layout::{LayoutRoot, PageElem};
 use crate::prelude::*;
 
@@ -29,6 +31,19 @@ pub struct DocumentElem {
     /// The document's authors.
     pub author: Author,
 
+    /// The document's keywords.
+    pub keywords: Keywords,
+
+    /// The document's creation date.
+    ///
+    /// The year component must be at least zero in order to be embedded into
+    /// a PDF.
+    ///
+    /// ```example
+    /// #set document(date: datetime(year: 2023, month: 6, day: 14))
+    /// ```
+    pub date: Option<Datetime>,
+
     /// The page runs.
     #[internal]
     #[variadic]
@@ -70,6 +85,8 @@ impl LayoutRoot for DocumentElem {
             pages,
             title: self.title(styles),
             author: self.author(styles).0,
+            keywords: self.keywords(styles).0,
+            date: self.date(styles),
         })
     }
 }
@@ -84,3 +101,14 @@ cast! {
     v: EcoString => Self(vec![v]),
     v: Array => Self(v.into_iter().map(Value::cast).collect::<StrResult<_>>()?),
 }
+
+/// A list of keywords.
+#[derive(Debug, Default, Clone, Hash)]
+pub struct Keywords(Vec<EcoString>);
+
+cast! {
+    Keywords,
+    self => self.0.into_value(),
+    v: EcoString => Self(vec![v]),
+    v: Array => Self(v.into_iter().map(Value::cast).collect::<StrResult<_>>()?),
+}
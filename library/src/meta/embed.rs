@@ -0,0 +1,55 @@
+use typst::util::Bytes;
+
+use crate::prelude::*;
+
+/// A file embedded into the exported PDF.
+///
+/// The file is not rendered within the document. Instead, it is attached to
+/// the PDF so that readers can extract it from their viewer's attachment
+/// panel. This is useful to ship the data a table or plot was built from or
+/// even the document's own source alongside the PDF.
+///
+/// The attachment is named after the file. If files from different
+/// directories share a name, a number is appended to the later ones, e.g.
+/// `data-2.csv`. Embedding the same file twice attaches it only once.
+///
+/// ```example
+/// #embed(
+///   "data.csv",
+///   description: "Raw measurements",
+/// )
+/// ```
+///
+/// Display: Embed
+/// Category: meta
+#[element(Locatable, Show)]
+pub struct EmbedElem {
+    /// Path to the file to embed.
+    ///
+    /// The file name part of the path is used as the attachment's name.
+    #[required]
+    #[parse(
+        let Spanned { v: path, span } =
+            args.expect::<Spanned<EcoString>>("path to the file to embed")?;
+        let id = vm.location().join(&path).at(span)?;
+        let data = vm.world().file(id).at(span)?;
+        path
+    )]
+    pub path: EcoString,
+
+    /// The raw file data.
+    #[internal]
+    #[required]
+    #[parse(data)]
+    pub data: Bytes,
+
+    /// A description of the embedded file.
+    pub description: Option<EcoString>,
+}
+
+impl Show for EmbedElem {
+    #[tracing::instrument(name = "EmbedElem::show", skip_all)]
+    fn show(&self, _: &mut Vt, _: StyleChain) -> SourceResult<Content> {
+        Ok(Content::empty())
+    }
+}
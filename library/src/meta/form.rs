@@ -0,0 +1,184 @@
+use crate::layout::{BoxElem, Sizing};
+use crate::prelude::*;
+
+/// A fillable text field.
+///
+/// In the exported PDF, the field can be filled in with a PDF viewer. In other
+/// output formats, it is shown as an empty box.
+///
+/// ## Example { #example }
+/// ```example
+/// Name: #textfield("name") \
+/// Address: #textfield(
+///   "address",
+///   multiline: true,
+///   height: 3em,
+/// )
+/// ```
+///
+/// Display: Text Field
+/// Category: meta
+#[element(Show)]
+pub struct TextFieldElem {
+    /// The name that identifies the field in the form's data.
+    #[required]
+    pub name: EcoString,
+
+    /// The text the field initially contains.
+    #[default]
+    pub value: EcoString,
+
+    /// Whether the field accepts multiple lines of text.
+    #[default(false)]
+    pub multiline: bool,
+
+    /// The width of the field.
+    #[default(Em::new(10.0).into())]
+    pub width: Length,
+
+    /// The height of the field.
+    #[default(Em::new(1.4).into())]
+    pub height: Length,
+}
+
+impl Show for TextFieldElem {
+    #[tracing::instrument(name = "TextFieldElem::show", skip_all)]
+    fn show(&self, _: &mut Vt, styles: StyleChain) -> SourceResult<Content> {
+        let kind = FormFieldKind::Text {
+            value: self.value(styles),
+            multiline: self.multiline(styles),
+        };
+        Ok(field(self.name(), kind, self.width(styles), self.height(styles)))
+    }
+}
+
+/// A checkbox that can be checked in the exported PDF.
+///
+/// ## Example { #example }
+/// ```example
+/// #checkbox("terms") I agree to the terms.
+/// ```
+///
+/// Display: Checkbox
+/// Category: meta
+#[element(Show)]
+pub struct CheckboxElem {
+    /// The name that identifies the field in the form's data.
+    #[required]
+    pub name: EcoString,
+
+    /// Whether the checkbox is initially checked.
+    #[default(false)]
+    pub checked: bool,
+
+    /// The width and height of the checkbox.
+    #[default(Em::new(0.8).into())]
+    pub size: Length,
+}
+
+impl Show for CheckboxElem {
+    #[tracing::instrument(name = "CheckboxElem::show", skip_all)]
+    fn show(&self, _: &mut Vt, styles: StyleChain) -> SourceResult<Content> {
+        let kind = FormFieldKind::Checkbox { checked: self.checked(styles) };
+        let size = self.size(styles);
+        Ok(field(self.name(), kind, size, size))
+    }
+}
+
+/// A dropdown to choose one of several options in the exported PDF.
+///
+/// ## Example { #example }
+/// ```example
+/// Size: #dropdown(
+///   "size",
+///   ("Small", "Medium", "Large"),
+///   selected: 1,
+/// )
+/// ```
+///
+/// Display: Dropdown
+/// Category: meta
+#[element(Show)]
+pub struct DropdownElem {
+    /// The name that identifies the field in the form's data.
+    #[required]
+    pub name: EcoString,
+
+    /// The options to choose from.
+    #[required]
+    pub options: Vec<EcoString>,
+
+    /// The index of the initially selected option.
+    pub selected: Option<usize>,
+
+    /// The width of the field.
+    #[default(Em::new(8.0).into())]
+    pub width: Length,
+
+    /// The height of the field.
+    #[default(Em::new(1.4).into())]
+    pub height: Length,
+}
+
+impl Show for DropdownElem {
+    #[tracing::instrument(name = "DropdownElem::show", skip_all)]
+    fn show(&self, _: &mut Vt, styles: StyleChain) -> SourceResult<Content> {
+        let options = self.options();
+        let selected = self.selected(styles);
+        if selected.map_or(false, |i| i >= options.len()) {
+            bail!(self.span(), "selected option is out of bounds");
+        }
+
+        let kind = FormFieldKind::Dropdown { options, selected };
+        Ok(field(self.name(), kind, self.width(styles), self.height(styles)))
+    }
+}
+
+/// A placeholder for a digital signature in the exported PDF.
+///
+/// ## Example { #example }
+/// ```example
+/// Signature: #signature("signature")
+/// ```
+///
+/// Display: Signature
+/// Category: meta
+#[element(Show)]
+pub struct SignatureElem {
+    /// The name that identifies the field in the form's data.
+    #[required]
+    pub name: EcoString,
+
+    /// The width of the field.
+    #[default(Em::new(12.0).into())]
+    pub width: Length,
+
+    /// The height of the field.
+    #[default(Em::new(3.0).into())]
+    pub height: Length,
+}
+
+impl Show for SignatureElem {
+    #[tracing::instrument(name = "SignatureElem::show", skip_all)]
+    fn show(&self, _: &mut Vt, styles: StyleChain) -> SourceResult<Content> {
+        let kind = FormFieldKind::Signature;
+        Ok(field(self.name(), kind, self.width(styles), self.height(styles)))
+    }
+}
+
+/// Create an outlined box of the given size that exporters turn into a form
+/// field.
+fn field(name: EcoString, kind: FormFieldKind, width: Length, height: Length) -> Content {
+    let stroke = PartialStroke {
+        thickness: Smart::Custom(Abs::pt(0.5).into()),
+        ..PartialStroke::default()
+    };
+
+    BoxElem::new()
+        .with_width(Sizing::Rel(width.into()))
+        .with_height(Smart::Custom(height.into()))
+        .with_baseline(Rel::new(Ratio::new(0.25), Length::zero()))
+        .with_stroke(Sides::splat(Some(Some(stroke))))
+        .pack()
+        .styled(MetaElem::set_data(vec![Meta::Field(FormField { name, kind })]))
+}
@@ -127,7 +127,10 @@ impl Show for HeadingElem {
                 + HElem::new(Em::new(0.3).into()).with_weak(true).pack()
                 + realized;
         }
-        Ok(BlockElem::new().with_body(Some(realized)).pack())
+        Ok(BlockElem::new()
+            .with_body(Some(realized))
+            .pack()
+            .role(Role::Heading(self.level(styles))))
     }
 }
 
@@ -222,6 +225,7 @@ impl LocalName for HeadingElem {
             Lang::FRENCH => "Chapitre",
             Lang::GERMAN => "Abschnitt",
             Lang::ITALIAN => "Sezione",
+            Lang::JAPANESE => "節",
             Lang::NYNORSK => "Kapittel",
             Lang::POLISH => "Sekcja",
             Lang::PORTUGUESE if option_eq(region, "PT") => "Secção",
@@ -0,0 +1,174 @@
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+use super::{Counter, CounterKey, HeadingElem, LocalName, Numbering, NumberingPattern};
+use crate::layout::{ColumnsElem, ParbreakElem};
+use crate::prelude::*;
+use crate::text::{LinebreakElem, StrongElem, TextElem};
+
+/// Marks a term for inclusion in the index.
+///
+/// The marker itself is invisible. It records the page it ends up on so that
+/// the [`index`]($func/index) can list the term together with all pages on
+/// which it was marked.
+///
+/// ```example
+/// Typst is a markup-based
+/// typesetting system.
+/// #index-entry("typesetting")
+/// ```
+///
+/// Display: Index Entry
+/// Category: meta
+#[element(Locatable, Show)]
+pub struct IndexEntryElem {
+    /// The term under which this location is listed in the index.
+    #[required]
+    pub term: EcoString,
+}
+
+impl Show for IndexEntryElem {
+    #[tracing::instrument(name = "IndexEntryElem::show", skip_all)]
+    fn show(&self, _: &mut Vt, _: StyleChain) -> SourceResult<Content> {
+        Ok(Content::empty())
+    }
+}
+
+/// A back-of-book index.
+///
+/// Collects all terms marked with [`index-entry`]($func/index-entry) and lists
+/// them together with the pages on which they occur. Terms are grouped by
+/// their first letter and sorted by the Unicode code points of their lowercase
+/// form. This ignores case, but is not aware of language-specific collation
+/// rules: Accented letters, for instance, are sorted after `z`. Each page
+/// number links to the marked location.
+///
+/// ```example
+/// #index(columns: 1)
+///
+/// = Introduction
+/// Typst is a typesetting system.
+/// #index-entry("typesetting")
+/// #index-entry("Typst")
+/// ```
+///
+/// Display: Index
+/// Category: meta
+#[element(Show, Finalize, LocalName)]
+pub struct IndexElem {
+    /// The title of the index.
+    ///
+    /// - When set to `{auto}`, an appropriate title for the
+    ///   [text language]($func/text.lang) will be used. This is the default.
+    /// - When set to `{none}`, the index will not have a title.
+    /// - A custom title can be set by passing content.
+    #[default(Some(Smart::Auto))]
+    pub title: Option<Smart<Content>>,
+
+    /// The number of columns the index is set in.
+    #[default(NonZeroUsize::new(2).unwrap())]
+    pub columns: NonZeroUsize,
+}
+
+impl Show for IndexElem {
+    #[tracing::instrument(name = "IndexElem::show", skip_all)]
+    fn show(&self, vt: &mut Vt, styles: StyleChain) -> SourceResult<Content> {
+        let mut seq = vec![ParbreakElem::new().pack()];
+        if let Some(title) = self.title(styles) {
+            let title = title.unwrap_or_else(|| {
+                TextElem::packed(self.local_name_in(styles)).spanned(self.span())
+            });
+
+            seq.push(HeadingElem::new(title).with_level(NonZeroUsize::ONE).pack());
+        }
+
+        // Group the marked locations by term. The key makes sure that terms
+        // which only differ in case end up next to each other.
+        let mut terms: BTreeMap<(EcoString, EcoString), Vec<Location>> = BTreeMap::new();
+        let selector = Selector::Elem(IndexEntryElem::func(), None);
+        for elem in vt.introspector.query(&selector) {
+            let entry = elem.to::<IndexEntryElem>().unwrap();
+            let term = entry.term();
+            let location = elem.location().unwrap();
+            terms
+                .entry((term.to_lowercase().into(), term))
+                .or_default()
+                .push(location);
+        }
+
+        let mut body = vec![];
+        let mut group = None;
+        for ((key, term), mut locations) in terms {
+            // Start a new letter group if the initial changes.
+            let initial: Option<EcoString> =
+                key.chars().next().map(|c| c.to_uppercase().collect());
+            if initial != group {
+                if let Some(letter) = &initial {
+                    body.push(ParbreakElem::new().pack());
+                    body.push(StrongElem::new(TextElem::packed(letter.clone())).pack());
+                    body.push(LinebreakElem::new().pack());
+                }
+                group = initial;
+            }
+
+            // List every page only once, even if a term is marked multiple
+            // times on it.
+            locations.sort_by_key(|&loc| vt.introspector.page(loc));
+            locations.dedup_by_key(|loc| vt.introspector.page(*loc));
+
+            body.push(TextElem::packed(term));
+            for location in locations {
+                body.push(TextElem::packed(", "));
+                body.push(
+                    page_number(vt, location)?.linked(Destination::Location(location)),
+                );
+            }
+            body.push(LinebreakElem::new().pack());
+        }
+
+        seq.push(
+            ColumnsElem::new(Content::sequence(body))
+                .with_count(self.columns(styles))
+                .pack(),
+        );
+        seq.push(ParbreakElem::new().pack());
+
+        Ok(Content::sequence(seq))
+    }
+}
+
+impl Finalize for IndexElem {
+    fn finalize(&self, realized: Content, _: StyleChain) -> Content {
+        realized
+            .styled(HeadingElem::set_outlined(false))
+            .styled(HeadingElem::set_numbering(None))
+    }
+}
+
+impl LocalName for IndexElem {
+    fn local_name(&self, lang: Lang, _: Option<Region>) -> &'static str {
+        match lang {
+            Lang::FRENCH => "Index",
+            Lang::GERMAN => "Stichwortverzeichnis",
+            Lang::ITALIAN => "Indice analitico",
+            Lang::JAPANESE => "索引",
+            Lang::PORTUGUESE => "Índice remissivo",
+            Lang::SPANISH => "Índice alfabético",
+            Lang::ENGLISH | _ => "Index",
+        }
+    }
+}
+
+/// Display the page number of a location with the page's numbering.
+fn page_number(vt: &mut Vt, location: Location) -> SourceResult<Content> {
+    let numbering = vt
+        .introspector
+        .page_numbering(location)
+        .cast::<Option<Numbering>>()
+        .unwrap()
+        .unwrap_or_else(|| Numbering::Pattern(NumberingPattern::from_str("1").unwrap()));
+
+    Counter::new(CounterKey::Page)
+        .at(vt, location)?
+        .display(vt, &numbering)
+}
@@ -75,6 +75,26 @@ pub struct LinkElem {
         _ => args.expect("body")?,
     })]
     pub body: Content,
+
+    /// Whether to turn bare URLs and email addresses in text into links.
+    ///
+    /// Markup already turns text starting with `http://` or `https://` into
+    /// links. When this is enabled, URLs starting with `www.`, email addresses
+    /// and URLs in strings are detected, too. The resulting links can be styled
+    /// with show rules like any other link.
+    ///
+    /// In markup, an `@` starts a [reference]($func/ref), so the `@` of an
+    /// email address needs to be escaped.
+    ///
+    /// ```example
+    /// #set link(detect: true)
+    /// #show link: underline
+    ///
+    /// Write to hello\@typst.app
+    /// or visit www.typst.app.
+    /// ```
+    #[default(false)]
+    pub detect: bool,
 }
 
 impl LinkElem {
@@ -104,6 +124,73 @@ impl Show for LinkElem {
     }
 }
 
+/// Turn bare URLs and email addresses in a text into links.
+///
+/// Returns `None` if the text contains neither.
+pub fn detect_links(text: &str, span: Span) -> Option<Content> {
+    let mut seq = vec![];
+    let mut cursor = 0;
+    let mut offset = 0;
+
+    for part in text.split_inclusive(char::is_whitespace) {
+        let start = offset;
+        offset += part.len();
+
+        // Exclude surrounding punctuation, which most likely belongs to the
+        // sentence rather than the address.
+        let token = part.trim_end();
+        let trimmed = token.trim_start_matches(['(', '<', '"', '\'']);
+        let start = start + token.len() - trimmed.len();
+        let token =
+            trimmed.trim_end_matches(['.', ',', ';', ':', '!', '?', ')', '>', '"', '\'']);
+
+        let url = if token.starts_with("http://") || token.starts_with("https://") {
+            token.into()
+        } else if token.len() > 4 && token.starts_with("www.") {
+            eco_format!("https://{token}")
+        } else if is_email(token) {
+            eco_format!("mailto:{token}")
+        } else {
+            continue;
+        };
+
+        if cursor < start {
+            seq.push(TextElem::packed(&text[cursor..start]).spanned(span));
+        }
+
+        let body = TextElem::packed(token).spanned(span);
+        let dest = LinkTarget::Dest(Destination::Url(url));
+        seq.push(
+            LinkElem::new(dest, body)
+                .pack()
+                .spanned(span)
+                .styled(LinkElem::set_detect(false)),
+        );
+        cursor = start + token.len();
+    }
+
+    if seq.is_empty() {
+        return None;
+    }
+
+    if cursor < text.len() {
+        seq.push(TextElem::packed(&text[cursor..]).spanned(span));
+    }
+
+    Some(Content::sequence(seq))
+}
+
+/// Whether the text looks like an email address.
+fn is_email(text: &str) -> bool {
+    let Some((local, domain)) = text.split_once('@') else { return false };
+    !local.is_empty()
+        && local.chars().all(|c| c.is_alphanumeric() || "._%+-".contains(c))
+        && domain.contains('.')
+        && !domain.starts_with('.')
+        && !domain.ends_with('.')
+        && domain.chars().all(|c| c.is_alphanumeric() || c == '-' || c == '.')
+}
+
 fn body_from_url(url: &EcoString) -> Content {
     let mut text = url.as_str();
     for prefix in ["mailto:", "tel:"] {
@@ -4,28 +4,36 @@ mod bibliography;
 mod context;
 mod counter;
 mod document;
+mod embed;
 mod figure;
 mod footnote;
+mod form;
 mod heading;
+mod index;
 mod link;
 mod numbering;
 mod outline;
 mod query;
 mod reference;
+mod role;
 mod state;
 
 pub use self::bibliography::*;
 pub use self::context::*;
 pub use self::counter::*;
 pub use self::document::*;
+pub use self::embed::*;
 pub use self::figure::*;
 pub use self::footnote::*;
+pub use self::form::*;
 pub use self::heading::*;
+pub use self::index::*;
 pub use self::link::*;
 pub use self::numbering::*;
 pub use self::outline::*;
 pub use self::query::*;
 pub use self::reference::*;
+pub use self::role::*;
 pub use self::state::*;
 
 use crate::prelude::*;
@@ -38,10 +46,17 @@ pub(super) fn define(global: &mut Scope) {
     global.define("link", LinkElem::func());
     global.define("outline", OutlineElem::func());
     global.define("heading", HeadingElem::func());
+    global.define("index", IndexElem::func());
+    global.define("index-entry", IndexEntryElem::func());
     global.define("figure", FigureElem::func());
     global.define("footnote", FootnoteElem::func());
     global.define("cite", CiteElem::func());
     global.define("bibliography", BibliographyElem::func());
+    global.define("embed", EmbedElem::func());
+    global.define("textfield", TextFieldElem::func());
+    global.define("checkbox", CheckboxElem::func());
+    global.define("dropdown", DropdownElem::func());
+    global.define("signature", SignatureElem::func());
     global.define("locate", locate_func());
     global.define("style", style_func());
     global.define("layout", layout_func());
@@ -49,6 +64,7 @@ pub(super) fn define(global: &mut Scope) {
     global.define("numbering", numbering_func());
     global.define("state", state_func());
     global.define("query", query_func());
+    global.define("running", running_func());
     global.define("selector", selector_func());
 }
 
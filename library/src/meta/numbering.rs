@@ -36,8 +36,8 @@ use crate::text::Case;
 pub fn numbering(
     /// Defines how the numbering works.
     ///
-    /// **Counting symbols** are `1`, `a`, `A`, `i`, `I`, `い`, `イ`, `א`, `가`,
-    /// `ㄱ`, and `*`. They are replaced by the number in the sequence, in the
+    /// **Counting symbols** are `1`, `a`, `A`, `i`, `I`, `一`, `壹`, `い`, `イ`,
+    /// `א`, `가`, `ㄱ`, `①`, and `*`. They are replaced by the number in the sequence, in the
     /// given case.
     ///
     /// The `*` character means that symbols should be used to count, in the
@@ -127,7 +127,8 @@ cast! {
 /// How to turn a number into text.
 ///
 /// A pattern consists of a prefix, followed by one of `1`, `a`, `A`, `i`,
-/// `I`, `い`, `イ`, `א`, `가`, `ㄱ`, or `*`, and then a suffix.
+/// `I`, `一`, `壹`, `い`, `イ`, `א`, `가`, `ㄱ`, `①`, or `*`, and then a
+/// suffix.
 ///
 /// Examples of valid patterns:
 /// - `1)`
@@ -191,6 +192,49 @@ impl NumberingPattern {
         fmt
     }
 
+    /// Describe how a page with the given number is labelled in a document
+    /// viewer.
+    ///
+    /// Patterns with a single counting symbol that viewers know how to count
+    /// with yield a styled label. Everything else is labelled with its full
+    /// formatted text.
+    pub fn page_label(&self, number: usize) -> PageLabel {
+        let fallback = PageLabel {
+            prefix: self.apply_kth(0, number),
+            style: None,
+            number,
+        };
+
+        let [(prefix, kind, case)] = self.pieces.as_slice() else {
+            return fallback;
+        };
+
+        if number == 0 || !self.suffix.is_empty() {
+            return fallback;
+        }
+
+        // Viewers count letters as a, ..., z, aa, ..., zz and know no roman
+        // numerals beyond 3999, so we only use these where they agree with us.
+        let style = match (kind, case) {
+            (NumberingKind::Arabic, _) => PageLabelStyle::Arabic,
+            (NumberingKind::Roman, Case::Lower) if number < 4000 => {
+                PageLabelStyle::LowerRoman
+            }
+            (NumberingKind::Roman, Case::Upper) if number < 4000 => {
+                PageLabelStyle::UpperRoman
+            }
+            (NumberingKind::Letter, Case::Lower) if number <= 26 => {
+                PageLabelStyle::LowerAlpha
+            }
+            (NumberingKind::Letter, Case::Upper) if number <= 26 => {
+                PageLabelStyle::UpperAlpha
+            }
+            _ => return fallback,
+        };
+
+        PageLabel { prefix: prefix.clone(), style: Some(style), number }
+    }
+
     /// How many counting symbols this pattern has.
     pub fn pieces(&self) -> usize {
         self.pieces.len()
@@ -263,6 +307,7 @@ enum NumberingKind {
     KatakanaIroha,
     KoreanJamo,
     KoreanSyllable,
+    Circled,
 }
 
 impl NumberingKind {
@@ -279,6 +324,7 @@ impl NumberingKind {
             'イ' => NumberingKind::KatakanaIroha,
             'ㄱ' => NumberingKind::KoreanJamo,
             '가' => NumberingKind::KoreanSyllable,
+            '①' => NumberingKind::Circled,
             _ => return None,
         })
     }
@@ -297,6 +343,7 @@ impl NumberingKind {
             Self::KatakanaIroha => 'イ',
             Self::KoreanJamo => 'ㄱ',
             Self::KoreanSyllable => '가',
+            Self::Circled => '①',
         }
     }
 
@@ -480,6 +527,21 @@ impl NumberingKind {
                 },
                 n,
             ),
+            Self::Circled => {
+                // Unicode only has circled numbers up to fifty, spread
+                // over three blocks. Larger numbers fall back to arabic.
+                let c = match n {
+                    0 => Some('⓪'),
+                    1..=20 => char::from_u32(0x2460 + n as u32 - 1),
+                    21..=35 => char::from_u32(0x3251 + n as u32 - 21),
+                    36..=50 => char::from_u32(0x32B1 + n as u32 - 36),
+                    _ => None,
+                };
+                match c {
+                    Some(c) => c.into(),
+                    None => eco_format!("{n}"),
+                }
+            }
         }
     }
 }
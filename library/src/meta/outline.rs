@@ -271,6 +271,7 @@ impl LocalName for OutlineElem {
             Lang::FRENCH => "Table des matières",
             Lang::GERMAN => "Inhaltsverzeichnis",
             Lang::ITALIAN => "Indice",
+            Lang::JAPANESE => "目次",
             Lang::NYNORSK => "Innhald",
             Lang::POLISH => "Spis treści",
             Lang::PORTUGUESE if option_eq(region, "PT") => "Índice",
@@ -1,3 +1,5 @@
+use comemo::Prehashed;
+
 use crate::prelude::*;
 
 /// Finds elements in the document.
@@ -100,8 +102,9 @@ use crate::prelude::*;
 /// Category: meta
 #[func]
 pub fn query(
-    /// Can be an element function like a `heading` or `figure`, a `{<label>}`
-    /// or a more complex selector like `{heading.where(level: 1)}`.
+    /// Can be an element function like a `heading` or `figure`, a `{<label>}`,
+    /// a [location]($type/location) or a more complex selector like
+    /// `{heading.where(level: 1)}`.
     ///
     /// Currently, only a subset of element functions is supported. Aside from
     /// headings and figures, this includes equations, references and all
@@ -128,6 +131,93 @@ pub fn query(
         .collect()
 }
 
+/// Finds the element that is current on the page of a location.
+///
+/// This is useful for running heads: Page headers and footers that display
+/// the title of the current chapter or section. For a location on a page, the
+/// function looks at the elements matching the target and returns
+///
+/// - with `{"last"}`: the last element on this or any previous page. This is
+///   the section the page ends in.
+/// - with `{"first"}`: the first element on this page or, if there is none, the
+///   last element on any previous page. This is the section the page starts
+///   with or is in, like the first entry at the top of a dictionary page.
+///
+/// If there is no such element, the function returns `{none}`.
+///
+/// ```example
+/// >>> #set page(
+/// >>>   width: 240pt,
+/// >>>   height: 180pt,
+/// >>>   margin: (top: 35pt, rest: 15pt),
+/// >>>   header-ascent: 12pt,
+/// >>> )
+/// #set page(header: locate(loc => {
+///   let elem = running(heading, loc)
+///   if elem != none {
+///     align(right, emph(elem.body))
+///   }
+/// }))
+///
+/// = Introduction
+/// #lorem(23)
+///
+/// = Background
+/// #lorem(30)
+///
+/// = Analysis
+/// #lorem(15)
+/// ```
+///
+/// Display: Running
+/// Category: meta
+#[func]
+pub fn running(
+    /// Can be an element function like a `heading` or `figure`, a `{<label>}`
+    /// or a more complex selector like `{heading.where(level: 1)}`.
+    target: LocatableSelector,
+    /// The location whose page to look at, typically the location of a page
+    /// header or footer.
+    location: Location,
+    /// Which of the elements on the page to pick.
+    #[named]
+    #[default(RunningMode::Last)]
+    mode: RunningMode,
+    /// The virtual machine.
+    vm: &mut Vm,
+) -> Value {
+    let introspector = &vm.vt.introspector;
+    let page = introspector.page(location);
+    let elems = introspector.query(&target.0);
+    let page_of = |elem: &Prehashed<Content>| {
+        elem.location().map_or(page, |loc| introspector.page(loc))
+    };
+
+    let last_before = |limit| elems.iter().filter(|&elem| page_of(elem) <= limit).last();
+    let found = match mode {
+        RunningMode::Last => last_before(page),
+        RunningMode::First => {
+            elems.iter().find(|&elem| page_of(elem) == page).or_else(|| {
+                NonZeroUsize::new(page.get() - 1).and_then(|prev| last_before(prev))
+            })
+        }
+    };
+
+    match found {
+        Some(elem) => Value::Content(elem.clone().into_inner()),
+        None => Value::None,
+    }
+}
+
+/// Which element is picked on a page by [`running`]($func/running).
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Cast)]
+pub enum RunningMode {
+    /// The first element on the page, or the last on a previous page.
+    First,
+    /// The last element on the page or a previous page.
+    Last,
+}
+
 /// Turns a value into a selector. The following values are accepted:
 /// - An element function like a `heading` or `figure`.
 /// - A `{<label>}`.
@@ -0,0 +1,36 @@
+use crate::prelude::*;
+
+/// Attaches a semantic role to the frames of its body.
+///
+/// Unlike metadata set through styles, which every piece of the content
+/// carries, the role is attached once to each frame the body produces. It is
+/// thus only meaningful for block-level content.
+///
+/// Display: Role
+/// Category: meta
+#[element(Layout)]
+pub struct RoleElem {
+    /// The content the role applies to.
+    #[required]
+    pub body: Content,
+
+    /// The role of the content.
+    #[required]
+    pub role: Role,
+}
+
+impl Layout for RoleElem {
+    #[tracing::instrument(name = "RoleElem::layout", skip_all)]
+    fn layout(
+        &self,
+        vt: &mut Vt,
+        styles: StyleChain,
+        regions: Regions,
+    ) -> SourceResult<Fragment> {
+        let mut fragment = self.body().layout(vt, styles, regions)?;
+        for frame in &mut fragment {
+            frame.meta_iter([Meta::Role(self.role())]);
+        }
+        Ok(fragment)
+    }
+}
@@ -1,6 +1,7 @@
 //! Extension traits.
 
 use crate::layout::{AlignElem, MoveElem, PadElem};
+use crate::meta::RoleElem;
 use crate::prelude::*;
 use crate::text::{EmphElem, FontFamily, FontList, StrongElem, TextElem, UnderlineElem};
 
@@ -23,6 +24,9 @@ pub trait ContentExt {
     /// Should be used in combination with [`Location::variant`].
     fn backlinked(self, loc: Location) -> Self;
 
+    /// Mark this content with a semantic role for exporters.
+    fn role(self, role: Role) -> Self;
+
     /// Set alignments for this content.
     fn aligned(self, aligns: Axes<Option<GenAlign>>) -> Self;
 
@@ -56,6 +60,10 @@ impl ContentExt for Content {
         self.styled(MetaElem::set_data(vec![Meta::Elem(backlink)]))
     }
 
+    fn role(self, role: Role) -> Self {
+        RoleElem::new(self, role).pack()
+    }
+
     fn aligned(self, aligns: Axes<Option<GenAlign>>) -> Self {
         self.styled(AlignElem::set_alignment(aligns))
     }
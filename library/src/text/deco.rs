@@ -235,6 +235,55 @@ impl Show for StrikeElem {
     }
 }
 
+/// Highlights text with a background color.
+///
+/// The highlight spans the full height of the font's ascender and descender
+/// and follows the text across line breaks.
+///
+/// ## Example { #example }
+/// ```example
+/// This is #highlight[important].
+/// ```
+///
+/// Display: Highlight
+/// Category: text
+#[element(Show)]
+pub struct HighlightElem {
+    /// The color to highlight the text with.
+    ///
+    /// ```example
+    /// This is #highlight(fill: blue)[with blue].
+    /// ```
+    #[default(Color::Rgba(RgbaColor::new(0xFF, 0xFD, 0x11, 0xA1)).into())]
+    pub fill: Paint,
+
+    /// The amount by which to extend the background to the sides beyond
+    /// (or within if negative) the content.
+    ///
+    /// ```example
+    /// A long #highlight(extent: 4pt)[background].
+    /// ```
+    #[resolve]
+    pub extent: Length,
+
+    /// The content that should be highlighted.
+    #[required]
+    pub body: Content,
+}
+
+impl Show for HighlightElem {
+    #[tracing::instrument(name = "HighlightElem::show", skip_all)]
+    fn show(&self, _: &mut Vt, styles: StyleChain) -> SourceResult<Content> {
+        Ok(self.body().styled(TextElem::set_deco(Decoration {
+            line: DecoLine::Highlight { fill: self.fill(styles) },
+            stroke: PartialStroke::default(),
+            offset: Smart::Auto,
+            extent: self.extent(styles),
+            evade: false,
+        })))
+    }
+}
+
 /// Defines a line that is positioned over, under or on top of text.
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
 pub struct Decoration {
@@ -259,11 +308,19 @@ cast! {
 }
 
 /// A kind of decorative line.
-#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
 pub enum DecoLine {
     Underline,
     Strikethrough,
     Overline,
+    Highlight { fill: Paint },
+}
+
+impl DecoLine {
+    /// Whether the decoration is drawn below the text instead of on top of it.
+    pub fn is_background(&self) -> bool {
+        matches!(self, Self::Highlight { .. })
+    }
 }
 
 /// Add line decorations to a single run of shaped text.
@@ -276,10 +333,19 @@ pub(super) fn decorate(
     width: Abs,
 ) {
     let font_metrics = text.font.metrics();
-    let metrics = match deco.line {
+    let metrics = match &deco.line {
         DecoLine::Strikethrough => font_metrics.strikethrough,
         DecoLine::Overline => font_metrics.overline,
         DecoLine::Underline => font_metrics.underline,
+        DecoLine::Highlight { fill } => {
+            let top = font_metrics.ascender.at(text.size);
+            let bottom = font_metrics.descender.at(text.size);
+            let origin = Point::new(pos.x - deco.extent, pos.y - top);
+            let size = Size::new(width + 2.0 * deco.extent, top - bottom);
+            let shape = Geometry::Rect(size).filled(fill.clone());
+            frame.push(origin, FrameItem::Shape(shape, Span::detached()));
+            return;
+        }
     };
 
     let offset = deco.offset.unwrap_or(-metrics.position.at(text.size)) - shift;
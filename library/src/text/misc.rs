@@ -1,4 +1,6 @@
-use super::TextElem;
+use typst::font::LineMetrics;
+
+use super::{families, variant, TextDir, TextElem};
 use crate::prelude::*;
 
 /// A text space.
@@ -303,6 +305,40 @@ pub fn smallcaps(
     body.styled(TextElem::set_smallcaps(true))
 }
 
+/// Isolates content from the surrounding text's bidirectional ordering.
+///
+/// Within an isolate, text is ordered on its own and the isolate as a whole is
+/// treated like a single neutral character by the surrounding text. This keeps
+/// a quotation in one direction from pulling adjacent punctuation or numbers of
+/// a paragraph in the other direction into its run.
+///
+/// ## Example { #example }
+/// ```example
+/// #set text(lang: "ar")
+/// قال #bidi-isolate["Hello, world!"] ثم غادر.
+/// ```
+///
+/// Display: BiDi Isolate
+/// Category: text
+#[func]
+pub fn bidi_isolate(
+    /// The content to isolate.
+    body: Content,
+    /// The direction of the isolated content. When `{auto}`, it is determined
+    /// from the first strongly directional character in the content.
+    #[named]
+    #[default]
+    dir: TextDir,
+) -> Content {
+    let open = match dir.0 {
+        Smart::Auto => '\u{2068}',
+        Smart::Custom(Dir::RTL) => '\u{2067}',
+        Smart::Custom(_) => '\u{2066}',
+    };
+
+    Content::sequence([TextElem::packed(open), body, TextElem::packed('\u{2069}')])
+}
+
 /// Creates blind text.
 ///
 /// This function yields a Latin-like _Lorem Ipsum_ blind text with the given
@@ -328,3 +364,64 @@ pub fn lorem(
 ) -> Str {
     lipsum::lipsum(words).replace("--", "–").into()
 }
+
+/// Retrieves the metrics of the font that is used for text.
+///
+/// Looks up the first available font in the text's font family list and
+/// returns its vertical metrics, resolved at the current text size. Like with
+/// [`measure`]($func/measure), you first need to retrieve the active styles
+/// with the [`style`]($func/style) function.
+///
+/// ## Example { #example }
+/// ```example
+/// #style(styles => {
+///   let metrics = font-metrics(styles)
+///   [The cap height is #metrics.cap-height.]
+/// })
+/// ```
+///
+/// The returned dictionary contains the lengths `ascender`, `cap-height`,
+/// `x-height` and `descender`, measured upwards from the baseline. It also
+/// contains the dictionaries `underline`, `strikethrough` and `overline` with
+/// the recommended `position` and `thickness` of these decorative lines.
+///
+/// Display: Font Metrics
+/// Category: text
+#[func]
+pub fn font_metrics(
+    /// The styles with which to look up the font.
+    styles: Styles,
+    /// The virtual machine.
+    vm: &mut Vm,
+) -> StrResult<Dict> {
+    let styles = StyleChain::new(&styles);
+    let world = vm.world();
+    let variant = variant(styles);
+    let font = families(styles)
+        .find_map(|family| {
+            world
+                .book()
+                .select(family.as_str(), variant)
+                .and_then(|id| world.font(id))
+        })
+        .ok_or("no font could be found")?;
+
+    let size = TextElem::size_in(styles);
+    let metrics = font.metrics();
+    let line = |line: LineMetrics| {
+        dict! {
+            "position" => line.position.at(size),
+            "thickness" => line.thickness.at(size),
+        }
+    };
+
+    Ok(dict! {
+        "ascender" => metrics.ascender.at(size),
+        "cap-height" => metrics.cap_height.at(size),
+        "x-height" => metrics.x_height.at(size),
+        "descender" => metrics.descender.at(size),
+        "underline" => line(metrics.underline),
+        "strikethrough" => line(metrics.strikethrough),
+        "overline" => line(metrics.overline),
+    })
+}
@@ -4,18 +4,23 @@ mod deco;
 mod misc;
 mod quotes;
 mod raw;
+mod ruby;
 mod shaping;
 mod shift;
+mod truncate;
 
 pub use self::deco::*;
 pub use self::misc::*;
 pub use self::quotes::*;
 pub use self::raw::*;
+pub use self::ruby::*;
 pub use self::shaping::*;
 pub use self::shift::*;
+pub use self::truncate::*;
 
 use rustybuzz::Tag;
 use typst::font::{FontMetrics, FontStretch, FontStyle, FontWeight, VerticalFontMetric};
+use unicode_script::Script;
 
 use crate::layout::ParElem;
 use crate::prelude::*;
@@ -30,13 +35,18 @@ pub(super) fn define(global: &mut Scope) {
     global.define("lower", lower_func());
     global.define("upper", upper_func());
     global.define("smallcaps", smallcaps_func());
+    global.define("bidi-isolate", bidi_isolate_func());
     global.define("sub", SubElem::func());
     global.define("super", SuperElem::func());
     global.define("underline", UnderlineElem::func());
     global.define("strike", StrikeElem::func());
     global.define("overline", OverlineElem::func());
+    global.define("highlight", HighlightElem::func());
     global.define("raw", RawElem::func());
+    global.define("ruby", RubyElem::func());
+    global.define("truncate", TruncateElem::func());
     global.define("lorem", lorem_func());
+    global.define("font-metrics", font_metrics_func());
 }
 
 /// Customizes the look and layout of text in a variety of ways.
@@ -79,6 +89,29 @@ pub struct TextElem {
     #[default(FontList(vec![FontFamily::new("Linux Libertine")]))]
     pub font: FontList,
 
+    /// Font families to prefer for specific scripts.
+    ///
+    /// A dictionary mapping script names to a font family or a prioritized
+    /// list of font families. When a run of text is written in one of the
+    /// given scripts, its families are tried before the ones in the
+    /// [`font`]($func/text.font) list. This way, mixed-script paragraphs pick
+    /// the right face for each run without manual font switching.
+    ///
+    /// Scripts are identified by their Unicode name in lowercase, like
+    /// `{"latin"}`, `{"greek"}` or `{"arabic"}`. The special name `{"cjk"}`
+    /// covers Han, Hiragana, Katakana, Bopomofo and Hangul.
+    ///
+    /// ```example
+    /// #set text(script-font: (
+    ///   cjk: "Noto Serif CJK SC",
+    ///   arabic: "Noto Sans Arabic",
+    /// ))
+    ///
+    /// Typst 排版 هذا عربي
+    /// ```
+    #[fold]
+    pub script_font: ScriptFonts,
+
     /// Whether to allow last resort font fallback when the primary font list
     /// contains no match. This lets Typst search through all available fonts
     /// for the most similar one that has the necessary glyphs.
@@ -204,6 +237,20 @@ pub struct TextElem {
     #[default(Rel::one())]
     pub spacing: Rel<Length>,
 
+    /// How to handle spaces, tabs and newlines in text.
+    ///
+    /// Spaces and newlines in markup are always collapsed into single spaces.
+    /// This property controls whitespace in text that stems from strings or
+    /// raw text.
+    ///
+    /// ```example
+    /// #let poem = "Roses  are  red,\nviolets  are  blue."
+    /// #text(whitespace: "collapse", poem) \
+    /// #text(whitespace: "pre-wrap", poem)
+    /// ```
+    #[default(Whitespace::PreWrap)]
+    pub whitespace: Whitespace,
+
     /// An amount to shift the text baseline by.
     ///
     /// ```example
@@ -216,6 +263,10 @@ pub struct TextElem {
     /// Whether certain glyphs can hang over into the margin in justified text.
     /// This can make justification visually more pleasing.
     ///
+    /// Dashes and punctuation hang into the end margin. Quotation marks only
+    /// hang into the margins if [`overhang-quotes`]($func/text.overhang-quotes)
+    /// is enabled.
+    ///
     /// ```example
     /// #set par(justify: true)
     /// This justified text has a hyphen in
@@ -232,6 +283,20 @@ pub struct TextElem {
     #[default(true)]
     pub overhang: bool,
 
+    /// Whether quotation marks hang into the margins when
+    /// [`overhang`]($func/text.overhang) is enabled. Opening quotes hang into
+    /// the start margin and closing quotes into the end margin.
+    ///
+    /// ```example
+    /// #set par(justify: true)
+    /// #set text(overhang-quotes: true)
+    /// "Hanging quotation marks into
+    /// the margin keeps the edge of the
+    /// paragraph visually straight."
+    /// ```
+    #[default(false)]
+    pub overhang_quotes: bool,
+
     /// The top end of the conceptual frame around the text used for layout and
     /// positioning. This affects the size of containers that hold text.
     ///
@@ -426,6 +491,9 @@ pub struct TextElem {
     /// The width of numbers / figures. When set to `{auto}`, the default
     /// numbers for the font are used.
     ///
+    /// If tabular numbers are requested, but the font has no `tnum` feature,
+    /// Typst evens out the advances of the digits itself.
+    ///
     /// ```example
     /// #set text(font: "Noto Sans", 20pt)
     /// #set text(number-width: "proportional")
@@ -466,7 +534,8 @@ pub struct TextElem {
     /// - If given an array of strings, sets the features identified by the
     ///   strings to `{1}`.
     /// - If given a dictionary mapping to numbers, sets the features
-    ///   identified by the keys to the values.
+    ///   identified by the keys to the values. Booleans can be used to turn
+    ///   features on or off, mapping to `{1}` and `{0}` respectively.
     ///
     /// ```example
     /// // Enable the `frac` feature manually.
@@ -588,6 +657,93 @@ cast! {
     values: Array => Self(values.into_iter().map(|v| v.cast()).collect::<StrResult<_>>()?),
 }
 
+/// Font families to prefer for specific scripts.
+#[derive(Debug, Default, Clone, Eq, PartialEq, Hash)]
+pub struct ScriptFonts(pub Vec<(EcoString, FontList)>);
+
+impl ScriptFonts {
+    /// The families to prefer for text in the given script.
+    pub fn get(&self, script: Script) -> impl Iterator<Item = FontFamily> + Clone {
+        self.0
+            .iter()
+            .find(|(name, _)| script_matches(name, script))
+            .map(|(_, list)| list.0.clone())
+            .unwrap_or_default()
+            .into_iter()
+    }
+}
+
+cast! {
+    ScriptFonts,
+    self => self.0
+        .into_iter()
+        .map(|(name, list)| (name.into(), list.into_value()))
+        .collect::<Dict>()
+        .into_value(),
+    values: Dict => Self(values
+        .into_iter()
+        .map(|(k, v)| {
+            let name: EcoString = k.as_str().to_lowercase().into();
+            if name != "cjk" && !SCRIPTS.iter().any(|&s| script_matches(&name, s)) {
+                bail!("unknown script: {}", k);
+            }
+            Ok((name, v.cast::<FontList>()?))
+        })
+        .collect::<StrResult<_>>()?),
+}
+
+impl Fold for ScriptFonts {
+    type Output = Self;
+
+    fn fold(mut self, outer: Self::Output) -> Self::Output {
+        self.0.extend(outer.0);
+        self
+    }
+}
+
+/// Scripts that can be configured by name.
+const SCRIPTS: &[Script] = &[
+    Script::Arabic,
+    Script::Armenian,
+    Script::Bengali,
+    Script::Bopomofo,
+    Script::Cyrillic,
+    Script::Devanagari,
+    Script::Ethiopic,
+    Script::Georgian,
+    Script::Greek,
+    Script::Gujarati,
+    Script::Gurmukhi,
+    Script::Han,
+    Script::Hangul,
+    Script::Hebrew,
+    Script::Hiragana,
+    Script::Kannada,
+    Script::Katakana,
+    Script::Khmer,
+    Script::Lao,
+    Script::Latin,
+    Script::Malayalam,
+    Script::Mongolian,
+    Script::Myanmar,
+    Script::Sinhala,
+    Script::Syriac,
+    Script::Tamil,
+    Script::Telugu,
+    Script::Thaana,
+    Script::Thai,
+    Script::Tibetan,
+];
+
+/// Whether a configured script name covers the given script.
+fn script_matches(name: &str, script: Script) -> bool {
+    use Script::*;
+    match name {
+        "cjk" => matches!(script, Han | Hiragana | Katakana | Bopomofo | Hangul),
+        _ => script.full_name().eq_ignore_ascii_case(name),
+    }
+}
+
 /// The size of text.
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
 pub struct TextSize(pub Length);
@@ -707,6 +863,62 @@ cast! {
     },
 }
 
+/// How to handle whitespace in text.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Cast)]
+pub enum Whitespace {
+    /// Runs of spaces, tabs and newlines are collapsed into a single space.
+    Collapse,
+    /// Spaces, tabs and newlines are kept as they are. Lines only break at
+    /// newlines.
+    Preserve,
+    /// Spaces, tabs and newlines are kept as they are, but lines may also
+    /// break at spaces.
+    PreWrap,
+}
+
+impl Whitespace {
+    /// Append text to a paragraph's full text according to this mode.
+    ///
+    /// Returns the pieces the text was appended in as pairs of their length in
+    /// the full text and their start offset in the given text. Since
+    /// collapsing drops characters, these offsets differ from the pieces'
+    /// offsets in the full text.
+    pub fn apply(self, full: &mut String, text: &str) -> Vec<(usize, usize)> {
+        match self {
+            Self::Collapse => {
+                let mut pieces = vec![];
+                let mut start = full.len();
+                let mut offset = 0;
+                for (i, c) in text.char_indices() {
+                    if !matches!(c, ' ' | '\t' | '\n' | '\r') {
+                        full.push(c);
+                    } else if !full.ends_with(' ') {
+                        full.push(' ');
+                    } else {
+                        // The character is dropped, so a new piece starts
+                        // behind it.
+                        if full.len() > start {
+                            pieces.push((full.len() - start, offset));
+                        }
+                        start = full.len();
+                        offset = i + c.len_utf8();
+                    }
+                }
+                if full.len() > start {
+                    pieces.push((full.len() - start, offset));
+                }
+                pieces
+            }
+            // Preserved text is kept as is. That lines don't break at its
+            // spaces is handled by the line breaker.
+            Self::Preserve | Self::PreWrap => {
+                full.push_str(text);
+                vec![(text.len(), 0)]
+            }
+        }
+    }
+}
+
 /// Which kind of numbers / figures to select.
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Cast)]
 pub enum NumberType {
@@ -752,7 +964,10 @@ cast! {
     values: Dict => Self(values
         .into_iter()
         .map(|(k, v)| {
-            let num = v.cast::<u32>()?;
+            let num = match v {
+                Value::Bool(enabled) => u32::from(enabled),
+                v => v.cast::<u32>()?,
+            };
             let tag = Tag::from_bytes_lossy(k.as_bytes());
             Ok((tag, num))
         })
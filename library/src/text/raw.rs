@@ -240,10 +240,13 @@ impl LocalName for RawElem {
             Lang::FRENCH => "Liste",
             Lang::GERMAN => "Listing",
             Lang::ITALIAN => "Codice",
+            Lang::JAPANESE => "リスト",
             Lang::NYNORSK => "Utskrift",
             Lang::POLISH => "Program",
+            Lang::PORTUGUESE => "Listagem",
             Lang::RUSSIAN => "Листинг",
             Lang::SLOVENIAN => "Program",
+            Lang::SPANISH => "Listado",
             Lang::SWEDISH => "Listing",
             Lang::TURKISH => "Liste",
             Lang::UKRAINIAN => "Лістинг",
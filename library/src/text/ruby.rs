@@ -0,0 +1,93 @@
+use super::{TextElem, TextSize};
+use crate::layout::{BoxElem, PadElem, StackChild, StackElem};
+use crate::prelude::*;
+
+/// Annotates text with small ruby text above it.
+///
+/// Ruby annotations are typically used to indicate the pronunciation of CJK
+/// characters. The annotation is centered above its base text. If the
+/// annotation is wider than the base text, it may overhang the adjacent text
+/// and the base text is centered below it. The line grows to make room for
+/// the annotation.
+///
+/// There are a few limitations: The whole annotation is centered above the
+/// whole base text, so compounds aren't annotated character by character as
+/// with jukugo ruby. Typst doesn't look at the adjacent text to decide whether
+/// the annotation may overhang it. Where the overhang doesn't suffice, the
+/// base text is spaced apart from the adjacent text. Vertical writing, where
+/// the annotation would be placed beside the base text, isn't supported.
+///
+/// ## Example { #example }
+/// ```example
+/// #set text(lang: "ja")
+/// #ruby[東京][とうきょう]に行きます。
+/// ```
+///
+/// Display: Ruby
+/// Category: text
+#[element(Show)]
+pub struct RubyElem {
+    /// The font size of the annotation.
+    ///
+    /// ```example
+    /// #ruby(size: 0.7em)[漢字][かんじ]
+    /// ```
+    #[default(TextSize(Em::new(0.5).into()))]
+    pub size: TextSize,
+
+    /// The gap between the base text and the annotation.
+    #[default(Em::new(0.1).into())]
+    pub gap: Length,
+
+    /// How far an annotation that is wider than its base text may extend
+    /// over the adjacent text on each side.
+    ///
+    /// Annotations typically overhang adjacent kana, but not kanji. Set this
+    /// to `{0pt}` where the annotation would cover kanji.
+    ///
+    /// ```example
+    /// #set text(lang: "ja")
+    /// の#ruby[聞][きこえ]る \
+    /// 新#ruby(overhang: 0pt)[聞][ぶん]紙
+    /// ```
+    #[resolve]
+    #[default(Em::new(0.25).into())]
+    pub overhang: Length,
+
+    /// The text to annotate.
+    #[required]
+    pub base: Content,
+
+    /// The annotation to display above the base text.
+    #[required]
+    pub annotation: Content,
+}
+
+impl Show for RubyElem {
+    #[tracing::instrument(name = "RubyElem::show", skip_all)]
+    fn show(&self, vt: &mut Vt, styles: StyleChain) -> SourceResult<Content> {
+        let center = Axes::with_x(Some(Align::Center.into()));
+        let annotation = self.annotation().styled(TextElem::set_size(self.size(styles)));
+
+        // Determine how far the annotation overhangs the base text on each
+        // side, up to the allowed amount.
+        let pod = Regions::one(Size::splat(Abs::inf()), Axes::splat(false));
+        let base_width = self.base().measure(vt, styles, pod)?.into_frame().width();
+        let annotation_width = annotation.measure(vt, styles, pod)?.into_frame().width();
+        let overhang = ((annotation_width - base_width) / 2.0)
+            .min(self.overhang(styles))
+            .max(Abs::zero());
+
+        let stack = StackElem::new(vec![
+            StackChild::Block(annotation.aligned(center)),
+            StackChild::Block(self.base().aligned(center)),
+        ])
+        .with_spacing(Some(self.gap(styles).into()))
+        .pack();
+
+        // Negative padding lets the annotation extend beyond the box.
+        let inset = Rel::from(Length::from(-overhang));
+        let body = PadElem::new(stack).with_left(inset).with_right(inset).pack();
+        Ok(BoxElem::new().with_body(Some(body)).pack())
+    }
+}
@@ -1,11 +1,14 @@
 use std::borrow::Cow;
 use std::ops::Range;
 use std::str::FromStr;
+use std::sync::Arc;
 
 use az::SaturatingAs;
-use rustybuzz::{Feature, Tag, UnicodeBuffer};
-use typst::font::{Font, FontStyle, FontVariant};
-use typst::util::SliceExt;
+use rustybuzz::{Feature, GlyphInfo, GlyphPosition, Tag, UnicodeBuffer};
+use typst::eval::Tracer;
+use typst::font::{Font, FontStyle, FontVariant, FontWeight};
+use typst::model::DelayedErrors;
+use typst::util::{hash128, SliceExt};
 use unicode_script::{Script, UnicodeScript};
 
 use super::{decorate, FontFamily, NumberType, NumberWidth, TextElem};
@@ -38,6 +41,11 @@ pub struct ShapedText<'a> {
     pub width: Abs,
     /// The shaped glyphs.
     pub glyphs: Cow<'a, [ShapedGlyph]>,
+    /// Warnings raised while shaping, to be reported by the caller.
+    pub warnings: Vec<SourceError>,
+    /// Identifies the runs of text that were shaped with a single font, to be
+    /// recorded in the tracer by the caller.
+    pub runs: Vec<u128>,
 }
 
 /// A single glyph resulting from shaping.
@@ -293,11 +301,17 @@ impl<'a> ShapedText<'a> {
                 glyphs,
             };
 
-            let layer = frame.layer();
             let width = item.width();
 
+            // Apply background decorations below the text.
+            for deco in decos.iter().filter(|deco| deco.line.is_background()) {
+                decorate(&mut frame, deco, &item, shift, pos, width);
+            }
+
+            let layer = frame.layer();
+
             // Apply line decorations.
-            for deco in &decos {
+            for deco in decos.iter().filter(|deco| !deco.line.is_background()) {
                 decorate(&mut frame, deco, &item, shift, pos, width);
             }
 
@@ -407,6 +421,8 @@ impl<'a> ShapedText<'a> {
                 variant: self.variant,
                 width: glyphs.iter().map(|g| g.x_advance).sum::<Em>().at(self.size),
                 glyphs: Cow::Borrowed(glyphs),
+                warnings: vec![],
+                runs: vec![],
             }
         } else {
             shape(
@@ -532,6 +548,8 @@ struct ShapingContext<'a, 'v> {
     tags: Vec<rustybuzz::Feature>,
     fallback: bool,
     dir: Dir,
+    warnings: Vec<SourceError>,
+    runs: Vec<u128>,
 }
 
 /// Shape text into [`ShapedText`].
@@ -558,12 +576,22 @@ pub fn shape<'a>(
         tags: tags(styles),
         fallback: TextElem::fallback_in(styles),
         dir,
+        warnings: vec![],
+        runs: vec![],
     };
 
     if !text.is_empty() {
-        shape_segment(&mut ctx, base, text, families(styles));
+        // Prefer the families configured for the run's script, if any.
+        let script = text
+            .chars()
+            .map(|c| c.script())
+            .find(|s| !matches!(s, Script::Unknown | Script::Common | Script::Inherited))
+            .unwrap_or(Script::Unknown);
+        let preferred = TextElem::script_font_in(styles).get(script);
+        shape_segment(&mut ctx, base, text, preferred.chain(families(styles)));
     }
 
+    synthesize_tabular(&mut ctx);
     track_and_space(&mut ctx);
     calculate_adjustability(&mut ctx, lang, region);
 
@@ -583,7 +611,66 @@ pub fn shape<'a>(
         size,
         width: ctx.glyphs.iter().map(|g| g.x_advance).sum::<Em>().at(size),
         glyphs: Cow::Owned(ctx.glyphs),
+        warnings: ctx.warnings,
+        runs: ctx.runs,
+    }
+}
+
+/// Metrics of a piece of text, see [`measure_text`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextMetrics {
+    /// The advance width of the text, including kerning and tracking.
+    pub width: Abs,
+    /// The extent of the text above the baseline, as determined by the
+    /// text's top edge.
+    pub ascent: Abs,
+    /// The extent of the text below the baseline, as determined by the
+    /// text's bottom edge.
+    pub descent: Abs,
+    /// The text's clusters in visual order, each with its byte range in the
+    /// text and its advance width. Carets can be placed between clusters, but
+    /// not within them.
+    pub clusters: Vec<(Range<usize>, Abs)>,
+}
+
+/// Measure a single line of text the way the layouter would set it.
+///
+/// The text is shaped with the font, size, features and spacing configured in
+/// the `styles`, so the results are consistent with laid out paragraphs. This
+/// is meant for applications that embed Typst and need to position carets or
+/// selections within text. Outside of a document, the library's default
+/// styles can be used through `StyleChain::new(&world.library().styles)`.
+pub fn measure_text(world: &dyn World, text: &str, styles: StyleChain) -> TextMetrics {
+    let introspector = Introspector::default();
+    let mut locator = Locator::new();
+    let mut delayed = DelayedErrors::default();
+    let mut tracer = Tracer::default();
+    let vt = Vt {
+        world: world.track(),
+        introspector: introspector.track(),
+        locator: &mut locator,
+        delayed: delayed.track_mut(),
+        tracer: tracer.track_mut(),
+    };
+
+    let vt = &vt;
+    let spans = SpanMapper::new();
+    let dir = TextElem::dir_in(styles);
+    let lang = TextElem::lang_in(styles);
+    let region = TextElem::region_in(styles);
+    let shaped = shape(vt, 0, text, &spans, styles, dir, lang, region);
+    let (ascent, descent) = shaped.measure(vt);
+
+    let mut clusters: Vec<(Range<usize>, Abs)> = vec![];
+    for glyph in shaped.glyphs.iter() {
+        let advance = glyph.x_advance.at(shaped.size);
+        match clusters.last_mut() {
+            Some((range, width)) if *range == glyph.range => *width += advance,
+            _ => clusters.push((glyph.range.clone(), advance)),
+        }
     }
+
+    TextMetrics { width: shaped.width, ascent, descent, clusters }
 }
 
 /// Shape text with font fallback using the `families` iterator.
@@ -618,28 +705,25 @@ fn shape_segment(
 
     // Extract the font id or shape notdef glyphs if we couldn't find any font.
     let Some(font) = selection else {
+        warn_missing_glyphs(ctx, base, text);
         if let Some(font) = ctx.used.first().cloned() {
             shape_tofus(ctx, base, text, font);
         }
         return;
     };
 
-    ctx.used.push(font.clone());
+    // Fallback fonts are expected to not match exactly, so only check the
+    // primary selection.
+    if ctx.used.is_empty() {
+        warn_missing_variant(ctx, &font, base);
+    }
 
-    // Fill the buffer with our text.
-    let mut buffer = UnicodeBuffer::new();
-    buffer.push_str(text);
-    buffer.set_language(language(ctx.styles));
-    buffer.set_direction(match ctx.dir {
-        Dir::LTR => rustybuzz::Direction::LeftToRight,
-        Dir::RTL => rustybuzz::Direction::RightToLeft,
-        _ => unimplemented!("vertical text layout"),
-    });
+    ctx.used.push(font.clone());
 
     // Shape!
-    let buffer = rustybuzz::shape(font.rusty(), &ctx.tags, buffer);
-    let infos = buffer.glyph_infos();
-    let pos = buffer.glyph_positions();
+    let run = shape_run(ctx, &font, text);
+    let infos = &run.infos;
+    let pos = &run.positions;
     let ltr = ctx.dir.is_positive();
 
     // Collect the shaped glyphs, doing fallback and shaping parts again with
@@ -718,6 +802,119 @@ fn shape_segment(
     ctx.used.pop();
 }
 
+/// The glyphs the shaper produced for a run of text in a single font.
+struct ShapedRun {
+    infos: Vec<GlyphInfo>,
+    positions: Vec<GlyphPosition>,
+}
+
+/// Shape a run of text with a single font, reusing earlier results for the
+/// same text, font, language, direction and features.
+fn shape_run(ctx: &mut ShapingContext, font: &Font, text: &str) -> Arc<ShapedRun> {
+    let features: Vec<_> =
+        ctx.tags.iter().map(|f| (f.tag.0, f.value, f.start, f.end)).collect();
+    let lang = TextElem::lang_in(ctx.styles);
+    let region = TextElem::region_in(ctx.styles);
+    ctx.runs
+        .push(hash128(&(font, text, lang, region, ctx.dir, &features)));
+    shape_run_impl(font, text, lang, region, ctx.dir, &features)
+}
+
+/// Memoized implementation of `shape_run`.
+///
+/// The results are cached per font and evicted together with all other
+/// memoized results.
+#[comemo::memoize]
+#[tracing::instrument(skip_all)]
+fn shape_run_impl(
+    font: &Font,
+    text: &str,
+    lang: Lang,
+    region: Option<Region>,
+    dir: Dir,
+    features: &[(u32, u32, u32, u32)],
+) -> Arc<ShapedRun> {
+    // Fill the buffer with our text.
+    let mut buffer = UnicodeBuffer::new();
+    buffer.push_str(text);
+    buffer.set_language(language(lang, region));
+    buffer.set_direction(match dir {
+        Dir::LTR => rustybuzz::Direction::LeftToRight,
+        Dir::RTL => rustybuzz::Direction::RightToLeft,
+        _ => unimplemented!("vertical text layout"),
+    });
+
+    let tags: Vec<_> = features
+        .iter()
+        .map(|&(tag, value, start, end)| Feature { tag: Tag(tag), value, start, end })
+        .collect();
+
+    let buffer = rustybuzz::shape(font.rusty(), &tags, buffer);
+    Arc::new(ShapedRun {
+        infos: buffer.glyph_infos().to_vec(),
+        positions: buffer.glyph_positions().to_vec(),
+    })
+}
+
+/// Warn when the selected font lacks the requested bold or italic variant.
+///
+/// There is no synthesis of bold or italic faces, so text would silently be
+/// rendered in the regular face. This is most likely a styling mistake.
+fn warn_missing_variant(ctx: &mut ShapingContext, font: &Font, base: usize) {
+    let info = font.info();
+    let found = info.variant;
+    let requested = ctx.variant;
+
+    let missing = if requested.weight >= FontWeight::SEMIBOLD
+        && found.weight < FontWeight::SEMIBOLD
+    {
+        "bold"
+    } else if requested.style != FontStyle::Normal && found.style == FontStyle::Normal {
+        "italic"
+    } else {
+        return;
+    };
+
+    let span = ctx.spans.span_from(base);
+    if span.is_detached() {
+        return;
+    }
+
+    ctx.warnings.push(
+        SourceError::warning(
+            span,
+            eco_format!("font family \"{}\" has no {missing} variant", info.family),
+        )
+        .with_hints(["the closest available variant is used instead".into()]),
+    );
+}
+
+/// Warn about characters that none of the available fonts contain.
+fn warn_missing_glyphs(ctx: &mut ShapingContext, base: usize, text: &str) {
+    let mut missing: Vec<char> = vec![];
+    for c in text.chars().filter(|c| !c.is_whitespace() && !c.is_control()) {
+        if !missing.contains(&c) {
+            missing.push(c);
+        }
+    }
+
+    let span = ctx.spans.span_from(base);
+    if missing.is_empty() || span.is_detached() {
+        return;
+    }
+
+    let list = missing
+        .iter()
+        .map(|c| format!("{c} (U+{:04X})", *c as u32))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    ctx.warnings.push(
+        SourceError::warning(span, eco_format!("no font contains {list}"))
+            .with_hints(["the characters are shown as boxes instead".into()]),
+    );
+}
+
 /// Shape the text with tofus from the given font.
 fn shape_tofus(ctx: &mut ShapingContext, base: usize, text: &str, font: Font) {
     let x_advance = font.advance(0).unwrap_or_default();
@@ -770,6 +967,41 @@ fn track_and_space(ctx: &mut ShapingContext) {
     }
 }
 
+/// Give all digits the same width if tabular numbers are requested, but the
+/// font has no `tnum` feature.
+fn synthesize_tabular(ctx: &mut ShapingContext) {
+    if TextElem::number_width_in(ctx.styles) != Smart::Custom(NumberWidth::Tabular) {
+        return;
+    }
+
+    for glyph in &mut ctx.glyphs {
+        if !glyph.c.is_ascii_digit() || has_feature(&glyph.font, b"tnum") {
+            continue;
+        }
+
+        // Center the digit within the widest digit's advance.
+        if let Some(width) = digit_width(&glyph.font) {
+            glyph.x_offset += (width - glyph.x_advance) / 2.0;
+            glyph.x_advance = width;
+        }
+    }
+}
+
+/// Whether the font supports an OpenType substitution feature.
+fn has_feature(font: &Font, tag: &[u8; 4]) -> bool {
+    font.ttf()
+        .tables()
+        .gsub
+        .map_or(false, |gsub| gsub.features.find(Tag::from_bytes(tag)).is_some())
+}
+
+/// The advance of the widest ASCII digit in the font.
+fn digit_width(font: &Font) -> Option<Em> {
+    ('0'..='9')
+        .filter_map(|c| font.advance(font.ttf().glyph_index(c)?.0))
+        .max()
+}
+
 pub fn is_gb_style(lang: Lang, region: Option<Region>) -> bool {
     // Most CJK variants, including zh-CN, ja-JP, zh-SG, zh-MY use GB-style punctuation,
     // while zh-HK and zh-TW use alternative style. We default to use GB-style.
@@ -924,11 +1156,11 @@ fn tags(styles: StyleChain) -> Vec<Feature> {
     tags
 }
 
-/// Process the language and and region of a style chain into a
-/// rustybuzz-compatible BCP 47 language.
-fn language(styles: StyleChain) -> rustybuzz::Language {
-    let mut bcp: EcoString = TextElem::lang_in(styles).as_str().into();
-    if let Some(region) = TextElem::region_in(styles) {
+/// Process a language and region into a rustybuzz-compatible BCP 47
+/// language.
+fn language(lang: Lang, region: Option<Region>) -> rustybuzz::Language {
+    let mut bcp: EcoString = lang.as_str().into();
+    if let Some(region) = region {
         bcp.push('-');
         bcp.push_str(region.as_str());
     }
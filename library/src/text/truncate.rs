@@ -0,0 +1,84 @@
+use unicode_segmentation::UnicodeSegmentation;
+
+use super::TextElem;
+use crate::layout::BoxElem;
+use crate::prelude::*;
+
+/// Shortens content to a maximum width, ending it with an ellipsis.
+///
+/// The content is measured with its shaped width. If it is wider than
+/// `max-width`, it is replaced by as much of its plain text as fits together
+/// with the ellipsis. The result never wraps, which makes this useful for
+/// table cells and running headers.
+///
+/// Note that formatting within the content is lost when it is truncated.
+///
+/// ## Example { #example }
+/// ```example
+/// #truncate(3cm)[A rather long chapter title]
+///
+/// #truncate(3cm, ellipsis: " [...]")[
+///   A rather long chapter title
+/// ]
+/// ```
+///
+/// Display: Truncate
+/// Category: text
+#[element(Show)]
+pub struct TruncateElem {
+    /// The maximum width of the content.
+    #[required]
+    pub max_width: Length,
+
+    /// The text that marks where the content was cut off.
+    #[default("…".into())]
+    pub ellipsis: EcoString,
+
+    /// The content to truncate.
+    #[required]
+    pub body: Content,
+}
+
+impl Show for TruncateElem {
+    #[tracing::instrument(name = "TruncateElem::show", skip_all)]
+    fn show(&self, vt: &mut Vt, styles: StyleChain) -> SourceResult<Content> {
+        let max = self.max_width().resolve(styles);
+        let body = self.body();
+        let truncated = if width(vt, styles, &body)? <= max {
+            body
+        } else {
+            let ellipsis = self.ellipsis(styles);
+            let text = body.plain_text();
+            let bounds: Vec<usize> =
+                text.grapheme_indices(true).map(|(i, _)| i).collect();
+
+            // Find the longest prefix that still fits with the ellipsis.
+            let cut = |i: usize| {
+                let end = bounds.get(i).copied().unwrap_or(text.len());
+                let mut cut = EcoString::from(text[..end].trim_end());
+                cut.push_str(&ellipsis);
+                TextElem::packed(cut)
+            };
+
+            let (mut lo, mut hi) = (0, bounds.len());
+            while lo < hi {
+                let mid = (lo + hi + 1) / 2;
+                if width(vt, styles, &cut(mid))? <= max {
+                    lo = mid;
+                } else {
+                    hi = mid - 1;
+                }
+            }
+
+            cut(lo).spanned(self.span())
+        };
+
+        Ok(BoxElem::new().with_body(Some(truncated)).pack())
+    }
+}
+
+/// Measure the natural width of content.
+fn width(vt: &mut Vt, styles: StyleChain, content: &Content) -> SourceResult<Abs> {
+    let pod = Regions::one(Axes::splat(Abs::inf()), Axes::splat(false));
+    Ok(content.measure(vt, styles, pod)?.into_frame().width())
+}
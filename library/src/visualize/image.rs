@@ -167,6 +167,7 @@ impl LocalName for ImageElem {
             Lang::FRENCH => "Figure",
             Lang::GERMAN => "Abbildung",
             Lang::ITALIAN => "Figura",
+            Lang::JAPANESE => "図",
             Lang::NYNORSK => "Figur",
             Lang::POLISH => "Rysunek",
             Lang::PORTUGUESE => "Figura",
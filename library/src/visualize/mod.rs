@@ -3,12 +3,14 @@
 mod image;
 mod line;
 mod path;
+mod plot;
 mod polygon;
 mod shape;
 
 pub use self::image::*;
 pub use self::line::*;
 pub use self::path::*;
+pub use self::plot::*;
 pub use self::polygon::*;
 pub use self::shape::*;
 
@@ -24,6 +26,7 @@ pub(super) fn define(global: &mut Scope) {
     global.define("circle", CircleElem::func());
     global.define("polygon", PolygonElem::func());
     global.define("path", PathElem::func());
+    global.define("plot", PlotElem::func());
     global.define("black", Color::BLACK);
     global.define("gray", Color::GRAY);
     global.define("silver", Color::SILVER);
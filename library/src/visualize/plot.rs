@@ -0,0 +1,327 @@
+use crate::prelude::*;
+use crate::text::TextElem;
+
+/// A simple chart of numeric data.
+///
+/// Draws a line, bar or scatter chart of a list of data points together with
+/// axes, ticks and tick labels. The labels are set in the surrounding text
+/// style.
+///
+/// ## Example { #example }
+/// ```example
+/// #plot(
+///   height: 3cm,
+///   ((0, 1), (1, 3), (2, 2), (3, 5)),
+/// )
+///
+/// #plot(
+///   kind: "bar",
+///   height: 3cm,
+///   fill: eastern,
+///   ((1, 4), (2, 7), (3, 3)),
+/// )
+/// ```
+///
+/// Display: Plot
+/// Category: visualize
+#[element(Layout)]
+pub struct PlotElem {
+    /// How to draw the data.
+    #[default(PlotKind::Line)]
+    pub kind: PlotKind,
+
+    /// The width of the plot, including its axes and labels.
+    #[resolve]
+    #[default(Ratio::one().into())]
+    pub width: Rel<Length>,
+
+    /// The height of the plot, including its axes and labels.
+    #[resolve]
+    #[default(Abs::cm(4.0).into())]
+    pub height: Rel<Length>,
+
+    /// The color of the plotted data.
+    #[default(Color::BLUE.into())]
+    pub fill: Paint,
+
+    /// Roughly how many ticks to place on each axis. The exact number depends
+    /// on where round tick values fall.
+    #[default(NonZeroUsize::new(5).unwrap())]
+    pub ticks: NonZeroUsize,
+
+    /// The data points. Each point is an array of an x and a y value.
+    #[required]
+    pub data: Vec<DataPoint>,
+}
+
+impl Layout for PlotElem {
+    #[tracing::instrument(name = "PlotElem::layout", skip_all)]
+    fn layout(
+        &self,
+        vt: &mut Vt,
+        styles: StyleChain,
+        regions: Regions,
+    ) -> SourceResult<Fragment> {
+        let base = regions.base();
+        let size = Size::new(
+            self.width(styles).relative_to(base.x),
+            self.height(styles).relative_to(base.y),
+        );
+
+        if !size.is_finite() {
+            bail!(self.span(), "cannot create plot with infinite size");
+        }
+
+        let kind = self.kind(styles);
+        let fill = self.fill(styles);
+        let data = self.data();
+        let ticks = self.ticks(styles).get();
+
+        // Determine the data ranges. Bars always start at zero.
+        let xs = data.iter().map(|p| p.0.x.0);
+        let ys = data.iter().map(|p| p.0.y.0);
+        let mut x_range = range(xs).unwrap_or((0.0, 1.0));
+        let mut y_range = range(ys).unwrap_or((0.0, 1.0));
+        if kind == PlotKind::Bar {
+            y_range = (y_range.0.min(0.0), y_range.1.max(0.0));
+            let half = gap(&data) / 2.0;
+            x_range = (x_range.0 - half, x_range.1 + half);
+        }
+
+        let x_axis = Scale::new(x_range, ticks);
+        let y_axis = Scale::new(y_range, ticks);
+
+        // Lay out the tick labels.
+        let mut label = |value: f64, step: f64| -> SourceResult<Frame> {
+            let text = TextElem::packed(format_tick(value, step));
+            let pod = Regions::one(Size::splat(Abs::inf()), Axes::splat(false));
+            Ok(text.layout(vt, styles, pod)?.into_frame())
+        };
+
+        let x_labels = x_axis
+            .ticks()
+            .map(|v| Ok((v, label(v, x_axis.step)?)))
+            .collect::<SourceResult<Vec<_>>>()?;
+        let y_labels = y_axis
+            .ticks()
+            .map(|v| Ok((v, label(v, y_axis.step)?)))
+            .collect::<SourceResult<Vec<_>>>()?;
+
+        // Reserve space for the labels and ticks.
+        let tick = Abs::pt(3.0);
+        let pad = Abs::pt(2.0);
+        let label_width = y_labels.iter().map(|(_, f)| f.width()).max();
+        let label_height = x_labels.iter().map(|(_, f)| f.height()).max();
+        let left = label_width.unwrap_or_default() + tick + pad;
+        let bottom = label_height.unwrap_or_default() + tick + pad;
+        let top = label_height.unwrap_or_default() / 2.0;
+        let right = x_labels.last().map_or(Abs::zero(), |(_, f)| f.width() / 2.0);
+        let area = Size::new(size.x - left - right, size.y - top - bottom);
+
+        // Map data coordinates to positions in the frame.
+        let map = |x: f64, y: f64| {
+            Point::new(
+                left + area.x * x_axis.fraction(x),
+                top + area.y * (1.0 - y_axis.fraction(y)),
+            )
+        };
+
+        let mut frame = Frame::new(size);
+        let axis = Stroke { thickness: Abs::pt(0.5), ..Stroke::default() };
+        let line = |from: Point, to: Point| {
+            let shape = Geometry::Line(to - from).stroked(axis.clone());
+            (from, FrameItem::Shape(shape, self.span()))
+        };
+
+        // Draw the axes, ticks and labels.
+        let origin = Point::new(left, top + area.y);
+        let (pos, item) = line(origin, origin + Point::with_x(area.x));
+        frame.push(pos, item);
+        let (pos, item) = line(origin, origin - Point::with_y(area.y));
+        frame.push(pos, item);
+
+        for (value, label) in x_labels {
+            let x = map(value, y_axis.min).x;
+            let (pos, item) =
+                line(Point::new(x, origin.y), Point::new(x, origin.y + tick));
+            frame.push(pos, item);
+            let pos = Point::new(x - label.width() / 2.0, origin.y + tick + pad);
+            frame.push_frame(pos, label);
+        }
+
+        for (value, label) in y_labels {
+            let y = map(x_axis.min, value).y;
+            let (pos, item) =
+                line(Point::new(origin.x - tick, y), Point::new(origin.x, y));
+            frame.push(pos, item);
+            let pos = Point::new(
+                origin.x - tick - pad - label.width(),
+                y - label.height() / 2.0,
+            );
+            frame.push_frame(pos, label);
+        }
+
+        // Draw the data.
+        if data.is_empty() {
+            return Ok(Fragment::frame(frame));
+        }
+
+        match kind {
+            PlotKind::Line => {
+                let mut path = Path::new();
+                for (i, p) in data.iter().enumerate() {
+                    let point = map(p.0.x.0, p.0.y.0);
+                    if i == 0 {
+                        path.move_to(point);
+                    } else {
+                        path.line_to(point);
+                    }
+                }
+                let stroke = Stroke { paint: fill, ..Stroke::default() };
+                let shape = Geometry::Path(path).stroked(stroke);
+                frame.push(Point::zero(), FrameItem::Shape(shape, self.span()));
+            }
+            PlotKind::Bar => {
+                let width = area.x * x_axis.fraction_of(gap(&data) * 0.8);
+                let zero = map(0.0, 0.0).y;
+                for p in &data {
+                    let point = map(p.0.x.0, p.0.y.0);
+                    let pos = Point::new(point.x - width / 2.0, point.y.min(zero));
+                    let size = Size::new(width, (zero - point.y).abs());
+                    let shape = Geometry::Rect(size).filled(fill.clone());
+                    frame.push(pos, FrameItem::Shape(shape, self.span()));
+                }
+            }
+            PlotKind::Scatter => {
+                let radius = Abs::pt(2.0);
+                for p in &data {
+                    let point = map(p.0.x.0, p.0.y.0);
+                    let shape =
+                        ellipse(Size::splat(2.0 * radius), Some(fill.clone()), None);
+                    frame.push(
+                        point - Point::splat(radius),
+                        FrameItem::Shape(shape, self.span()),
+                    );
+                }
+            }
+        }
+
+        Ok(Fragment::frame(frame))
+    }
+}
+
+/// How the data of a plot is drawn.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Cast)]
+pub enum PlotKind {
+    /// The points are connected by a line.
+    Line,
+    /// Each point is drawn as a bar that starts at zero.
+    Bar,
+    /// Each point is drawn as a dot.
+    Scatter,
+}
+
+/// A data point in a plot.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct DataPoint(pub Axes<Scalar>);
+
+cast! {
+    DataPoint,
+    self => array![self.0.x.0, self.0.y.0].into_value(),
+    array: Array => {
+        let mut iter = array.into_iter();
+        match (iter.next(), iter.next(), iter.next()) {
+            (Some(a), Some(b), None) => {
+                let (x, y) = (a.cast::<f64>()?, b.cast::<f64>()?);
+                if !x.is_finite() || !y.is_finite() {
+                    bail!("data point must be finite");
+                }
+                Self(Axes::new(Scalar(x), Scalar(y)))
+            }
+            _ => bail!("data point must contain exactly two entries"),
+        }
+    },
+}
+
+/// The axis of a plot, covering a range of data values with evenly spaced
+/// ticks at round values.
+struct Scale {
+    min: f64,
+    max: f64,
+    step: f64,
+}
+
+impl Scale {
+    /// Create a scale that covers the range with roughly `count` ticks.
+    fn new((mut min, mut max): (f64, f64), count: usize) -> Self {
+        if min == max {
+            min -= 1.0;
+            max += 1.0;
+        }
+
+        let raw = (max - min) / count as f64;
+        let magnitude = 10f64.powf(raw.log10().floor());
+        let step = magnitude
+            * match raw / magnitude {
+                v if v <= 1.0 => 1.0,
+                v if v <= 2.0 => 2.0,
+                v if v <= 5.0 => 5.0,
+                _ => 10.0,
+            };
+
+        Self {
+            min: (min / step).floor() * step,
+            max: (max / step).ceil() * step,
+            step,
+        }
+    }
+
+    /// The tick values.
+    fn ticks(&self) -> impl Iterator<Item = f64> + '_ {
+        let count = ((self.max - self.min) / self.step).round() as usize;
+        (0..=count).map(|i| self.min + i as f64 * self.step)
+    }
+
+    /// Where a value lies on the scale, from zero to one.
+    fn fraction(&self, value: f64) -> f64 {
+        (value - self.min) / (self.max - self.min)
+    }
+
+    /// Which fraction of the scale a distance covers.
+    fn fraction_of(&self, distance: f64) -> f64 {
+        distance / (self.max - self.min)
+    }
+}
+
+/// The minimum and maximum of some values.
+fn range(values: impl Iterator<Item = f64>) -> Option<(f64, f64)> {
+    values.fold(None, |acc, v| match acc {
+        Some((min, max)) => Some((v.min(min), v.max(max))),
+        None => Some((v, v)),
+    })
+}
+
+/// The smallest distance between the x values of two data points, or one if
+/// there are fewer than two distinct values.
+fn gap(data: &[DataPoint]) -> f64 {
+    let mut xs: Vec<f64> = data.iter().map(|p| p.0.x.0).collect();
+    xs.sort_by(|a, b| a.total_cmp(b));
+    xs.windows(2)
+        .map(|w| w[1] - w[0])
+        .filter(|&d| d > 0.0)
+        .fold(None, |acc: Option<f64>, d| Some(acc.map_or(d, |a| a.min(d))))
+        .unwrap_or(1.0)
+}
+
+/// Format a tick value with as many decimals as the step requires.
+fn format_tick(value: f64, step: f64) -> EcoString {
+    let decimals = (-step.log10().floor()).max(0.0) as usize;
+    let text = format!("{:.*}", decimals, value);
+    match text.strip_prefix('-') {
+        Some(rest) if rest.chars().any(|c| c != '0' && c != '.') => {
+            eco_format!("−{rest}")
+        }
+        Some(rest) => rest.into(),
+        None => text.into(),
+    }
+}
@@ -68,12 +68,14 @@ pub use ecow::{eco_format, EcoString};
 /// A result that can carry multiple source errors.
 pub type SourceResult<T> = Result<T, Box<Vec<SourceError>>>;
 
-/// An error in a source file.
+/// An error or warning in a source file.
 ///
 /// The contained spans will only be detached if any of the input source files
 /// were detached.
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
 pub struct SourceError {
+    /// Whether the diagnostic is an error or a warning.
+    pub severity: Severity,
     /// The span of the erroneous node in the source code.
     pub span: Span,
     /// A diagnostic message describing the problem.
@@ -89,6 +91,7 @@ impl SourceError {
     /// Create a new, bare error.
     pub fn new(span: Span, message: impl Into<EcoString>) -> Self {
         Self {
+            severity: Severity::Error,
             span,
             trace: vec![],
             message: message.into(),
@@ -96,6 +99,14 @@ impl SourceError {
         }
     }
 
+    /// Create a new, bare warning.
+    pub fn warning(span: Span, message: impl Into<EcoString>) -> Self {
+        Self {
+            severity: Severity::Warning,
+            ..Self::new(span, message)
+        }
+    }
+
     /// Adds user-facing hints to the error.
     pub fn with_hints(mut self, hints: impl IntoIterator<Item = EcoString>) -> Self {
         self.hints.extend(hints);
@@ -103,6 +114,16 @@ impl SourceError {
     }
 }
 
+/// The severity of a [`SourceError`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Severity {
+    /// A fatal error that stops compilation.
+    Error,
+    /// A problem that doesn't prevent compilation, but likely leads to
+    /// unexpected output.
+    Warning,
+}
+
 /// A part of an error's [trace](SourceError::trace).
 #[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
 pub enum Tracepoint {
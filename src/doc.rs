@@ -1,5 +1,6 @@
 //! Finished documents.
 
+use std::collections::{BTreeSet, HashSet};
 use std::fmt::{self, Debug, Formatter};
 use std::num::NonZeroUsize;
 use std::ops::Range;
@@ -7,8 +8,9 @@ use std::str::FromStr;
 use std::sync::Arc;
 
 use ecow::EcoString;
+use unicode_segmentation::UnicodeSegmentation;
 
-use crate::eval::{cast, dict, Dict, Value};
+use crate::eval::{cast, dict, Datetime, Dict, Value};
 use crate::font::Font;
 use crate::geom::{
     self, rounded_rect, Abs, Align, Axes, Color, Corners, Dir, Em, Geometry, Length,
@@ -27,6 +29,78 @@ pub struct Document {
     pub title: Option<EcoString>,
     /// The document's author.
     pub author: Vec<EcoString>,
+    /// The document's keywords.
+    pub keywords: Vec<EcoString>,
+    /// The document's creation date.
+    pub date: Option<Datetime>,
+}
+
+impl Document {
+    /// Gather statistics about the document's contents.
+    pub fn stats(&self) -> DocumentStats {
+        let mut collector = StatsCollector::default();
+        for page in &self.pages {
+            collector.visit(page, Point::zero());
+            collector.text.push('\n');
+        }
+
+        DocumentStats {
+            pages: self.pages.len(),
+            words: collector.text.unicode_words().count(),
+            chars: collector.text.chars().filter(|c| !c.is_whitespace()).count(),
+            fonts: collector.fonts.into_iter().collect(),
+            images: collector.images.len(),
+        }
+    }
+}
+
+/// Statistics about a finished document.
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
+pub struct DocumentStats {
+    /// The number of pages.
+    pub pages: usize,
+    /// The number of words in the visible text.
+    pub words: usize,
+    /// The number of non-whitespace characters in the visible text.
+    pub chars: usize,
+    /// The families of all fonts that are used for text, sorted by name.
+    pub fonts: Vec<EcoString>,
+    /// The number of distinct images.
+    pub images: usize,
+}
+
+/// Walks frames to gather document statistics.
+#[derive(Default)]
+struct StatsCollector {
+    text: String,
+    baseline: Option<Abs>,
+    fonts: BTreeSet<EcoString>,
+    images: HashSet<Image>,
+}
+
+impl StatsCollector {
+    fn visit(&mut self, frame: &Frame, offset: Point) {
+        for (pos, item) in frame.items() {
+            let pos = offset + *pos;
+            match item {
+                FrameItem::Group(group) => self.visit(&group.frame, pos),
+                FrameItem::Text(text) => {
+                    // Separate text runs on different lines so that words
+                    // at line ends are not merged.
+                    if self.baseline.map_or(false, |y| !y.approx_eq(pos.y)) {
+                        self.text.push('\n');
+                    }
+                    self.baseline = Some(pos.y);
+                    self.text.push_str(&text.text);
+                    self.fonts.insert(text.font.info().family.as_str().into());
+                }
+                FrameItem::Image(image, _, _) => {
+                    self.images.insert(image.clone());
+                }
+                FrameItem::Shape(..) | FrameItem::Meta(..) => {}
+            }
+        }
+    }
 }
 
 /// A finished layout with items at fixed positions.
@@ -313,6 +387,37 @@ impl Frame {
         }
     }
 
+    /// Remove the metadata of locatable elements from the frame and its groups,
+    /// so that introspection doesn't find these elements in it.
+    pub fn strip_elems(&mut self) {
+        let items = Arc::make_mut(&mut self.items);
+        items.retain(|(_, item)| !matches!(item, FrameItem::Meta(Meta::Elem(_), _)));
+        for (_, item) in items {
+            if let FrameItem::Group(group) = item {
+                group.frame.strip_elems();
+            }
+        }
+    }
+
+    /// Rebase the locations of elements and links in the frame and its groups
+    /// so that they don't collide with the locations of another document.
+    pub fn rebase_locations(&mut self, document: usize) {
+        for (_, item) in Arc::make_mut(&mut self.items) {
+            match item {
+                FrameItem::Group(group) => group.frame.rebase_locations(document),
+                FrameItem::Meta(Meta::Elem(content), _) => {
+                    if let Some(location) = content.location() {
+                        content.set_location(location.rebase(document));
+                    }
+                }
+                FrameItem::Meta(Meta::Link(Destination::Location(location)), _) => {
+                    *location = location.rebase(document);
+                }
+                _ => {}
+            }
+        }
+    }
+
     /// Clip the contents of a frame to its size.
     pub fn clip(&mut self) {
         if !self.is_empty() {
@@ -362,6 +467,51 @@ impl Frame {
         self
     }
 
+    /// Outline the bounds of this frame, all nested frames and all text runs
+    /// for debugging.
+    pub fn outline(&mut self) {
+        let stroke = |color: Color| Stroke {
+            paint: color.into(),
+            thickness: Abs::pt(0.25),
+            ..Stroke::default()
+        };
+
+        let mut runs = vec![];
+        for (pos, item) in Arc::make_mut(&mut self.items) {
+            match item {
+                FrameItem::Group(group) => group.frame.outline(),
+                FrameItem::Text(text) => {
+                    let metrics = text.font.metrics();
+                    let top = metrics.ascender.at(text.size);
+                    let bottom = -metrics.descender.at(text.size);
+                    runs.push((
+                        *pos - Point::with_y(top),
+                        Size::new(text.width(), top + bottom),
+                    ));
+                }
+                _ => {}
+            }
+        }
+
+        for (pos, size) in runs {
+            self.push(
+                pos,
+                FrameItem::Shape(
+                    Geometry::Rect(size).stroked(stroke(Color::RED)),
+                    Span::detached(),
+                ),
+            );
+        }
+
+        self.push(
+            Point::zero(),
+            FrameItem::Shape(
+                Geometry::Rect(self.size).stroked(stroke(Color::BLUE)),
+                Span::detached(),
+            ),
+        );
+    }
+
     /// Add a green marker at a position for debugging.
     pub fn mark_point(&mut self, pos: Point) {
         let radius = Abs::pt(2.0);
@@ -624,6 +774,18 @@ pub enum Meta {
     Elem(Content),
     /// The numbering of the current page.
     PageNumbering(Value),
+    /// The label of the current page, that is, its logical page number
+    /// formatted with the page's numbering.
+    PageLabel(PageLabel),
+    /// The area of the finished page after trimming. The contained length is
+    /// the bleed that extends beyond it on each side.
+    PageTrim(Abs),
+    /// The semantic role of the content in the area this metadata is attached
+    /// to. Exporters can use it to produce structured output.
+    Role(Role),
+    /// An interactive form field that covers the area this metadata is
+    /// attached to.
+    Field(FormField),
     /// Indicates that content should be hidden. This variant doesn't appear
     /// in the final frames as it is removed alongside the content that should
     /// be hidden.
@@ -640,11 +802,84 @@ impl Debug for Meta {
             Self::Link(dest) => write!(f, "Link({dest:?})"),
             Self::Elem(content) => write!(f, "Elem({:?})", content.func()),
             Self::PageNumbering(value) => write!(f, "PageNumbering({value:?})"),
+            Self::PageLabel(label) => write!(f, "PageLabel({label:?})"),
+            Self::PageTrim(bleed) => write!(f, "PageTrim({bleed:?})"),
+            Self::Role(role) => write!(f, "Role({role:?})"),
+            Self::Field(field) => write!(f, "Field({:?})", field.name),
             Self::Hide => f.pad("Hide"),
         }
     }
 }
 
+/// The semantic role of a piece of block-level content.
+///
+/// The role is attached once to the frame of the content it describes, so the
+/// area of a role's metadata is the area of that content.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Role {
+    /// A heading of the given level.
+    Heading(NonZeroUsize),
+    /// An item of a bullet list.
+    ListItem,
+    /// An item of a numbered list.
+    EnumItem,
+    /// A table cell in the given column and row.
+    TableCell { x: usize, y: usize },
+}
+
+cast! {
+    type Role: "role",
+}
+
+/// The logical number of a page as shown in a viewer's page indicator.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct PageLabel {
+    /// Text that precedes the number. If there is no style, this is the whole
+    /// label.
+    pub prefix: EcoString,
+    /// How the number is displayed, if at all.
+    pub style: Option<PageLabelStyle>,
+    /// The logical page number.
+    pub number: usize,
+}
+
+/// How the number in a page label is displayed.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum PageLabelStyle {
+    /// Decimal arabic numerals.
+    Arabic,
+    /// Lowercase roman numerals.
+    LowerRoman,
+    /// Uppercase roman numerals.
+    UpperRoman,
+    /// Lowercase letters.
+    LowerAlpha,
+    /// Uppercase letters.
+    UpperAlpha,
+}
+
+/// An interactive form field.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct FormField {
+    /// The name that identifies the field in the form's data.
+    pub name: EcoString,
+    /// What kind of input the field takes.
+    pub kind: FormFieldKind,
+}
+
+/// The kind of input a form field takes.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub enum FormFieldKind {
+    /// A text input with an initial value.
+    Text { value: EcoString, multiline: bool },
+    /// A checkbox.
+    Checkbox { checked: bool },
+    /// A dropdown to choose one of several options.
+    Dropdown { options: Vec<EcoString>, selected: Option<usize> },
+    /// A placeholder for a digital signature.
+    Signature,
+}
+
 /// A link destination.
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
 pub enum Destination {
@@ -711,6 +946,26 @@ mod tests {
         assert!(!option_eq(region, "AB"));
     }
 
+    #[test]
+    fn test_rebase_locations() {
+        let location = crate::model::Locator::new().locate(1);
+        let link = Meta::Link(Destination::Location(location));
+        let mut frame = Frame::new(Size::zero());
+        frame.push(Point::zero(), FrameItem::Meta(link, Size::zero()));
+
+        let dest = |frame: &Frame| match frame.items().next() {
+            Some((_, FrameItem::Meta(Meta::Link(Destination::Location(loc)), _))) => *loc,
+            _ => panic!("link is missing"),
+        };
+
+        let mut rebased = frame.clone();
+        rebased.rebase_locations(1);
+        assert_eq!(dest(&frame), location);
+        assert_eq!(dest(&rebased), location.rebase(1));
+        assert_ne!(location.rebase(1), location);
+        assert_ne!(location.rebase(1), location.rebase(2));
+    }
+
     #[test]
     fn test_document_is_send() {
         fn ensure_send<T: Send>() {}
@@ -1,3 +1,4 @@
+use std::cmp::Ordering;
 use std::fmt;
 use std::fmt::{Debug, Formatter};
 use std::hash::Hash;
@@ -136,6 +137,17 @@ impl Datetime {
     }
 }
 
+impl PartialOrd for Datetime {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        match (self, other) {
+            (Self::Date(a), Self::Date(b)) => a.partial_cmp(b),
+            (Self::Time(a), Self::Time(b)) => a.partial_cmp(b),
+            (Self::Datetime(a), Self::Datetime(b)) => a.partial_cmp(b),
+            _ => None,
+        }
+    }
+}
+
 impl Debug for Datetime {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
         let year = self.year().map(|y| eco_format!("year: {y}"));
@@ -358,6 +358,7 @@ impl Closure {
         // Prepare VM.
         let mut vm = Vm::new(vt, route, closure.location, scopes);
         vm.depth = depth;
+        vm.args = Some(args.clone());
 
         // Provide the closure itself for recursive calls.
         if let Some(name) = &closure.name {
@@ -71,6 +71,8 @@ pub struct LangItems {
     pub heading: fn(level: NonZeroUsize, body: Content) -> Content,
     /// The heading function.
     pub heading_func: ElemFunc,
+    /// The function for files embedded into the exported document.
+    pub embed_func: ElemFunc,
     /// An item in a bullet list: `- ...`.
     pub list_item: fn(body: Content) -> Content,
     /// An item in an enumeration (numbered list): `+ ...` or `1. ...`.
@@ -139,6 +141,7 @@ impl Hash for LangItems {
         (self.bibliography_keys as usize).hash(state);
         self.heading.hash(state);
         self.heading_func.hash(state);
+        self.embed_func.hash(state);
         self.list_item.hash(state);
         self.enum_item.hash(state);
         self.term_item.hash(state);
@@ -1,10 +1,11 @@
 //! Methods on values.
 
-use ecow::EcoString;
+use ecow::{eco_format, EcoString};
 
 use super::{Args, IntoValue, Str, Value, Vm};
 use crate::diag::{At, SourceResult};
 use crate::eval::Datetime;
+use crate::geom::{AbsUnit, Length, Numeric};
 use crate::model::{Location, Selector};
 use crate::syntax::Span;
 
@@ -27,6 +28,20 @@ pub fn call(
             _ => return missing(),
         },
 
+        Value::Length(length) => match method {
+            "pt" => to_unit(length, AbsUnit::Pt).at(span)?.into_value(),
+            "mm" => to_unit(length, AbsUnit::Mm).at(span)?.into_value(),
+            "cm" => to_unit(length, AbsUnit::Cm).at(span)?.into_value(),
+            "inches" => to_unit(length, AbsUnit::In).at(span)?.into_value(),
+            _ => return missing(),
+        },
+
+        Value::Angle(angle) => match method {
+            "deg" => angle.to_deg().into_value(),
+            "rad" => angle.to_rad().into_value(),
+            _ => return missing(),
+        },
+
         Value::Str(string) => match method {
             "len" => string.len().into_value(),
             "first" => string.first().at(span)?.into_value(),
@@ -285,6 +300,22 @@ pub fn is_accessor(method: &str) -> bool {
     matches!(method, "first" | "last" | "at")
 }
 
+/// Convert a length into a number in the given absolute unit.
+fn to_unit(length: Length, unit: AbsUnit) -> Result<f64, EcoString> {
+    if !length.em.is_zero() {
+        return Err(eco_format!(
+            "cannot convert a length with non-zero em units ({length:?}) to {}",
+            match unit {
+                AbsUnit::Pt => "pt",
+                AbsUnit::Mm => "mm",
+                AbsUnit::Cm => "cm",
+                AbsUnit::In => "inches",
+            }
+        ));
+    }
+    Ok(length.abs.to_unit(unit))
+}
+
 /// The missing method error message.
 #[cold]
 fn missing_method(type_name: &str, method: &str) -> String {
@@ -295,6 +326,8 @@ fn missing_method(type_name: &str, method: &str) -> String {
 pub fn methods_on(type_name: &str) -> &[(&'static str, bool)] {
     match type_name {
         "color" => &[("lighten", true), ("darken", true), ("negate", false)],
+        "length" => &[("pt", false), ("mm", false), ("cm", false), ("inches", false)],
+        "angle" => &[("deg", false), ("rad", false)],
         "string" => &[
             ("len", false),
             ("at", true),
@@ -72,6 +72,7 @@ use crate::model::{
 };
 use crate::syntax::ast::{self, AstNode};
 use crate::syntax::{parse_code, Source, Span, Spanned, SyntaxKind, SyntaxNode};
+use crate::util::hash128;
 use crate::World;
 
 const MAX_ITERATIONS: usize = 10_000;
@@ -199,6 +200,10 @@ pub struct Vm<'a> {
     depth: usize,
     /// A span that is currently traced.
     traced: Option<Span>,
+    /// The arguments of the closure call the VM evaluates, if any.
+    args: Option<Args>,
+    /// The number of keys for pseudo-random values drawn so far.
+    draws: usize,
 }
 
 impl<'a> Vm<'a> {
@@ -220,6 +225,8 @@ impl<'a> Vm<'a> {
             scopes,
             depth: 0,
             traced,
+            args: None,
+            draws: 0,
         }
     }
 
@@ -233,6 +240,18 @@ impl<'a> Vm<'a> {
         self.location
     }
 
+    /// Draw a new key for a pseudo-random value.
+    ///
+    /// Successive draws yield different keys. The keys depend on the file, the
+    /// call depth and the arguments of the closure that is evaluated, but not
+    /// on spans, so that they stay the same when unrelated parts of the source
+    /// are edited.
+    pub fn draw(&mut self) -> u128 {
+        self.draws += 1;
+        let args = self.args.as_ref().map(|args| eco_format!("{args:?}"));
+        hash128(&(self.location, self.depth, args, self.draws))
+    }
+
     /// Define a variable in the current scope.
     #[tracing::instrument(skip_all)]
     pub fn define(&mut self, var: ast::Ident, value: impl IntoValue) {
@@ -317,11 +336,16 @@ impl<'a> Route<'a> {
     }
 }
 
-/// Traces which values existed for an expression at a span.
+/// Traces which values existed for an expression at a span and collects the
+/// warnings raised during compilation.
 #[derive(Default, Clone)]
 pub struct Tracer {
     span: Option<Span>,
     values: Vec<Value>,
+    warnings: Vec<SourceError>,
+    warned: HashSet<u128>,
+    shaped: usize,
+    runs: HashSet<u128>,
 }
 
 impl Tracer {
@@ -330,13 +354,23 @@ impl Tracer {
 
     /// Create a new tracer, possibly with a span under inspection.
     pub fn new(span: Option<Span>) -> Self {
-        Self { span, values: vec![] }
+        Self { span, ..Self::default() }
     }
 
     /// Get the traced values.
     pub fn finish(self) -> Vec<Value> {
         self.values
     }
+
+    /// The warnings raised so far.
+    pub fn warnings(&self) -> &[SourceError] {
+        &self.warnings
+    }
+
+    /// Statistics about the text shaped so far.
+    pub fn shaping_stats(&self) -> ShapingStats {
+        ShapingStats { runs: self.shaped, distinct: self.runs.len() }
+    }
 }
 
 #[comemo::track]
@@ -356,6 +390,36 @@ impl Tracer {
             self.values.push(v);
         }
     }
+
+    /// Raise a warning.
+    ///
+    /// A warning with the same span and message as an earlier one is only
+    /// reported once, even if its hints differ.
+    pub fn warn(&mut self, warning: SourceError) {
+        let hash = hash128(&(warning.span, &warning.message));
+        if self.warned.insert(hash) {
+            self.warnings.push(warning);
+        }
+    }
+
+    /// Record that a run of text was shaped with a single font.
+    ///
+    /// Runs with the same key have the same shaping result.
+    pub fn shaped(&mut self, key: u128) {
+        self.shaped += 1;
+        self.runs.insert(key);
+    }
+}
+
+/// Statistics about the text shaped during a compilation.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq)]
+pub struct ShapingStats {
+    /// The number of runs of text that were shaped with a single font,
+    /// including repeated ones, for example across layout iterations.
+    pub runs: usize,
+    /// The number of distinct runs. As shaping results are memoized, only
+    /// these had to be shaped within the compilation.
+    pub distinct: usize,
 }
 
 /// Evaluate an expression.
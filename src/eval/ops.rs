@@ -5,7 +5,7 @@ use std::fmt::Debug;
 
 use ecow::eco_format;
 
-use super::{format_str, Regex, Value};
+use super::{format_str, Datetime, Regex, Value};
 use crate::diag::{bail, StrResult};
 use crate::geom::{Axes, Axis, GenAlign, Length, Numeric, PartialStroke, Rel, Smart};
 use Value::*;
@@ -389,6 +389,12 @@ pub fn compare(lhs: &Value, rhs: &Value) -> StrResult<Ordering> {
         (Relative(a), Length(b)) if a.rel.is_zero() => try_cmp_values(&a.abs, b)?,
         (Relative(a), Ratio(b)) if a.abs.is_zero() => a.rel.cmp(b),
 
+        // Datetimes of the same kind are comparable.
+        (Dyn(a), Dyn(b)) => match (a.downcast::<Datetime>(), b.downcast::<Datetime>()) {
+            (Some(a), Some(b)) => try_cmp_values(a, b)?,
+            _ => mismatch!("cannot compare {} and {}", lhs, rhs),
+        },
+
         _ => mismatch!("cannot compare {} and {}", lhs, rhs),
     })
 }
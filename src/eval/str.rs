@@ -535,11 +535,20 @@ pub struct Regex(regex::Regex);
 
 impl Regex {
     /// Create a new regular expression.
+    ///
+    /// Compiled expressions are cached, so creating the same expression
+    /// repeatedly, for instance in a loop, is cheap.
     pub fn new(re: &str) -> StrResult<Self> {
-        regex::Regex::new(re).map(Self).map_err(|err| eco_format!("{err}"))
+        compile_regex(re)
     }
 }
 
+/// Compile a regular expression.
+#[comemo::memoize]
+fn compile_regex(re: &str) -> StrResult<Regex> {
+    regex::Regex::new(re).map(Regex).map_err(|err| eco_format!("{err}"))
+}
+
 impl Deref for Regex {
     type Target = regex::Regex;
 
@@ -0,0 +1,143 @@
+//! Arranging logical pages on physical sheets.
+
+use crate::doc::{Document, Frame, FrameItem, GroupItem};
+use crate::geom::{Angle, Point, Size, Transform};
+
+/// How logical pages are arranged on physical sheets.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Imposition {
+    /// Two consecutive pages side by side on each sheet.
+    TwoUp,
+    /// Pages ordered for a saddle-stitched booklet: Printing the sheets
+    /// double-sided, stacking, folding them in the middle and stapling along
+    /// the fold yields the pages in reading order.
+    Booklet,
+}
+
+/// Arrange the pages of a document on physical sheets.
+///
+/// Every sheet holds two pages side by side and is twice as wide as the
+/// largest page. Smaller pages are centered in their half. If a booklet's page
+/// count is not divisible by four, it is padded with blank pages at the end.
+///
+/// If the largest page is in landscape orientation, all pages are rotated by
+/// a quarter turn before they are placed. Like this, two A5 pages end up side
+/// by side on an A4 sheet in landscape orientation regardless of the pages'
+/// orientation.
+///
+/// Links to locations within the document are not adjusted and may point to
+/// the wrong place in the imposed document.
+pub fn impose(document: &Document, imposition: Imposition) -> Document {
+    let pages = &document.pages;
+    let cell = pages.iter().fold(Size::zero(), |max, page| max.max(page.size()));
+    let blank = Frame::new(cell);
+    let rotate = cell.x > cell.y;
+    let cell = if rotate { Size::new(cell.y, cell.x) } else { cell };
+    let page = |i: usize| pages.get(i).unwrap_or(&blank);
+
+    let pairs: Vec<(usize, usize)> = match imposition {
+        Imposition::TwoUp => (0..pages.len()).step_by(2).map(|i| (i, i + 1)).collect(),
+        Imposition::Booklet => {
+            let n = (pages.len() + 3) / 4 * 4;
+            (0..n / 4)
+                .flat_map(|i| [(n - 1 - 2 * i, 2 * i), (2 * i + 1, n - 2 - 2 * i)])
+                .collect()
+        }
+    };
+
+    let sheets = pairs
+        .into_iter()
+        .map(|(left, right)| {
+            let mut sheet = Frame::new(Size::new(2.0 * cell.x, cell.y));
+            for (k, frame) in [page(left), page(right)].into_iter().enumerate() {
+                let mut group = GroupItem::new(frame.clone());
+                let mut size = frame.size();
+                if rotate {
+                    group.transform = Transform::rotate(Angle::deg(90.0));
+                    size = Size::new(size.y, size.x);
+                }
+
+                // A quarter turn moves the page to the left of its origin, so
+                // it is shifted back by its rotated width.
+                let offset = (cell - size) / 2.0;
+                let mut pos = Point::new(k as f64 * cell.x + offset.x, offset.y);
+                if rotate {
+                    pos.x += size.x;
+                }
+
+                sheet.push(pos, FrameItem::Group(group));
+            }
+            sheet
+        })
+        .collect();
+
+    Document { pages: sheets, ..document.clone() }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geom::Abs;
+
+    fn document(n: usize) -> Document {
+        let pages = (1..=n)
+            .map(|i| {
+                let mut frame = Frame::new(Size::new(Abs::pt(i as f64), Abs::pt(10.0)));
+                let inner = GroupItem::new(Frame::new(Size::zero()));
+                frame.push(Point::zero(), FrameItem::Group(inner));
+                frame
+            })
+            .collect();
+        Document { pages, ..Document::default() }
+    }
+
+    /// The widths of the pages on each sheet, zero for blank pages.
+    fn widths(document: &Document) -> Vec<(f64, f64)> {
+        document
+            .pages
+            .iter()
+            .map(|sheet| {
+                let width = |(_, item): &(Point, FrameItem)| match item {
+                    FrameItem::Group(group) if !group.frame.is_empty() => {
+                        group.frame.width().to_pt()
+                    }
+                    _ => 0.0,
+                };
+                let mut items = sheet.items();
+                (width(items.next().unwrap()), width(items.next().unwrap()))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_impose_two_up() {
+        let imposed = impose(&document(3), Imposition::TwoUp);
+        assert_eq!(widths(&imposed), [(1.0, 2.0), (3.0, 0.0)]);
+        assert_eq!(imposed.pages[0].width(), Abs::pt(6.0));
+    }
+
+    #[test]
+    fn test_impose_landscape() {
+        let page = Frame::new(Size::new(Abs::pt(21.0), Abs::pt(14.8)));
+        let document = Document {
+            pages: vec![page.clone(), page],
+            ..Document::default()
+        };
+        let imposed = impose(&document, Imposition::TwoUp);
+        let sheet = &imposed.pages[0];
+        assert_eq!(sheet.size(), Size::new(Abs::pt(29.6), Abs::pt(21.0)));
+
+        let positions: Vec<_> = sheet.items().map(|(pos, _)| pos.x.to_pt()).collect();
+        assert_eq!(positions, [14.8, 29.6]);
+        assert!(sheet.items().all(|(_, item)| matches!(
+            item,
+            FrameItem::Group(group) if group.transform == Transform::rotate(Angle::deg(90.0))
+        )));
+    }
+
+    #[test]
+    fn test_impose_booklet() {
+        let imposed = impose(&document(6), Imposition::Booklet);
+        assert_eq!(widths(&imposed), [(0.0, 1.0), (2.0, 0.0), (6.0, 3.0), (4.0, 5.0)]);
+    }
+}
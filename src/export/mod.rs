@@ -1,7 +1,14 @@
 //! Exporting into external formats.
 
+mod impose;
 mod pdf;
 mod render;
+mod scene;
+mod text;
 
-pub use self::pdf::pdf;
+pub use self::impose::{impose, Imposition};
+pub(crate) use self::pdf::subset_font;
+pub use self::pdf::{pdf, pdf_with, PdfOptions};
 pub use self::render::render;
+pub use self::scene::{scene, Scene, SceneGlyph, SceneItem, ScenePage};
+pub use self::text::{text_runs, TextCluster, TextRun};
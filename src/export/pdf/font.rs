@@ -1,12 +1,13 @@
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashMap};
 
 use ecow::{eco_format, EcoString};
 use pdf_writer::types::{CidFontType, FontFlags, SystemInfo, UnicodeCmap};
 use pdf_writer::{Filter, Finish, Name, Rect, Str};
-use ttf_parser::{name_id, GlyphId, Tag};
+use ttf_parser::{name_id, GlyphId, Permissions, Tag};
 use unicode_general_category::GeneralCategory;
 
-use super::{deflate, EmExt, PdfContext, RefExt};
+use super::{deflate, sfnt, EmExt, PdfContext, PdfOptions, RefExt};
+use crate::diag::{bail, StrResult};
 use crate::font::Font;
 use crate::util::{Bytes, SliceExt};
 
@@ -19,8 +20,11 @@ const SYSTEM_INFO: SystemInfo = SystemInfo {
 
 /// Embed all used fonts into the PDF.
 #[tracing::instrument(skip_all)]
-pub fn write_fonts(ctx: &mut PdfContext) {
-    for font in ctx.font_map.items() {
+pub fn write_fonts(ctx: &mut PdfContext) -> StrResult<()> {
+    let fonts: Vec<&Font> = ctx.font_map.items().collect();
+    let subsets = subset_fonts(&fonts, &ctx.glyph_sets, ctx.options)?;
+
+    for (font, data) in ctx.font_map.items().zip(subsets) {
         let type0_ref = ctx.alloc.bump();
         let cid_ref = ctx.alloc.bump();
         let descriptor_ref = ctx.alloc.bump();
@@ -136,11 +140,10 @@ pub fn write_fonts(ctx: &mut PdfContext) {
         // Write the /ToUnicode character map, which maps glyph ids back to
         // unicode codepoints to enable copying out of the PDF.
         let cmap = create_cmap(ttf, glyph_set);
-        ctx.writer.cmap(cmap_ref, &cmap.finish());
+        let cmap_data = deflate(&cmap.finish());
+        ctx.writer.cmap(cmap_ref, &cmap_data).filter(Filter::FlateDecode);
 
-        // Subset and write the font's bytes.
-        let glyphs: Vec<_> = glyph_set.keys().copied().collect();
-        let data = subset_font(font, &glyphs);
+        // Write the subsetted font's bytes.
         let mut stream = ctx.writer.stream(data_ref, &data);
         stream.filter(Filter::FlateDecode);
 
@@ -150,16 +153,125 @@ pub fn write_fonts(ctx: &mut PdfContext) {
 
         stream.finish();
     }
+
+    Ok(())
+}
+
+/// Subset the fonts to their used glyphs.
+///
+/// Subsetting is the most expensive part of embedding fonts, so it is
+/// memoized: Repeated exports, for example in watch mode, only subset the
+/// fonts whose glyph sets changed. Since comemo keeps its cache per thread,
+/// the fonts are subsetted on the exporting thread.
+fn subset_fonts(
+    fonts: &[&Font],
+    glyph_sets: &HashMap<Font, BTreeMap<u16, EcoString>>,
+    options: PdfOptions,
+) -> StrResult<Vec<Bytes>> {
+    fonts
+        .iter()
+        .map(|&font| {
+            let glyphs: Vec<_> = glyph_sets[font].keys().copied().collect();
+            subset_font(font, &glyphs, options)
+        })
+        .collect()
 }
 
 /// Subset a font to the given glyphs.
+///
+/// Fonts whose license restricts embedding (through the `OS/2` table's
+/// `fsType` field) are refused unless the options allow them. Fonts whose
+/// license forbids subsetting are embedded in full instead. The subsetter
+/// keeps the `OS/2` table as is, so the embedded font carries the same
+/// permissions.
 #[comemo::memoize]
-fn subset_font(font: &Font, glyphs: &[u16]) -> Bytes {
+#[tracing::instrument(skip_all)]
+pub(crate) fn subset_font(
+    font: &Font,
+    glyphs: &[u16],
+    options: PdfOptions,
+) -> StrResult<Bytes> {
     let data = font.data();
+    if font.ttf().permissions() == Some(Permissions::Restricted)
+        && !options.embed_restricted_fonts
+    {
+        bail!(
+            "the license of font family \"{}\" does not permit embedding",
+            font.info().family
+        );
+    }
+
+    // A PDF can only embed a single face, so a face of a collection is
+    // extracted when the font is embedded in full.
+    let full = || {
+        if !data.starts_with(b"ttcf") {
+            return Ok(data.to_vec());
+        }
+        sfnt::Tables::parse(data, font.index())
+            .map(sfnt::Tables::write)
+            .ok_or_else(|| {
+                eco_format!(
+                    "failed to extract font family \"{}\" from its collection",
+                    font.info().family
+                )
+            })
+    };
+
+    if !font.ttf().is_subsetting_allowed() {
+        return Ok(deflate(&full()?).into());
+    }
+
     let profile = subsetter::Profile::pdf(glyphs);
-    let subsetted = subsetter::subset(data, font.index(), profile);
-    let data = subsetted.as_deref().unwrap_or(data);
-    deflate(data).into()
+    let subsetted = subsetter::subset(data, font.index(), profile)
+        .map(|subset| prune_tables(&subset, glyphs, options).unwrap_or(subset));
+
+    // Make sure the subset can be parsed again and that its glyph offsets
+    // match its outlines and fall back to the full font otherwise, so that a
+    // corrupt subset never ends up in the PDF.
+    let valid = |subset: &[u8]| {
+        ttf_parser::Face::parse(subset, 0).is_ok()
+            && sfnt::Tables::parse(subset, 0)
+                .map_or(false, |tables| sfnt::check_glyph_offsets(&tables))
+    };
+
+    let data = match subsetted {
+        Ok(subset) if valid(&subset) => subset,
+        _ => full()?,
+    };
+
+    Ok(deflate(&data).into())
+}
+
+/// Remove data that PDF viewers don't need from a subsetted font.
+///
+/// The subsetter keeps the names of all retained glyphs in the `post` table,
+/// all hinting instructions and the whole `name` table. Unless glyph names are
+/// requested, the `post` table is reduced to one without names. Requested
+/// names are kept for the used glyphs and the components of composite glyphs
+/// among them, which the subsetter retains as well.
+fn prune_tables(data: &[u8], glyphs: &[u16], options: PdfOptions) -> Option<Vec<u8>> {
+    let mut tables = sfnt::Tables::parse(data, 0)?;
+    let post = Tag::from_bytes(b"post");
+    if let Some(table) = tables.get(post) {
+        let keep = if options.glyph_names {
+            Some(sfnt::glyph_closure(&tables, glyphs)?)
+        } else {
+            None
+        };
+        let table = sfnt::subset_post(table, keep.as_deref())?;
+        tables.set(post, table);
+    }
+    if options.strip_hinting {
+        sfnt::strip_hinting(&mut tables)?;
+    }
+    let name = Tag::from_bytes(b"name");
+    if let Some(table) = tables.get(name) {
+        if options.minimize_names {
+            let table = sfnt::minimize_name(table)?;
+            tables.set(name, table);
+        }
+    }
+    Some(tables.write())
 }
 
 /// Create a /ToUnicode CMap.
@@ -202,3 +314,151 @@ fn create_cmap(
 
     cmap
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::doc::{Document, Frame, FrameItem, Glyph, Lang, TextItem};
+    use crate::geom::{Abs, Color, Em, Point, Size};
+    use crate::syntax::Span;
+
+    /// Extract all decompressed streams from a PDF.
+    fn streams(pdf: &[u8]) -> Vec<Vec<u8>> {
+        let (start, end) = (b"\nstream\n", b"\nendstream");
+        let mut streams = vec![];
+        let mut rest = pdf;
+        while let Some(i) = rest.windows(start.len()).position(|w| w == start) {
+            rest = &rest[i + start.len()..];
+            let Some(j) = rest.windows(end.len()).position(|w| w == end) else { break };
+            if let Ok(data) = miniz_oxide::inflate::decompress_to_vec_zlib(&rest[..j]) {
+                streams.push(data);
+            }
+            rest = &rest[j..];
+        }
+        streams
+    }
+
+    #[test]
+    fn test_embedded_font_is_parseable() {
+        let data = include_bytes!("../../../assets/fonts/IBMPlexSans-Regular.ttf");
+        let font = Font::new(Bytes::from_static(data), 0).unwrap();
+        let glyphs = "Hi"
+            .char_indices()
+            .map(|(i, c)| Glyph {
+                id: font.ttf().glyph_index(c).unwrap().0,
+                x_advance: Em::new(0.5),
+                x_offset: Em::zero(),
+                range: i as u16..i as u16 + 1,
+                span: (Span::detached(), 0),
+            })
+            .collect();
+
+        let mut frame = Frame::new(Size::splat(Abs::pt(100.0)));
+        let text = TextItem {
+            font,
+            size: Abs::pt(10.0),
+            fill: Color::BLACK.into(),
+            lang: Lang::ENGLISH,
+            text: "Hi".into(),
+            glyphs,
+        };
+        frame.push(Point::with_y(Abs::pt(20.0)), FrameItem::Text(text));
+
+        let pdf =
+            crate::export::pdf(&Document { pages: vec![frame], ..Document::default() })
+                .unwrap();
+        let faces = streams(&pdf)
+            .iter()
+            .filter(|data| ttf_parser::Face::parse(data, 0).is_ok())
+            .count();
+        assert_eq!(faces, 1);
+    }
+
+    #[test]
+    fn test_repeated_subsetting_is_cached() {
+        let data = include_bytes!("../../../assets/fonts/IBMPlexSans-Regular.ttf");
+        let font = Font::new(Bytes::from_static(data), 0).unwrap();
+        let glyph_sets = HashMap::from([(
+            font.clone(),
+            BTreeMap::from([(font.ttf().glyph_index('A').unwrap().0, "A".into())]),
+        )]);
+
+        // A cached subset shares its buffer with the first result.
+        let options = PdfOptions::default();
+        let first = subset_fonts(&[&font], &glyph_sets, options).unwrap();
+        let second = subset_fonts(&[&font], &glyph_sets, options).unwrap();
+        assert_eq!(first[0].as_ptr(), second[0].as_ptr());
+    }
+
+    #[test]
+    fn test_subsetting_keeps_glyph_ids() {
+        struct Sink;
+        impl ttf_parser::OutlineBuilder for Sink {
+            fn move_to(&mut self, _: f32, _: f32) {}
+            fn line_to(&mut self, _: f32, _: f32) {}
+            fn quad_to(&mut self, _: f32, _: f32, _: f32, _: f32) {}
+            fn curve_to(&mut self, _: f32, _: f32, _: f32, _: f32, _: f32, _: f32) {}
+            fn close(&mut self) {}
+        }
+
+        // The "Å" is a composite of the "A" and a ring with a high glyph id.
+        let data = include_bytes!("../../../assets/fonts/IBMPlexSans-Regular.ttf");
+        let font = Font::new(Bytes::from_static(data), 0).unwrap();
+        let (ring, a, aring) = (GlyphId(906), GlyphId(33), GlyphId(382));
+        assert_eq!(font.ttf().glyph_index('Å'), Some(aring));
+
+        let subset = subset_font(&font, &[aring.0], PdfOptions::default()).unwrap();
+        let subset = miniz_oxide::inflate::decompress_to_vec_zlib(&subset).unwrap();
+        let face = ttf_parser::Face::parse(&subset, 0).unwrap();
+        assert_eq!(face.number_of_glyphs(), font.ttf().number_of_glyphs());
+
+        // The used glyph and its components keep their indices, the others
+        // lose their outlines.
+        for id in [aring, a, ring] {
+            assert_eq!(
+                face.outline_glyph(id, &mut Sink),
+                font.ttf().outline_glyph(id, &mut Sink),
+            );
+        }
+        let unused = font.ttf().glyph_index('a').unwrap();
+        assert!(font.ttf().outline_glyph(unused, &mut Sink).is_some());
+        assert_eq!(face.outline_glyph(unused, &mut Sink), None);
+    }
+
+    #[test]
+    fn test_restricted_font_is_refused() {
+        // Mark the font as restricted through the `fsType` field.
+        let data = include_bytes!("../../../assets/fonts/IBMPlexSans-Regular.ttf");
+        let mut tables = sfnt::Tables::parse(data, 0).unwrap();
+        let os2 = Tag::from_bytes(b"OS/2");
+        let mut table = tables.get(os2).unwrap().to_vec();
+        table[8..10].copy_from_slice(&2u16.to_be_bytes());
+        tables.set(os2, table);
+
+        let font = Font::new(Bytes::from(tables.write()), 0).unwrap();
+        assert_eq!(font.ttf().permissions(), Some(Permissions::Restricted));
+
+        let options = PdfOptions::default();
+        assert!(subset_font(&font, &[0], options).is_err());
+
+        let options = PdfOptions { embed_restricted_fonts: true, ..options };
+        assert!(subset_font(&font, &[0], options).is_ok());
+    }
+
+    #[test]
+    fn test_minimized_names_keep_license() {
+        let data = include_bytes!("../../../assets/fonts/IBMPlexSans-Regular.ttf");
+        let font = Font::new(Bytes::from_static(data), 0).unwrap();
+        let options = PdfOptions { minimize_names: true, ..PdfOptions::default() };
+        let subset = subset_font(&font, &[0], options).unwrap();
+        let subset = miniz_oxide::inflate::decompress_to_vec_zlib(&subset).unwrap();
+        let face = ttf_parser::Face::parse(&subset, 0).unwrap();
+
+        let ids: Vec<_> = face.names().into_iter().map(|name| name.name_id).collect();
+        assert!(ids.contains(&name_id::FAMILY));
+        assert!(ids.contains(&name_id::LICENSE));
+        assert!(!ids.contains(&name_id::DESIGNER));
+        assert!(ids.len() < font.ttf().names().into_iter().count());
+        assert_eq!(face.permissions(), font.ttf().permissions());
+    }
+}
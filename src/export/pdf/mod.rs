@@ -4,35 +4,69 @@ mod font;
 mod image;
 mod outline;
 mod page;
+mod sfnt;
 
 use std::cmp::Eq;
-use std::collections::{BTreeMap, HashMap};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::ffi::OsStr;
 use std::hash::Hash;
+use std::path::Path;
 
-use ecow::EcoString;
+use ecow::{eco_format, EcoString};
 use pdf_writer::types::Direction;
-use pdf_writer::{Finish, Name, PdfWriter, Ref, TextStr};
-use xmp_writer::{LangId, RenditionClass, XmpWriter};
+use pdf_writer::{Filter, Finish, Name, PdfWriter, Ref, Str, TextStr};
+use xmp_writer::{DateTime, LangId, RenditionClass, XmpWriter};
+
+pub(crate) use self::font::subset_font;
 
 use self::page::Page;
-use crate::doc::{Document, Lang};
+use crate::diag::StrResult;
+use crate::doc::{Document, FormField, Lang, PageLabel, PageLabelStyle};
+use crate::eval::Datetime;
 use crate::font::Font;
 use crate::geom::{Abs, Dir, Em};
 use crate::image::Image;
 use crate::model::Introspector;
+use crate::util::Bytes;
 
 /// Export a document into a PDF file.
 ///
-/// Returns the raw bytes making up the PDF file.
+/// Returns the raw bytes making up the PDF file or an error if a font may not
+/// be embedded.
+pub fn pdf(document: &Document) -> StrResult<Vec<u8>> {
+    pdf_with(document, PdfOptions::default())
+}
+
+/// Export a document into a PDF file with the given options.
 #[tracing::instrument(skip_all)]
-pub fn pdf(document: &Document) -> Vec<u8> {
-    let mut ctx = PdfContext::new(document);
+pub fn pdf_with(document: &Document, options: PdfOptions) -> StrResult<Vec<u8>> {
+    let mut ctx = PdfContext::new(document, options);
     page::construct_pages(&mut ctx, &document.pages);
-    font::write_fonts(&mut ctx);
+    font::write_fonts(&mut ctx)?;
     image::write_images(&mut ctx);
     page::write_page_tree(&mut ctx);
+    page::write_form_fields(&mut ctx);
     write_catalog(&mut ctx);
-    ctx.writer.finish()
+    Ok(ctx.writer.finish())
+}
+
+/// Settings for PDF export.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct PdfOptions {
+    /// Whether to keep the names of the embedded glyphs. PDF viewers don't
+    /// need them, but they can help when debugging the embedded fonts.
+    pub glyph_names: bool,
+    /// Whether to remove the hinting instructions from embedded TrueType
+    /// fonts. Hinting only improves rendering at low resolutions, so this
+    /// makes print-targeted PDFs smaller at no cost.
+    pub strip_hinting: bool,
+    /// Whether to reduce the `name` tables of embedded fonts to the records
+    /// that identify the font and its license.
+    pub minimize_names: bool,
+    /// Whether to embed fonts whose license restricts embedding (through the
+    /// `OS/2` table's `fsType` field). By default, export fails for such
+    /// fonts. Only enable this if you have permission to embed them.
+    pub embed_restricted_fonts: bool,
 }
 
 /// Identifies the color space definitions.
@@ -42,15 +76,21 @@ const D65_GRAY: Name<'static> = Name(b"d65gray");
 /// Context for exporting a whole PDF document.
 pub struct PdfContext<'a> {
     document: &'a Document,
+    options: PdfOptions,
     introspector: Introspector,
     writer: PdfWriter,
     pages: Vec<Page>,
     page_heights: Vec<f32>,
+    /// The label of each page, if it is numbered.
+    page_labels: Vec<Option<PageLabel>>,
     alloc: Ref,
     page_tree_ref: Ref,
     font_refs: Vec<Ref>,
     image_refs: Vec<Ref>,
     page_refs: Vec<Ref>,
+    /// The form fields alongside the widget annotations that show them.
+    /// Widgets with the same name belong to the same field.
+    fields: Vec<(Ref, FormField, Vec<Ref>)>,
     font_map: Remapper<Font>,
     image_map: Remapper<Image>,
     /// For each font a mapping from used glyphs to their text representation.
@@ -64,18 +104,21 @@ pub struct PdfContext<'a> {
 }
 
 impl<'a> PdfContext<'a> {
-    fn new(document: &'a Document) -> Self {
+    fn new(document: &'a Document, options: PdfOptions) -> Self {
         let mut alloc = Ref::new(1);
         let page_tree_ref = alloc.bump();
         Self {
             document,
+            options,
             introspector: Introspector::new(&document.pages),
             writer: PdfWriter::new(),
             pages: vec![],
             page_heights: vec![],
+            page_labels: vec![],
             alloc,
             page_tree_ref,
             page_refs: vec![],
+            fields: vec![],
             font_refs: vec![],
             image_refs: vec![],
             font_map: Remapper::new(),
@@ -104,6 +147,9 @@ fn write_catalog(ctx: &mut PdfContext) {
     // Write the outline tree.
     let outline_root_id = outline::write_outline(ctx);
 
+    // Write the embedded files.
+    let embedded = write_embedded_files(ctx);
+
     // Write the document information.
     let mut info = ctx.writer.document_info(ctx.alloc.bump());
     let mut xmp = XmpWriter::new();
@@ -117,6 +163,25 @@ fn write_catalog(ctx: &mut PdfContext) {
         info.author(TextStr(&authors.join(", ")));
         xmp.creator(authors.iter().map(|s| s.as_str()));
     }
+
+    let keywords = &ctx.document.keywords;
+    if !keywords.is_empty() {
+        let joined = keywords.join(", ");
+        info.keywords(TextStr(&joined));
+        xmp.pdf_keywords(&joined);
+    }
+
+    if let Some(date) = ctx.document.date {
+        if let Some(pdf_date) = pdf_date(date) {
+            info.creation_date(pdf_date);
+            info.modified_date(pdf_date);
+        }
+        if let Some(xmp_date) = xmp_date(date) {
+            xmp.create_date(xmp_date);
+            xmp.modify_date(xmp_date);
+        }
+    }
+
     info.creator(TextStr("Typst"));
     info.finish();
     xmp.creator_tool("Typst");
@@ -143,11 +208,210 @@ fn write_catalog(ctx: &mut PdfContext) {
         catalog.outlines(outline_root_id);
     }
 
+    if !embedded.is_empty() {
+        let mut names = catalog.insert(Name(b"Names")).dict();
+        let mut files = names.insert(Name(b"EmbeddedFiles")).dict();
+        let mut array = files.insert(Name(b"Names")).array();
+        for (name, spec_ref) in &embedded {
+            array.item(TextStr(name));
+            array.item(*spec_ref);
+        }
+    }
+
+    // Let viewers show the same page numbers as the pages themselves.
+    if ctx.page_labels.iter().any(Option::is_some) {
+        let mut labels = catalog.insert(Name(b"PageLabels")).dict();
+        let mut nums = labels.insert(Name(b"Nums")).array();
+        let mut prev: Option<&Option<PageLabel>> = None;
+        for (i, label) in ctx.page_labels.iter().enumerate() {
+            if prev.map_or(false, |prev| continues(prev, label)) {
+                prev = Some(label);
+                continue;
+            }
+
+            nums.item(i as i32);
+            let mut range = nums.push().dict();
+            match label {
+                Some(label) => {
+                    if !label.prefix.is_empty() {
+                        range.pair(Name(b"P"), TextStr(&label.prefix));
+                    }
+                    if let Some(style) = label.style {
+                        range.pair(Name(b"S"), Name(page_label_style(style)));
+                        if label.number != 1 {
+                            range.pair(Name(b"St"), label.number as i32);
+                        }
+                    }
+                }
+                None => {
+                    range.pair(Name(b"S"), Name(b"D"));
+                    range.pair(Name(b"St"), i as i32 + 1);
+                }
+            }
+            prev = Some(label);
+        }
+    }
+
+    if !ctx.fields.is_empty() {
+        let mut form = catalog.insert(Name(b"AcroForm")).dict();
+        form.insert(Name(b"Fields"))
+            .array()
+            .items(ctx.fields.iter().map(|&(id, ..)| id));
+        form.pair(Name(b"NeedAppearances"), true);
+        form.pair(Name(b"DA"), Str(b"/Helv 0 Tf 0 g"));
+        let mut resources = form.insert(Name(b"DR")).dict();
+        let mut fonts = resources.insert(Name(b"Font")).dict();
+        for (name, base) in
+            [(b"Helv", &b"Helvetica"[..]), (b"ZaDb", &b"ZapfDingbats"[..])]
+        {
+            let mut font = fonts.insert(Name(name)).dict();
+            font.pair(Name(b"Type"), Name(b"Font"));
+            font.pair(Name(b"Subtype"), Name(b"Type1"));
+            font.pair(Name(b"BaseFont"), Name(base));
+        }
+    }
+
     if let Some(lang) = lang {
         catalog.lang(TextStr(lang.as_str()));
     }
 }
 
+/// Write the files embedded with the `embed` function.
+///
+/// Returns the names and file specification references sorted by name, as
+/// required for the entries of a PDF name tree. Files that are embedded more
+/// than once are only written once and files from different directories
+/// that share a name are told apart by a numeric suffix.
+fn write_embedded_files(ctx: &mut PdfContext) -> Vec<(EcoString, Ref)> {
+    let mut embedded = vec![];
+    let mut seen = HashSet::new();
+    let mut taken = HashSet::new();
+    for elem in ctx.introspector.query(&item!(embed_func).select()) {
+        let path = elem.expect_field::<EcoString>("path");
+        let data = elem.expect_field::<Bytes>("data");
+        if !seen.insert((path.clone(), data.clone())) {
+            continue;
+        }
+
+        let description =
+            elem.field("description").and_then(|v| v.cast::<EcoString>().ok());
+        let name = unique_name(
+            Path::new(path.as_str())
+                .file_name()
+                .and_then(OsStr::to_str)
+                .unwrap_or(path.as_str()),
+            &mut taken,
+        );
+
+        let file_ref = ctx.alloc.bump();
+        let mut stream = ctx.writer.stream(file_ref, &deflate(&data));
+        stream.filter(Filter::FlateDecode);
+        stream.pair(Name(b"Type"), Name(b"EmbeddedFile"));
+        stream.finish();
+
+        let spec_ref = ctx.alloc.bump();
+        let mut spec = ctx.writer.indirect(spec_ref).dict();
+        spec.pair(Name(b"Type"), Name(b"Filespec"));
+        spec.pair(Name(b"F"), Str(name.as_bytes()));
+        spec.pair(Name(b"UF"), TextStr(&name));
+        if let Some(description) = description.as_ref().map(EcoString::as_str) {
+            spec.pair(Name(b"Desc"), TextStr(description));
+        }
+        spec.insert(Name(b"EF")).dict().pair(Name(b"F"), file_ref);
+        spec.finish();
+
+        embedded.push((name, spec_ref));
+    }
+
+    embedded.sort_by(|(a, _), (b, _)| a.cmp(b));
+    embedded
+}
+
+/// Make a file name unique among the `taken` ones by appending a counter to
+/// its stem, e.g. `data-2.csv`, and mark the result as taken.
+fn unique_name(name: &str, taken: &mut HashSet<EcoString>) -> EcoString {
+    let (stem, extension) = match name.rsplit_once('.') {
+        Some((stem, extension)) if !stem.is_empty() => (stem, Some(extension)),
+        _ => (name, None),
+    };
+
+    let mut unique = EcoString::from(name);
+    let mut counter = 1;
+    while taken.contains(&unique) {
+        counter += 1;
+        unique = match extension {
+            Some(extension) => eco_format!("{stem}-{counter}.{extension}"),
+            None => eco_format!("{stem}-{counter}"),
+        };
+    }
+
+    taken.insert(unique.clone());
+    unique
+}
+
+/// Convert a datetime to a PDF date.
+fn pdf_date(datetime: Datetime) -> Option<pdf_writer::Date> {
+    let year = datetime.year().filter(|&y| y >= 0)? as u16;
+
+    let mut pdf_date = pdf_writer::Date::new(year);
+    if let Some(month) = datetime.month() {
+        pdf_date = pdf_date.month(month);
+    }
+    if let Some(day) = datetime.day() {
+        pdf_date = pdf_date.day(day);
+    }
+    if let Some(hour) = datetime.hour() {
+        pdf_date = pdf_date.hour(hour);
+    }
+    if let Some(minute) = datetime.minute() {
+        pdf_date = pdf_date.minute(minute);
+    }
+    if let Some(second) = datetime.second() {
+        pdf_date = pdf_date.second(second);
+    }
+
+    Some(pdf_date)
+}
+
+/// Convert a datetime to an XMP date.
+fn xmp_date(datetime: Datetime) -> Option<DateTime> {
+    let year = datetime.year().filter(|&y| y >= 0)? as u16;
+    Some(DateTime {
+        year,
+        month: datetime.month(),
+        day: datetime.day(),
+        hour: datetime.hour(),
+        minute: datetime.minute(),
+        second: datetime.second(),
+        timezone: None,
+    })
+}
+
+/// Whether a page's label follows from the range the previous page belongs to.
+fn continues(prev: &Option<PageLabel>, next: &Option<PageLabel>) -> bool {
+    match (prev, next) {
+        (None, None) => true,
+        (Some(prev), Some(next)) => {
+            prev.prefix == next.prefix
+                && prev.style == next.style
+                && (prev.style.is_none()
+                    || prev.number.checked_add(1) == Some(next.number))
+        }
+        _ => false,
+    }
+}
+
+/// The PDF name of a page label style.
+fn page_label_style(style: PageLabelStyle) -> &'static [u8] {
+    match style {
+        PageLabelStyle::Arabic => b"D",
+        PageLabelStyle::LowerRoman => b"r",
+        PageLabelStyle::UpperRoman => b"R",
+        PageLabelStyle::LowerAlpha => b"a",
+        PageLabelStyle::UpperAlpha => b"A",
+    }
+}
+
 /// Compress data with the DEFLATE algorithm.
 #[tracing::instrument(skip_all)]
 fn deflate(data: &[u8]) -> Vec<u8> {
@@ -233,3 +497,60 @@ impl RefExt for Ref {
         prev
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::doc::{FormFieldKind, Frame, FrameItem, Meta};
+    use crate::geom::{Point, Size};
+
+    #[test]
+    fn test_trim_and_bleed_boxes() {
+        // A page trimmed to 100pt with 10pt of bleed and 20pt for crop marks.
+        let mut frame = Frame::new(Size::splat(Abs::pt(160.0)));
+        let trim = Meta::PageTrim(Abs::pt(10.0));
+        frame.push(
+            Point::splat(Abs::pt(30.0)),
+            FrameItem::Meta(trim, Size::splat(Abs::pt(100.0))),
+        );
+
+        let pdf = pdf(&Document { pages: vec![frame], ..Document::default() }).unwrap();
+        let pdf = String::from_utf8_lossy(&pdf);
+        assert!(pdf.contains("/TrimBox [30 30 130 130]"));
+        assert!(pdf.contains("/BleedBox [20 20 140 140]"));
+    }
+
+    #[test]
+    fn test_widgets_with_same_name_share_field() {
+        let mut frame = Frame::new(Size::splat(Abs::pt(100.0)));
+        let field = FormField {
+            name: "agree".into(),
+            kind: FormFieldKind::Checkbox { checked: true },
+        };
+        for y in [10.0, 50.0] {
+            frame.push(
+                Point::with_y(Abs::pt(y)),
+                FrameItem::Meta(Meta::Field(field.clone()), Size::splat(Abs::pt(10.0))),
+            );
+        }
+
+        let pdf = pdf(&Document { pages: vec![frame], ..Document::default() }).unwrap();
+        let pdf = String::from_utf8_lossy(&pdf);
+        assert_eq!(pdf.matches("/Subtype /Widget").count(), 2);
+        assert_eq!(pdf.matches("/T (agree)").count(), 1);
+        assert_eq!(pdf.matches("/DA (/ZaDb 0 Tf 0 g)").count(), 1);
+    }
+
+    #[test]
+    fn test_unique_name() {
+        let mut taken = HashSet::new();
+        assert_eq!(unique_name("data.csv", &mut taken), "data.csv");
+        assert_eq!(unique_name("data.csv", &mut taken), "data-2.csv");
+        assert_eq!(unique_name("data.csv", &mut taken), "data-3.csv");
+        assert_eq!(unique_name("data-2.csv", &mut taken), "data-2-2.csv");
+        assert_eq!(unique_name("README", &mut taken), "README");
+        assert_eq!(unique_name("README", &mut taken), "README-2");
+        assert_eq!(unique_name(".hidden", &mut taken), ".hidden");
+        assert_eq!(unique_name(".hidden", &mut taken), ".hidden-2");
+    }
+}
@@ -2,11 +2,13 @@ use ecow::eco_format;
 use pdf_writer::types::{
     ActionType, AnnotationType, ColorSpaceOperand, LineCapStyle, LineJoinStyle,
 };
-use pdf_writer::writers::ColorSpace;
-use pdf_writer::{Content, Filter, Finish, Name, Rect, Ref, Str};
+use pdf_writer::writers::{Annotation, ColorSpace};
+use pdf_writer::{Content, Filter, Finish, Name, Rect, Ref, Str, TextStr};
 
 use super::{deflate, AbsExt, EmExt, PdfContext, RefExt, D65_GRAY, SRGB};
-use crate::doc::{Destination, Frame, FrameItem, GroupItem, Meta, TextItem};
+use crate::doc::{
+    Destination, FormField, FormFieldKind, Frame, FrameItem, GroupItem, Meta, TextItem,
+};
 use crate::font::Font;
 use crate::geom::{
     self, Abs, Color, Em, Geometry, LineCap, LineJoin, Numeric, Paint, Point, Ratio,
@@ -28,6 +30,10 @@ pub fn construct_page(ctx: &mut PdfContext, frame: &Frame) {
     let page_ref = ctx.alloc.bump();
     ctx.page_refs.push(page_ref);
     ctx.page_heights.push(frame.height().to_f32());
+    ctx.page_labels.push(frame.items().find_map(|(_, item)| match item {
+        FrameItem::Meta(Meta::PageLabel(label), _) => Some(label.clone()),
+        _ => None,
+    }));
 
     let mut ctx = PageContext {
         parent: ctx,
@@ -37,10 +43,27 @@ pub fn construct_page(ctx: &mut PdfContext, frame: &Frame) {
         saves: vec![],
         bottom: 0.0,
         links: vec![],
+        fields: vec![],
     };
 
     let size = frame.size();
 
+    // Find the trim and bleed boxes in the PDF coordinate system.
+    let boxes = frame.items().find_map(|(pos, item)| match item {
+        FrameItem::Meta(Meta::PageTrim(bleed), trim) => {
+            let rect = |outset: Abs| {
+                Rect::new(
+                    (pos.x - outset).max(Abs::zero()).to_f32(),
+                    (size.y - pos.y - trim.y - outset).max(Abs::zero()).to_f32(),
+                    (pos.x + trim.x + outset).min(size.x).to_f32(),
+                    (size.y - pos.y + outset).min(size.y).to_f32(),
+                )
+            };
+            Some((rect(Abs::zero()), rect(*bleed)))
+        }
+        _ => None,
+    });
+
     // Make the coordinate system start at the top-left.
     ctx.bottom = size.y.to_f32();
     ctx.transform(Transform {
@@ -60,6 +83,8 @@ pub fn construct_page(ctx: &mut PdfContext, frame: &Frame) {
         content: ctx.content,
         id: ctx.page_ref,
         links: ctx.links,
+        fields: ctx.fields,
+        boxes,
     };
 
     ctx.parent.pages.push(page);
@@ -107,17 +132,25 @@ pub fn write_page_tree(ctx: &mut PdfContext) {
 fn write_page(ctx: &mut PdfContext, page: Page) {
     let content_id = ctx.alloc.bump();
 
+    // Widget annotations must be indirect objects so that their form fields
+    // can refer to them.
+    let widget_refs: Vec<Ref> = page.fields.iter().map(|_| ctx.alloc.bump()).collect();
+
     let mut page_writer = ctx.writer.page(page.id);
     page_writer.parent(ctx.page_tree_ref);
 
     let w = page.size.x.to_f32();
     let h = page.size.y.to_f32();
     page_writer.media_box(Rect::new(0.0, 0.0, w, h));
+    if let Some((trim, bleed)) = page.boxes {
+        page_writer.trim_box(trim);
+        page_writer.bleed_box(bleed);
+    }
     page_writer.contents(content_id);
 
-    let mut annotations = page_writer.annotations();
+    let mut annotations = page_writer.insert(Name(b"Annots")).array();
     for (dest, rect) in page.links {
-        let mut annotation = annotations.push();
+        let mut annotation = annotations.push().start::<Annotation>();
         annotation.subtype(AnnotationType::Link).rect(rect);
         annotation.border(0.0, 0.0, 0.0, None);
 
@@ -145,14 +178,103 @@ fn write_page(ctx: &mut PdfContext, page: Page) {
         }
     }
 
+    annotations.items(widget_refs.iter().copied());
     annotations.finish();
     page_writer.finish();
 
+    for ((field, rect), id) in page.fields.iter().zip(widget_refs) {
+        let parent = add_widget(ctx, field, id);
+        write_widget(ctx, id, page.id, parent, field, *rect);
+    }
+
     let data = page.content.finish();
     let data = deflate(&data);
     ctx.writer.stream(content_id, &data).filter(Filter::FlateDecode);
 }
 
+/// Add a widget to the form field with its name and return the field's id.
+///
+/// A field can be shown several times, for example in a repeated table header,
+/// but its value exists only once. Thus, all widgets with the same name
+/// become kids of a single field.
+fn add_widget(ctx: &mut PdfContext, field: &FormField, widget: Ref) -> Ref {
+    if let Some((id, _, kids)) =
+        ctx.fields.iter_mut().find(|(_, other, _)| other.name == field.name)
+    {
+        kids.push(widget);
+        return *id;
+    }
+
+    let id = ctx.alloc.bump();
+    ctx.fields.push((id, field.clone(), vec![widget]));
+    id
+}
+
+/// Write a widget annotation that shows a form field.
+fn write_widget(
+    ctx: &mut PdfContext,
+    id: Ref,
+    page: Ref,
+    parent: Ref,
+    field: &FormField,
+    rect: Rect,
+) {
+    let mut dict = ctx.writer.indirect(id).dict();
+    dict.pair(Name(b"Type"), Name(b"Annot"));
+    dict.pair(Name(b"Subtype"), Name(b"Widget"));
+    dict.pair(Name(b"Rect"), rect);
+    dict.pair(Name(b"P"), page);
+    dict.pair(Name(b"F"), 4);
+    dict.pair(Name(b"Parent"), parent);
+
+    if let FormFieldKind::Checkbox { checked } = field.kind {
+        let state = if checked { Name(b"Yes") } else { Name(b"Off") };
+        dict.pair(Name(b"AS"), state);
+        dict.insert(Name(b"MK")).dict().pair(Name(b"CA"), TextStr("4"));
+    }
+}
+
+/// Write the form fields whose widgets were placed on the pages.
+#[tracing::instrument(skip_all)]
+pub fn write_form_fields(ctx: &mut PdfContext) {
+    for (id, field, kids) in &ctx.fields {
+        let mut dict = ctx.writer.indirect(*id).dict();
+        dict.pair(Name(b"T"), TextStr(&field.name));
+        dict.insert(Name(b"Kids")).array().items(kids.iter().copied());
+
+        match &field.kind {
+            FormFieldKind::Text { value, multiline } => {
+                dict.pair(Name(b"FT"), Name(b"Tx"));
+                dict.pair(Name(b"DA"), Str(b"/Helv 0 Tf 0 g"));
+                dict.pair(Name(b"V"), TextStr(value));
+                if *multiline {
+                    dict.pair(Name(b"Ff"), 1 << 12);
+                }
+            }
+            FormFieldKind::Checkbox { checked } => {
+                let state = if *checked { Name(b"Yes") } else { Name(b"Off") };
+                dict.pair(Name(b"FT"), Name(b"Btn"));
+                dict.pair(Name(b"DA"), Str(b"/ZaDb 0 Tf 0 g"));
+                dict.pair(Name(b"V"), state);
+            }
+            FormFieldKind::Dropdown { options, selected } => {
+                dict.pair(Name(b"FT"), Name(b"Ch"));
+                dict.pair(Name(b"DA"), Str(b"/Helv 0 Tf 0 g"));
+                dict.pair(Name(b"Ff"), 1 << 17);
+                dict.insert(Name(b"Opt"))
+                    .array()
+                    .items(options.iter().map(|option| TextStr(option)));
+                if let Some(option) = selected.and_then(|i| options.get(i)) {
+                    dict.pair(Name(b"V"), TextStr(option));
+                }
+            }
+            FormFieldKind::Signature => {
+                dict.pair(Name(b"FT"), Name(b"Sig"));
+            }
+        }
+    }
+}
+
 /// Data for an exported page.
 pub struct Page {
     /// The indirect object id of the page.
@@ -163,6 +285,11 @@ pub struct Page {
     pub content: Content,
     /// Links in the PDF coordinate system.
     pub links: Vec<(Destination, Rect)>,
+    /// Form fields in the PDF coordinate system.
+    pub fields: Vec<(FormField, Rect)>,
+    /// The trim and bleed boxes in the PDF coordinate system, if the page
+    /// extends beyond its trimmed size.
+    pub boxes: Option<(Rect, Rect)>,
 }
 
 /// An exporter for the contents of a single PDF page.
@@ -174,6 +301,7 @@ struct PageContext<'a, 'b> {
     saves: Vec<State>,
     bottom: f32,
     links: Vec<(Destination, Rect)>,
+    fields: Vec<(FormField, Rect)>,
 }
 
 /// A simulated graphics state used to deduplicate graphics state changes and
@@ -333,6 +461,10 @@ fn write_frame(ctx: &mut PageContext, frame: &Frame) {
                 Meta::Elem(_) => {}
                 Meta::Hide => {}
                 Meta::PageNumbering(_) => {}
+                Meta::PageLabel(_) => {}
+                Meta::PageTrim(_) => {}
+                Meta::Role(_) => {}
+                Meta::Field(field) => write_field(ctx, pos, field, *size),
             },
         }
     }
@@ -516,12 +648,24 @@ fn write_image(ctx: &mut PageContext, x: f32, y: f32, image: &Image, size: Size)
 
 /// Save a link for later writing in the annotations dictionary.
 fn write_link(ctx: &mut PageContext, pos: Point, dest: &Destination, size: Size) {
+    let rect = annotation_rect(ctx, pos, size);
+    ctx.links.push((dest.clone(), rect));
+}
+
+/// Save a form field for later writing in the annotations dictionary.
+fn write_field(ctx: &mut PageContext, pos: Point, field: &FormField, size: Size) {
+    let rect = annotation_rect(ctx, pos, size);
+    ctx.fields.push((field.clone(), rect));
+}
+
+/// Compute the bounding box of a transformed annotation area in the PDF
+/// coordinate system.
+fn annotation_rect(ctx: &PageContext, pos: Point, size: Size) -> Rect {
     let mut min_x = Abs::inf();
     let mut min_y = Abs::inf();
     let mut max_x = -Abs::inf();
     let mut max_y = -Abs::inf();
 
-    // Compute the bounding box of the transformed link.
     for point in [
         pos,
         pos + Point::with_x(size.x),
@@ -539,9 +683,7 @@ fn write_link(ctx: &mut PageContext, pos: Point, dest: &Destination, size: Size)
     let x2 = max_x.to_f32();
     let y1 = max_y.to_f32();
     let y2 = min_y.to_f32();
-    let rect = Rect::new(x1, y1, x2, y2);
-
-    ctx.links.push((dest.clone(), rect));
+    Rect::new(x1, y1, x2, y2)
 }
 
 impl From<&LineCap> for LineCapStyle {
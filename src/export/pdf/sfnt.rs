@@ -0,0 +1,598 @@
+//! Rewriting tables of OpenType fonts after subsetting.
+
+use ttf_parser::{name_id, Tag};
+
+/// The tables of a single font face.
+pub struct Tables {
+    /// The version tag of the font file.
+    version: u32,
+    /// The tables with their tags.
+    tables: Vec<(Tag, Vec<u8>)>,
+}
+
+impl Tables {
+    /// Parse the table directory of the face with the given index in a font
+    /// or font collection.
+    ///
+    /// The tables of a collection's face can be written as a standalone font,
+    /// which is needed to embed the face in a PDF.
+    pub fn parse(data: &[u8], index: u32) -> Option<Self> {
+        let start = if data.get(..4)? == b"ttcf" {
+            if index >= read_u32(data, 8)? {
+                return None;
+            }
+            read_u32(data, 12 + 4 * index as usize)? as usize
+        } else if index == 0 {
+            0
+        } else {
+            return None;
+        };
+
+        // Table offsets are relative to the start of the file, also in a
+        // collection.
+        let version = read_u32(data, start)?;
+        let count = read_u16(data, start + 4)?;
+        let mut tables = Vec::with_capacity(usize::from(count));
+        for i in 0..usize::from(count) {
+            let record = start + 12 + 16 * i;
+            let tag = Tag(read_u32(data, record)?);
+            let offset = read_u32(data, record + 8)? as usize;
+            let length = read_u32(data, record + 12)? as usize;
+            let table = data.get(offset..offset.checked_add(length)?)?;
+            tables.push((tag, table.to_vec()));
+        }
+        Some(Self { version, tables })
+    }
+
+    /// The data of the table with the given tag.
+    pub fn get(&self, tag: Tag) -> Option<&[u8]> {
+        self.tables
+            .iter()
+            .find(|(t, _)| *t == tag)
+            .map(|(_, data)| data.as_slice())
+    }
+
+    /// Replace the data of the table with the given tag.
+    pub fn set(&mut self, tag: Tag, data: Vec<u8>) {
+        match self.tables.iter_mut().find(|(t, _)| *t == tag) {
+            Some((_, table)) => *table = data,
+            None => self.tables.push((tag, data)),
+        }
+    }
+
+    /// Remove the table with the given tag.
+    pub fn remove(&mut self, tag: Tag) {
+        self.tables.retain(|(t, _)| *t != tag);
+    }
+
+    /// Write the font, recomputing the table directory and checksums.
+    pub fn write(mut self) -> Vec<u8> {
+        self.tables.sort_by_key(|(tag, _)| *tag);
+
+        // The checksum adjustment in the `head` table must be zero while
+        // computing the checksums.
+        let head = Tag::from_bytes(b"head");
+        for (tag, data) in &mut self.tables {
+            if *tag == head && data.len() >= 12 {
+                data[8..12].fill(0);
+            }
+        }
+
+        let count = self.tables.len() as u32;
+        let selector = count.checked_ilog2().unwrap_or(0);
+        let search_range = (1 << selector) * 16;
+
+        let mut font = vec![];
+        font.extend(self.version.to_be_bytes());
+        font.extend((count as u16).to_be_bytes());
+        font.extend((search_range as u16).to_be_bytes());
+        font.extend((selector as u16).to_be_bytes());
+        font.extend(((count * 16).saturating_sub(search_range) as u16).to_be_bytes());
+
+        let mut offset = 12 + 16 * self.tables.len();
+        let mut head_offset = None;
+        for (tag, data) in &self.tables {
+            if *tag == head {
+                head_offset = Some(offset);
+            }
+            font.extend(tag.0.to_be_bytes());
+            font.extend(checksum(data).to_be_bytes());
+            font.extend((offset as u32).to_be_bytes());
+            font.extend((data.len() as u32).to_be_bytes());
+            offset += pad(data.len());
+        }
+
+        for (_, data) in &self.tables {
+            font.extend(data);
+            font.resize(pad(font.len()), 0);
+        }
+
+        if let Some(offset) = head_offset.filter(|&o| o + 12 <= font.len()) {
+            let adjustment = 0xB1B0AFBA_u32.wrapping_sub(checksum(&font));
+            font[offset + 8..offset + 12].copy_from_slice(&adjustment.to_be_bytes());
+        }
+
+        font
+    }
+}
+
+/// The names that are kept when the `name` table is minimized. These
+/// identify the font and its license.
+const KEPT_NAMES: [u16; 6] = [
+    name_id::FAMILY,
+    name_id::SUBFAMILY,
+    name_id::UNIQUE_ID,
+    name_id::POST_SCRIPT_NAME,
+    name_id::LICENSE,
+    name_id::LICENSE_URL,
+];
+
+/// Reduce a `name` table to the records that identify the font and its
+/// license.
+///
+/// The minimized table always has format 0, so records that refer to the
+/// language tags of a format 1 table are removed as well.
+pub fn minimize_name(data: &[u8]) -> Option<Vec<u8>> {
+    const RECORD: usize = 12;
+
+    let count = usize::from(read_u16(data, 2)?);
+    let storage = usize::from(read_u16(data, 4)?);
+    let mut records = vec![];
+    for i in 0..count {
+        let record = data.get(6 + RECORD * i..6 + RECORD * (i + 1))?;
+        let language = read_u16(record, 4)?;
+        let id = read_u16(record, 6)?;
+        if language >= 0x8000 || !KEPT_NAMES.contains(&id) {
+            continue;
+        }
+
+        let length = usize::from(read_u16(record, 8)?);
+        let offset = storage + usize::from(read_u16(record, 10)?);
+        records.push((record, data.get(offset..offset.checked_add(length)?)?));
+    }
+
+    let mut name = vec![0, 0];
+    name.extend(u16::try_from(records.len()).ok()?.to_be_bytes());
+    name.extend(u16::try_from(6 + RECORD * records.len()).ok()?.to_be_bytes());
+
+    let mut strings = vec![];
+    for (record, string) in records {
+        name.extend(&record[..8]);
+        name.extend(u16::try_from(string.len()).ok()?.to_be_bytes());
+        name.extend(u16::try_from(strings.len()).ok()?.to_be_bytes());
+        strings.extend(string);
+    }
+
+    name.extend(strings);
+    Some(name)
+}
+
+/// Rewrite a `post` table such that it carries no glyph names of removed
+/// glyphs.
+///
+/// Without `keep`, the table is downgraded to version 3.0, which has no glyph
+/// names at all. Otherwise, a version 2.0 table retains the names of the
+/// glyphs in `keep` and the `.notdef` glyph.
+pub fn subset_post(data: &[u8], keep: Option<&[u16]>) -> Option<Vec<u8>> {
+    const HEADER: usize = 32;
+    const STANDARD_NAMES: u16 = 258;
+
+    let header = data.get(..HEADER)?;
+    let version = read_u32(data, 0)?;
+    let Some(keep) = keep else {
+        let mut post = header.to_vec();
+        post[..4].copy_from_slice(&0x00030000_u32.to_be_bytes());
+        return Some(post);
+    };
+
+    if version != 0x00020000 {
+        return Some(data.to_vec());
+    }
+
+    // Read the glyph name indices and the custom names.
+    let count = usize::from(read_u16(data, HEADER)?);
+    let indices = (0..count)
+        .map(|i| read_u16(data, HEADER + 2 + 2 * i))
+        .collect::<Option<Vec<_>>>()?;
+
+    let mut names = vec![];
+    let mut cursor = HEADER + 2 + 2 * count;
+    while cursor < data.len() {
+        let len = usize::from(*data.get(cursor)?);
+        names.push(data.get(cursor + 1..cursor + 1 + len)?);
+        cursor += 1 + len;
+    }
+
+    // Keep only the names of retained glyphs, renumbering the custom ones.
+    let mut post = header.to_vec();
+    let mut kept: Vec<&[u8]> = vec![];
+    post.extend((count as u16).to_be_bytes());
+    for (glyph, &index) in indices.iter().enumerate() {
+        let retained = glyph == 0 || keep.contains(&(glyph as u16));
+        let index = match index.checked_sub(STANDARD_NAMES) {
+            _ if !retained => 0,
+            None => index,
+            Some(custom) => {
+                kept.push(names.get(usize::from(custom))?);
+                STANDARD_NAMES.checked_add(u16::try_from(kept.len() - 1).ok()?)?
+            }
+        };
+        post.extend(index.to_be_bytes());
+    }
+
+    for name in kept {
+        post.push(name.len() as u8);
+        post.extend(name);
+    }
+
+    Some(post)
+}
+
+/// Remove all hinting instructions from a TrueType font.
+///
+/// This drops the `cvt `, `fpgm` and `prep` tables and the instructions of
+/// the individual glyphs in the `glyf` table.
+pub fn strip_hinting(tables: &mut Tables) -> Option<()> {
+    for tag in [b"cvt ", b"fpgm", b"prep"] {
+        tables.remove(Tag::from_bytes(tag));
+    }
+
+    let (glyf_tag, loca_tag) = (Tag::from_bytes(b"glyf"), Tag::from_bytes(b"loca"));
+    let Some((glyf, offsets, long)) = glyph_data(tables) else { return Some(()) };
+
+    let mut new_glyf = vec![];
+    let mut new_offsets = vec![0];
+    for window in offsets.windows(2) {
+        let glyph = glyf.get(window[0]..window[1])?;
+        new_glyf.extend(strip_glyph(glyph)?);
+        new_glyf.resize(new_glyf.len() + new_glyf.len() % 2, 0);
+        new_offsets.push(new_glyf.len());
+    }
+
+    let mut new_loca = vec![];
+    for offset in new_offsets {
+        if long {
+            new_loca.extend((offset as u32).to_be_bytes());
+        } else {
+            new_loca.extend(u16::try_from(offset / 2).ok()?.to_be_bytes());
+        }
+    }
+
+    tables.set(glyf_tag, new_glyf);
+    tables.set(loca_tag, new_loca);
+
+    // No glyph has instructions anymore.
+    let maxp_tag = Tag::from_bytes(b"maxp");
+    if let Some(maxp) = tables.get(maxp_tag).filter(|maxp| maxp.len() >= 32) {
+        let mut maxp = maxp.to_vec();
+        maxp[26..28].fill(0);
+        tables.set(maxp_tag, maxp);
+    }
+
+    Some(())
+}
+
+/// Extend a set of glyphs with the components of its composite glyphs,
+/// recursively.
+///
+/// Fonts without a `glyf` table are returned unchanged.
+pub fn glyph_closure(tables: &Tables, glyphs: &[u16]) -> Option<Vec<u16>> {
+    let mut closure = glyphs.to_vec();
+    let Some((glyf, offsets, _)) = glyph_data(tables) else { return Some(closure) };
+
+    let mut work = glyphs.to_vec();
+    while let Some(id) = work.pop() {
+        let start = *offsets.get(usize::from(id))?;
+        let end = *offsets.get(usize::from(id) + 1)?;
+        for component in components(glyf.get(start..end)?)? {
+            if !closure.contains(&component) {
+                closure.push(component);
+                work.push(component);
+            }
+        }
+    }
+
+    closure.sort_unstable();
+    Some(closure)
+}
+
+/// Whether the glyph offsets in the `loca` table are consistent with the
+/// `glyf` table: There must be an offset for each glyph and its end, and the
+/// offsets must ascend and stay within the `glyf` table.
+///
+/// Fonts without a `glyf` table have no offsets to check.
+pub fn check_glyph_offsets(tables: &Tables) -> bool {
+    if tables.get(Tag::from_bytes(b"glyf")).is_none() {
+        return true;
+    }
+
+    let Some((glyf, offsets, _)) = glyph_data(tables) else { return false };
+    let count = tables
+        .get(Tag::from_bytes(b"maxp"))
+        .and_then(|maxp| read_u16(maxp, 4))
+        .map_or(0, usize::from);
+
+    offsets.len() > count
+        && offsets.windows(2).all(|pair| pair[0] <= pair[1])
+        && offsets.last().map_or(false, |&end| end <= glyf.len())
+}
+
+/// The `glyf` table of a TrueType font together with the glyph offsets from
+/// the `loca` table and whether these are in the long format.
+fn glyph_data(tables: &Tables) -> Option<(&[u8], Vec<usize>, bool)> {
+    let glyf = tables.get(Tag::from_bytes(b"glyf"))?;
+    let loca = tables.get(Tag::from_bytes(b"loca"))?;
+    let head = tables.get(Tag::from_bytes(b"head"))?;
+
+    // Read the glyph offsets in the short or long format.
+    let long = read_u16(head, 50)? == 1;
+    let offsets = if long {
+        (0..loca.len() / 4)
+            .map(|i| read_u32(loca, 4 * i).map(|o| o as usize))
+            .collect::<Option<Vec<_>>>()?
+    } else {
+        (0..loca.len() / 2)
+            .map(|i| read_u16(loca, 2 * i).map(|o| 2 * usize::from(o)))
+            .collect::<Option<Vec<_>>>()?
+    };
+
+    Some((glyf, offsets, long))
+}
+
+/// The glyph ids of the components of a composite glyph. Simple and empty
+/// glyphs have no components.
+fn components(glyph: &[u8]) -> Option<Vec<u16>> {
+    let mut components = vec![];
+    if glyph.is_empty() || read_u16(glyph, 0)? as i16 >= 0 {
+        return Some(components);
+    }
+
+    let mut offset = 10;
+    loop {
+        let flags = read_u16(glyph, offset)?;
+        components.push(read_u16(glyph, offset + 2)?);
+        offset += component_len(flags);
+        if flags & MORE_COMPONENTS == 0 {
+            break;
+        }
+    }
+
+    Some(components)
+}
+
+/// Flag of a composite glyph's component that indicates that more components
+/// follow.
+const MORE_COMPONENTS: u16 = 0x0020;
+
+/// The length of a composite glyph's component with the given flags.
+fn component_len(flags: u16) -> usize {
+    const ARG_1_AND_2_ARE_WORDS: u16 = 0x0001;
+    const WE_HAVE_A_SCALE: u16 = 0x0008;
+    const WE_HAVE_AN_X_AND_Y_SCALE: u16 = 0x0040;
+    const WE_HAVE_A_TWO_BY_TWO: u16 = 0x0080;
+
+    let mut len = 4;
+    len += if flags & ARG_1_AND_2_ARE_WORDS != 0 { 4 } else { 2 };
+    if flags & WE_HAVE_A_SCALE != 0 {
+        len += 2;
+    } else if flags & WE_HAVE_AN_X_AND_Y_SCALE != 0 {
+        len += 4;
+    } else if flags & WE_HAVE_A_TWO_BY_TWO != 0 {
+        len += 8;
+    }
+    len
+}
+
+/// Remove the instructions from the data of a single glyph.
+fn strip_glyph(glyph: &[u8]) -> Option<Vec<u8>> {
+    const WE_HAVE_INSTRUCTIONS: u16 = 0x0100;
+
+    if glyph.is_empty() {
+        return Some(vec![]);
+    }
+
+    let contours = read_u16(glyph, 0)? as i16;
+    let mut stripped = vec![];
+    if contours >= 0 {
+        // A simple glyph: Its instructions follow the contour end points.
+        let at = 10 + 2 * contours as usize;
+        let len = usize::from(read_u16(glyph, at)?);
+        stripped.extend(glyph.get(..at)?);
+        stripped.extend([0, 0]);
+        stripped.extend(glyph.get(at + 2 + len..)?);
+    } else {
+        // A composite glyph: Its instructions follow the last component.
+        let mut offset = 10;
+        stripped.extend(glyph.get(..offset)?);
+        loop {
+            let flags = read_u16(glyph, offset)?;
+            let len = component_len(flags);
+            stripped.extend((flags & !WE_HAVE_INSTRUCTIONS).to_be_bytes());
+            stripped.extend(glyph.get(offset + 2..offset + len)?);
+            offset += len;
+
+            if flags & MORE_COMPONENTS == 0 {
+                break;
+            }
+        }
+    }
+
+    Some(stripped)
+}
+
+/// Compute the checksum of a table or font.
+fn checksum(data: &[u8]) -> u32 {
+    data.chunks(4).fold(0u32, |sum, chunk| {
+        let mut word = [0; 4];
+        word[..chunk.len()].copy_from_slice(chunk);
+        sum.wrapping_add(u32::from_be_bytes(word))
+    })
+}
+
+/// Round a length up to a multiple of four.
+fn pad(len: usize) -> usize {
+    (len + 3) & !3
+}
+
+/// Read a big-endian `u16` at the given offset.
+fn read_u16(data: &[u8], offset: usize) -> Option<u16> {
+    Some(u16::from_be_bytes(data.get(offset..offset + 2)?.try_into().ok()?))
+}
+
+/// Read a big-endian `u32` at the given offset.
+fn read_u32(data: &[u8], offset: usize) -> Option<u32> {
+    Some(u32::from_be_bytes(data.get(offset..offset + 4)?.try_into().ok()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A version 2.0 `post` table for three glyphs with custom names.
+    fn post() -> Vec<u8> {
+        let mut post = vec![0, 2, 0, 0];
+        post.resize(32, 0);
+        post.extend([0, 3, 0, 0, 1, 2, 1, 3]);
+        post.extend(b"\x03one\x03two");
+        post
+    }
+
+    #[test]
+    fn test_subset_post_without_names() {
+        let subset = subset_post(&post(), None).unwrap();
+        assert_eq!(subset.len(), 32);
+        assert_eq!(read_u32(&subset, 0), Some(0x00030000));
+    }
+
+    #[test]
+    fn test_subset_post_with_names() {
+        let subset = subset_post(&post(), Some(&[2])).unwrap();
+        assert_eq!(&subset[32..40], [0, 3, 0, 0, 0, 0, 1, 2]);
+        assert_eq!(&subset[40..], b"\x03two");
+    }
+
+    #[test]
+    fn test_glyph_closure() {
+        // Glyph 2 is a composite of glyph 3, which is a composite of glyph 1.
+        let simple = [0, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0];
+        let composite = |id: u8| [0xFF, 0xFF, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, id, 0, 0];
+        let mut glyf = vec![];
+        glyf.extend(simple);
+        glyf.extend(composite(3));
+        glyf.extend(composite(1));
+        glyf.extend(simple);
+
+        let mut tables = Tables { version: 0x00010000, tables: vec![] };
+        tables.set(Tag::from_bytes(b"glyf"), glyf);
+        tables.set(Tag::from_bytes(b"loca"), vec![0, 0, 0, 0, 0, 6, 0, 14, 0, 22, 0, 28]);
+        tables.set(Tag::from_bytes(b"head"), vec![0; 54]);
+
+        assert_eq!(glyph_closure(&tables, &[2]).unwrap(), [1, 2, 3]);
+        assert_eq!(glyph_closure(&tables, &[4, 1]).unwrap(), [1, 4]);
+    }
+
+    #[test]
+    fn test_check_glyph_offsets() {
+        let mut tables = Tables { version: 0x00010000, tables: vec![] };
+        tables.set(Tag::from_bytes(b"glyf"), vec![0; 12]);
+        tables.set(Tag::from_bytes(b"head"), vec![0; 54]);
+        tables.set(Tag::from_bytes(b"maxp"), vec![0, 0, 0x50, 0, 0, 2]);
+
+        tables.set(Tag::from_bytes(b"loca"), vec![0, 0, 0, 4, 0, 6]);
+        assert!(check_glyph_offsets(&tables));
+
+        // An offset beyond the end of the `glyf` table.
+        tables.set(Tag::from_bytes(b"loca"), vec![0, 0, 0, 4, 0, 7]);
+        assert!(!check_glyph_offsets(&tables));
+
+        // Descending offsets.
+        tables.set(Tag::from_bytes(b"loca"), vec![0, 0, 0, 4, 0, 2]);
+        assert!(!check_glyph_offsets(&tables));
+
+        // Too few offsets for the number of glyphs.
+        tables.set(Tag::from_bytes(b"loca"), vec![0, 0, 0, 4]);
+        assert!(!check_glyph_offsets(&tables));
+    }
+
+    #[test]
+    fn test_strip_glyph() {
+        // One contour with one point and two bytes of instructions.
+        let simple = [0, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 2, 0xB0, 0x01, 1, 7];
+        assert_eq!(
+            strip_glyph(&simple).unwrap(),
+            [0, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 7]
+        );
+
+        // One component with byte arguments and instructions.
+        let composite =
+            [0xFF, 0xFF, 0, 0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 3, 5, 6, 0, 1, 0xB0];
+        assert_eq!(
+            strip_glyph(&composite).unwrap(),
+            [0xFF, 0xFF, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 3, 5, 6],
+        );
+    }
+
+    #[test]
+    fn test_tables_roundtrip() {
+        let mut tables = Tables { version: 0x00010000, tables: vec![] };
+        tables.set(Tag::from_bytes(b"post"), post());
+        tables.set(Tag::from_bytes(b"head"), vec![1; 54]);
+        let font = tables.write();
+        let parsed = Tables::parse(&font, 0).unwrap();
+        assert_eq!(parsed.get(Tag::from_bytes(b"post")), Some(post().as_slice()));
+        assert_eq!(checksum(&font), 0xB1B0AFBA);
+    }
+
+    #[test]
+    fn test_tables_of_collection() {
+        let face = |post: Vec<u8>| {
+            let mut tables = Tables { version: 0x00010000, tables: vec![] };
+            tables.set(Tag::from_bytes(b"post"), post);
+            tables.write()
+        };
+
+        // Combine two faces into a collection, whose table offsets are
+        // relative to the start of the collection.
+        let faces = [face(vec![1; 32]), face(vec![2; 32])];
+        let mut collection = b"ttcf\0\x01\0\0\0\0\0\x02".to_vec();
+        let mut start = 20;
+        for face in &faces {
+            collection.extend((start as u32).to_be_bytes());
+            start += face.len();
+        }
+        for face in &faces {
+            let base = collection.len() as u32;
+            let mut face = face.clone();
+            let offset = 12 + 8;
+            let moved = read_u32(&face, offset).unwrap() + base;
+            face[offset..offset + 4].copy_from_slice(&moved.to_be_bytes());
+            collection.extend(face);
+        }
+
+        let post = Tag::from_bytes(b"post");
+        let second = Tables::parse(&collection, 1).unwrap();
+        assert_eq!(second.get(post), Some([2; 32].as_slice()));
+        assert_eq!(Tables::parse(&second.write(), 0).unwrap().get(post).unwrap()[0], 2);
+        assert!(Tables::parse(&collection, 2).is_none());
+        assert!(Tables::parse(&faces[0], 1).is_none());
+    }
+
+    #[test]
+    fn test_minimize_name() {
+        // The copyright, family and designer names with the strings "a", "b"
+        // and "c" for English on Windows.
+        let mut name = vec![0, 0, 0, 3, 0, 42];
+        for (id, offset) in [(0_u16, 0_u16), (1, 1), (9, 2)] {
+            name.extend([0, 3, 0, 1, 4, 9]);
+            name.extend(id.to_be_bytes());
+            name.extend(1_u16.to_be_bytes());
+            name.extend(offset.to_be_bytes());
+        }
+        name.extend(b"abc");
+
+        let minimized = minimize_name(&name).unwrap();
+        assert_eq!(&minimized[..6], [0, 0, 0, 1, 0, 18]);
+        assert_eq!(&minimized[6..18], [0, 3, 0, 1, 4, 9, 0, 1, 0, 1, 0, 0]);
+        assert_eq!(&minimized[18..], b"b");
+    }
+}
@@ -64,6 +64,10 @@ fn render_frame(
                 Meta::Link(_) => {}
                 Meta::Elem(_) => {}
                 Meta::PageNumbering(_) => {}
+                Meta::PageLabel(_) => {}
+                Meta::PageTrim(_) => {}
+                Meta::Role(_) => {}
+                Meta::Field(_) => {}
                 Meta::Hide => {}
             },
         }
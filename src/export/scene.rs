@@ -0,0 +1,272 @@
+//! Describing laid-out pages as a serializable scene graph.
+
+use ecow::{eco_format, EcoString};
+use serde::Serialize;
+
+use crate::doc::{Destination, Document, Frame, FrameItem, GroupItem, Meta, TextItem};
+use crate::geom::{Geometry, Paint, PathItem, Point, Shape, Size};
+use crate::image::{ImageFormat, RasterFormat, VectorFormat};
+
+/// The pages of a document as a tree of plain data.
+///
+/// All lengths are in points and all positions are relative to the
+/// top-left corner of the enclosing page or group. Colors are hex strings like
+/// `#rrggbbaa`. The scene derives `serde::Serialize`, so that external
+/// renderers and test harnesses can consume it as JSON, CBOR or any other
+/// format.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Scene {
+    /// The pages of the document.
+    pub pages: Vec<ScenePage>,
+}
+
+/// A page in a scene.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ScenePage {
+    /// The width of the page.
+    pub width: f64,
+    /// The height of the page.
+    pub height: f64,
+    /// The items on the page, from bottom to top.
+    pub items: Vec<SceneItem>,
+}
+
+/// An item in a scene.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "kind", rename_all = "kebab-case")]
+pub enum SceneItem {
+    /// A group of items with a transformation.
+    Group {
+        x: f64,
+        y: f64,
+        width: f64,
+        height: f64,
+        /// The affine transformation as `[sx, ky, kx, sy, tx, ty]`.
+        transform: [f64; 6],
+        /// Whether the group clips its items to its bounds.
+        clips: bool,
+        items: Vec<SceneItem>,
+    },
+    /// A run of shaped text. The position is that of the baseline origin.
+    Text {
+        x: f64,
+        y: f64,
+        /// The family name of the font.
+        font: EcoString,
+        size: f64,
+        fill: EcoString,
+        text: EcoString,
+        glyphs: Vec<SceneGlyph>,
+    },
+    /// A geometric shape.
+    Shape {
+        x: f64,
+        y: f64,
+        /// The outline of the shape as SVG path data.
+        path: EcoString,
+        fill: Option<EcoString>,
+        stroke: Option<EcoString>,
+        stroke_width: Option<f64>,
+    },
+    /// An image.
+    Image { x: f64, y: f64, width: f64, height: f64, format: EcoString },
+    /// A clickable link.
+    Link { x: f64, y: f64, width: f64, height: f64, url: Option<EcoString> },
+}
+
+/// A glyph in a text item of a scene.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SceneGlyph {
+    /// The glyph's index in the font.
+    pub id: u16,
+    /// The advance width of the glyph.
+    pub x_advance: f64,
+    /// The horizontal offset of the glyph.
+    pub x_offset: f64,
+    /// The byte range of the glyph's cluster in the item's text.
+    pub range: [usize; 2],
+}
+
+/// Describe the pages of a document as a scene.
+pub fn scene(document: &Document) -> Scene {
+    let pages = document
+        .pages
+        .iter()
+        .map(|frame| ScenePage {
+            width: frame.width().to_pt(),
+            height: frame.height().to_pt(),
+            items: items(frame),
+        })
+        .collect();
+    Scene { pages }
+}
+
+/// Convert the items of a frame.
+fn items(frame: &Frame) -> Vec<SceneItem> {
+    frame.items().filter_map(|(pos, item)| convert(*pos, item)).collect()
+}
+
+/// Convert a single frame item.
+fn convert(pos: Point, item: &FrameItem) -> Option<SceneItem> {
+    let (x, y) = (pos.x.to_pt(), pos.y.to_pt());
+    Some(match item {
+        FrameItem::Group(group) => convert_group(x, y, group),
+        FrameItem::Text(text) => convert_text(x, y, text),
+        FrameItem::Shape(shape, _) => convert_shape(x, y, shape),
+        FrameItem::Image(image, size, _) => SceneItem::Image {
+            x,
+            y,
+            width: size.x.to_pt(),
+            height: size.y.to_pt(),
+            format: format_name(image.format()).into(),
+        },
+        FrameItem::Meta(Meta::Link(dest), size) => SceneItem::Link {
+            x,
+            y,
+            width: size.x.to_pt(),
+            height: size.y.to_pt(),
+            url: match dest {
+                Destination::Url(url) => Some(url.clone()),
+                _ => None,
+            },
+        },
+        FrameItem::Meta(..) => return None,
+    })
+}
+
+/// Convert a group item.
+fn convert_group(x: f64, y: f64, group: &GroupItem) -> SceneItem {
+    let ts = group.transform;
+    SceneItem::Group {
+        x,
+        y,
+        width: group.frame.width().to_pt(),
+        height: group.frame.height().to_pt(),
+        transform: [
+            ts.sx.get(),
+            ts.ky.get(),
+            ts.kx.get(),
+            ts.sy.get(),
+            ts.tx.to_pt(),
+            ts.ty.to_pt(),
+        ],
+        clips: group.clips,
+        items: items(&group.frame),
+    }
+}
+
+/// Convert a text item.
+fn convert_text(x: f64, y: f64, text: &TextItem) -> SceneItem {
+    SceneItem::Text {
+        x,
+        y,
+        font: text.font.info().family.as_str().into(),
+        size: text.size.to_pt(),
+        fill: color(&text.fill),
+        text: text.text.clone(),
+        glyphs: text
+            .glyphs
+            .iter()
+            .map(|glyph| SceneGlyph {
+                id: glyph.id,
+                x_advance: glyph.x_advance.at(text.size).to_pt(),
+                x_offset: glyph.x_offset.at(text.size).to_pt(),
+                range: [glyph.range().start, glyph.range().end],
+            })
+            .collect(),
+    }
+}
+
+/// Convert a shape.
+fn convert_shape(x: f64, y: f64, shape: &Shape) -> SceneItem {
+    let mut path = EcoString::new();
+    let mut push = |op: &str, points: &[Point]| {
+        if !path.is_empty() {
+            path.push(' ');
+        }
+        path.push_str(op);
+        for p in points {
+            path.push_str(&eco_format!(" {} {}", p.x.to_pt(), p.y.to_pt()));
+        }
+    };
+
+    match &shape.geometry {
+        Geometry::Line(to) => {
+            push("M", &[Point::zero()]);
+            push("L", &[*to]);
+        }
+        Geometry::Rect(Size { x: w, y: h }) => {
+            push("M", &[Point::zero()]);
+            push("L", &[Point::with_x(*w)]);
+            push("L", &[Point::new(*w, *h)]);
+            push("L", &[Point::with_y(*h)]);
+            push("Z", &[]);
+        }
+        Geometry::Path(p) => {
+            for item in &p.0 {
+                match item {
+                    PathItem::MoveTo(a) => push("M", &[*a]),
+                    PathItem::LineTo(a) => push("L", &[*a]),
+                    PathItem::CubicTo(a, b, c) => push("C", &[*a, *b, *c]),
+                    PathItem::ClosePath => push("Z", &[]),
+                }
+            }
+        }
+    }
+
+    SceneItem::Shape {
+        x,
+        y,
+        path,
+        fill: shape.fill.as_ref().map(color),
+        stroke: shape.stroke.as_ref().map(|stroke| color(&stroke.paint)),
+        stroke_width: shape.stroke.as_ref().map(|stroke| stroke.thickness.to_pt()),
+    }
+}
+
+/// Format a paint as a hex color.
+fn color(paint: &Paint) -> EcoString {
+    let Paint::Solid(color) = paint;
+    let c = color.to_rgba();
+    eco_format!("#{:02x}{:02x}{:02x}{:02x}", c.r, c.g, c.b, c.a)
+}
+
+/// The name of an image format.
+fn format_name(format: ImageFormat) -> &'static str {
+    match format {
+        ImageFormat::Raster(RasterFormat::Png) => "png",
+        ImageFormat::Raster(RasterFormat::Jpg) => "jpg",
+        ImageFormat::Raster(RasterFormat::Gif) => "gif",
+        ImageFormat::Vector(VectorFormat::Svg) => "svg",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geom::{Abs, Stroke};
+    use crate::syntax::Span;
+
+    #[test]
+    fn test_scene_shape() {
+        let mut frame = Frame::new(Size::new(Abs::pt(20.0), Abs::pt(10.0)));
+        let shape =
+            Geometry::Line(Point::with_x(Abs::pt(5.0))).stroked(Stroke::default());
+        frame
+            .push(Point::with_y(Abs::pt(2.0)), FrameItem::Shape(shape, Span::detached()));
+
+        let scene = scene(&Document { pages: vec![frame], ..Document::default() });
+        assert_eq!(scene.pages[0].width, 20.0);
+        assert_eq!(
+            scene.pages[0].items,
+            [SceneItem::Shape {
+                x: 0.0,
+                y: 2.0,
+                path: "M 0 0 L 5 0".into(),
+                fill: None,
+                stroke: Some("#000000ff".into()),
+                stroke_width: Some(1.0),
+            }],
+        );
+    }
+}
@@ -0,0 +1,220 @@
+//! Extracting positioned text runs.
+
+use std::ops::Range;
+
+use ecow::EcoString;
+
+use crate::doc::{Frame, FrameItem, TextItem};
+use crate::geom::{Abs, Point, Size, Transform};
+use crate::syntax::Span;
+
+/// A run of text on a page together with its bounding box.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextRun {
+    /// The run's plain text.
+    pub text: EcoString,
+    /// The top-left corner of the run's bounding box, relative to the page.
+    pub pos: Point,
+    /// The size of the run's bounding box.
+    pub size: Size,
+    /// The run's glyph clusters in the order they were laid out.
+    pub clusters: Vec<TextCluster>,
+}
+
+/// A cluster of glyphs that can't be split, for example a ligature or a base
+/// character with its combining marks.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextCluster {
+    /// The byte range of the cluster in its run's text.
+    pub range: Range<usize>,
+    /// The top-left corner of the cluster's bounding box, relative to the
+    /// page.
+    pub pos: Point,
+    /// The size of the cluster's bounding box.
+    pub size: Size,
+    /// The source code location of the cluster's text and the byte offset
+    /// within it. The span is detached for generated text.
+    pub span: (Span, u16),
+}
+
+/// Extract the visible text runs of a page frame.
+///
+/// The runs are returned in reading order, that is, in the order in which
+/// they were laid out. Hidden text is not included. The bounding boxes span
+/// from the font's ascender to its descender and are axis-aligned on the page,
+/// even if the text itself is rotated or skewed.
+///
+/// This is useful for building search indices or for highlighting text on
+/// top of a rendered page. The runs' clusters additionally map positions on
+/// the page back to the source code, for example to highlight search results
+/// within a run or to jump from a position in the output to the source.
+pub fn text_runs(frame: &Frame) -> Vec<TextRun> {
+    let mut runs = vec![];
+    extract_frame(&mut runs, Transform::identity(), frame);
+    runs
+}
+
+/// Extract the text runs of a frame.
+fn extract_frame(runs: &mut Vec<TextRun>, ts: Transform, frame: &Frame) {
+    for (pos, item) in frame.items() {
+        let ts = ts.pre_concat(Transform::translate(pos.x, pos.y));
+        match item {
+            FrameItem::Group(group) => {
+                extract_frame(runs, ts.pre_concat(group.transform), &group.frame);
+            }
+            FrameItem::Text(text) => runs.push(extract_text(ts, text)),
+            FrameItem::Shape(..) | FrameItem::Image(..) | FrameItem::Meta(..) => {}
+        }
+    }
+}
+
+/// Extract a text item at the baseline origin of the given transform.
+fn extract_text(ts: Transform, text: &TextItem) -> TextRun {
+    let mut clusters: Vec<TextCluster> = vec![];
+    let mut x = Abs::zero();
+    let mut start = Abs::zero();
+    for glyph in &text.glyphs {
+        let width = glyph.x_advance.at(text.size);
+        match clusters.last_mut() {
+            Some(cluster) if cluster.range == glyph.range() => {
+                (cluster.pos, cluster.size) = bounds(ts, text, start, x + width);
+            }
+            _ => {
+                start = x;
+                let (pos, size) = bounds(ts, text, x, x + width);
+                clusters.push(TextCluster {
+                    range: glyph.range(),
+                    pos,
+                    size,
+                    span: glyph.span,
+                });
+            }
+        }
+        x += width;
+    }
+
+    let (pos, size) = bounds(ts, text, Abs::zero(), text.width());
+    TextRun { text: text.text.clone(), pos, size, clusters }
+}
+
+/// Compute the page-level bounding box of the horizontal slice `from..to` of
+/// a text item at the baseline origin of the given transform.
+fn bounds(ts: Transform, text: &TextItem, from: Abs, to: Abs) -> (Point, Size) {
+    let metrics = text.font.metrics();
+    let top = -metrics.ascender.at(text.size);
+    let bottom = -metrics.descender.at(text.size);
+
+    let mut min = Point::splat(Abs::inf());
+    let mut max = Point::splat(-Abs::inf());
+    for corner in [
+        Point::new(from, top),
+        Point::new(to, top),
+        Point::new(from, bottom),
+        Point::new(to, bottom),
+    ] {
+        let p = corner.transform(ts);
+        min = min.min(p);
+        max = max.max(p);
+    }
+
+    (min, (max - min).to_size())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::doc::{Glyph, GroupItem, Lang};
+    use crate::font::Font;
+    use crate::geom::{Angle, Color, Em};
+    use crate::util::Bytes;
+
+    fn font() -> Font {
+        let data = include_bytes!("../../assets/fonts/IBMPlexSans-Regular.ttf");
+        Font::new(Bytes::from_static(data), 0).unwrap()
+    }
+
+    /// Create a 10pt text item whose glyphs have the given characters, byte
+    /// ranges and span offsets and are all half an em wide.
+    fn item(font: &Font, text: &str, glyphs: &[(char, Range<u16>, u16)]) -> TextItem {
+        let glyphs = glyphs
+            .iter()
+            .map(|(c, range, offset)| Glyph {
+                id: font.ttf().glyph_index(*c).unwrap().0,
+                x_advance: Em::new(0.5),
+                x_offset: Em::zero(),
+                range: range.clone(),
+                span: (Span::detached(), *offset),
+            })
+            .collect();
+
+        TextItem {
+            font: font.clone(),
+            size: Abs::pt(10.0),
+            fill: Color::BLACK.into(),
+            lang: Lang::ENGLISH,
+            text: text.into(),
+            glyphs,
+        }
+    }
+
+    #[test]
+    fn test_text_runs_in_groups() {
+        let font = font();
+        let glyphs = [('a', 0..1, 0), ('b', 1..2, 1)];
+        let mut inner = Frame::new(Size::zero());
+        inner.push(Point::zero(), FrameItem::Text(item(&font, "ab", &glyphs)));
+        let mut group = GroupItem::new(inner);
+        group.transform = Transform::rotate(Angle::deg(90.0));
+
+        let mut frame = Frame::new(Size::splat(Abs::pt(100.0)));
+        let pos = Point::new(Abs::pt(10.0), Abs::pt(20.0));
+        frame.push(pos, FrameItem::Text(item(&font, "ab", &glyphs)));
+        frame.push(Point::splat(Abs::pt(50.0)), FrameItem::Group(group));
+
+        let runs = text_runs(&frame);
+        assert_eq!(runs.len(), 2);
+
+        // The bounding box spans from the ascender to the descender.
+        let metrics = font.metrics();
+        let ascent = metrics.ascender.at(Abs::pt(10.0));
+        let descent = -metrics.descender.at(Abs::pt(10.0));
+        assert!(runs[0].pos.x.approx_eq(Abs::pt(10.0)));
+        assert!(runs[0].pos.y.approx_eq(Abs::pt(20.0) - ascent));
+        assert!(runs[0].size.x.approx_eq(Abs::pt(10.0)));
+        assert!(runs[0].size.y.approx_eq(ascent + descent));
+
+        // The rotated run's box is axis-aligned on the page.
+        assert!(runs[1].pos.x.approx_eq(Abs::pt(50.0) - descent));
+        assert!(runs[1].pos.y.approx_eq(Abs::pt(50.0)));
+        assert!(runs[1].size.x.approx_eq(runs[0].size.y));
+        assert!(runs[1].size.y.approx_eq(runs[0].size.x));
+    }
+
+    #[test]
+    fn test_text_run_clusters() {
+        // The first two glyphs share a cluster, like a base with its mark.
+        let font = font();
+        let glyphs = [('a', 0..2, 0), ('b', 0..2, 0), ('c', 2..3, 2)];
+        let mut frame = Frame::new(Size::splat(Abs::pt(100.0)));
+        let pos = Point::new(Abs::pt(10.0), Abs::pt(20.0));
+        frame.push(pos, FrameItem::Text(item(&font, "abc", &glyphs)));
+
+        let runs = text_runs(&frame);
+        assert_eq!(runs.len(), 1);
+
+        let clusters = &runs[0].clusters;
+        assert_eq!(clusters.len(), 2);
+        assert_eq!(clusters[0].range, 0..2);
+        assert_eq!(clusters[1].range, 2..3);
+        assert_eq!(clusters[1].span, (Span::detached(), 2));
+
+        assert!(clusters[0].pos.x.approx_eq(Abs::pt(10.0)));
+        assert!(clusters[0].size.x.approx_eq(Abs::pt(10.0)));
+        assert!(clusters[1].pos.x.approx_eq(Abs::pt(20.0)));
+        assert!(clusters[1].size.x.approx_eq(Abs::pt(5.0)));
+
+        let top = Abs::pt(20.0) - font.metrics().ascender.at(Abs::pt(10.0));
+        assert!(clusters[1].pos.y.approx_eq(top));
+        assert_eq!(clusters[0].size.y, runs[0].size.y);
+    }
+}
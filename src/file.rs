@@ -2,7 +2,7 @@
 
 use std::collections::HashMap;
 use std::fmt::{self, Debug, Display, Formatter};
-use std::path::{Path, PathBuf};
+use std::path::{Component, Path, PathBuf};
 use std::str::FromStr;
 use std::sync::RwLock;
 
@@ -261,6 +261,8 @@ impl<'de> Deserialize<'de> for Version {
 pub struct PackageManifest {
     /// Details about the package itself.
     pub package: PackageInfo,
+    /// Details about the template the package provides, if any.
+    pub template: Option<TemplateInfo>,
 }
 
 impl PackageManifest {
@@ -285,10 +287,31 @@ impl PackageManifest {
             );
         }
 
+        validate_path(&self.package.entrypoint, "entrypoint")?;
+        if let Some(template) = &self.template {
+            validate_path(&template.path, "template path")?;
+            validate_path(&template.entrypoint, "template entrypoint")?;
+        }
+
         Ok(())
     }
 }
 
+/// Ensure that a path from a package manifest is relative and stays within
+/// the directory it is resolved in.
+fn validate_path(path: &str, key: &str) -> StrResult<()> {
+    let path = Path::new(path);
+    if path.has_root()
+        || path
+            .components()
+            .any(|c| !matches!(c, Component::Normal(_) | Component::CurDir))
+    {
+        bail!("package manifest contains invalid {key} `{}`", path.display());
+    }
+
+    Ok(())
+}
+
 /// The `package` key in the manifest.
 #[derive(Debug, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
 pub struct PackageInfo {
@@ -299,3 +322,46 @@ pub struct PackageInfo {
     /// The path of the entrypoint into the package.
     pub entrypoint: EcoString,
 }
+
+/// The `template` key in the manifest.
+///
+/// A template package provides a function that receives the document's body
+/// and returns it styled, applied with `{#show: template.with(..)}`. Next to
+/// it, the package contains a directory with a starter project that imports
+/// the package and is copied when a new project is created from the template.
+#[derive(Debug, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct TemplateInfo {
+    /// The path of the starter project's directory within the package.
+    pub path: EcoString,
+    /// The path of the file to compile within the starter project.
+    pub entrypoint: EcoString,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_manifest_paths() {
+        let spec: PackageSpec = "@preview/example:0.1.0".parse().unwrap();
+        let validate = |entrypoint: &str, template: &str| {
+            let manifest = format!(
+                "[package]\n\
+                 name = \"example\"\n\
+                 version = \"0.1.0\"\n\
+                 entrypoint = \"{entrypoint}\"\n\
+                 [template]\n\
+                 path = \"{template}\"\n\
+                 entrypoint = \"main.typ\"\n"
+            );
+            PackageManifest::parse(manifest.as_bytes()).unwrap().validate(&spec)
+        };
+
+        assert!(validate("lib.typ", "template").is_ok());
+        assert!(validate("./src/lib.typ", "./template").is_ok());
+        assert!(validate("/lib.typ", "template").is_err());
+        assert!(validate("../lib.typ", "template").is_err());
+        assert!(validate("lib.typ", "/home/user").is_err());
+        assert!(validate("lib.typ", "template/../../..").is_err());
+    }
+}
@@ -0,0 +1,49 @@
+//! Entry points for fuzzing.
+//!
+//! These are not part of the stable API. The targets in the `fuzz` directory
+//! feed them arbitrary bytes and they panic if they find a bug.
+
+use crate::export::{subset_font, PdfOptions};
+use crate::font::Font;
+use crate::syntax::{parse, Source, Span};
+
+/// Parse arbitrary text, checking that the syntax tree covers all of it and
+/// that incremental reparsing agrees with parsing from scratch.
+pub fn fuzz_parse(data: &[u8]) {
+    let Ok(text) = std::str::from_utf8(data) else { return };
+    let root = parse(text);
+    assert_eq!(root.len(), text.len(), "syntax tree must cover the whole text");
+
+    // Delete the middle third of the text incrementally.
+    let boundary = |i: usize| (i..=text.len()).find(|&i| text.is_char_boundary(i));
+    let (Some(start), Some(end)) =
+        (boundary(text.len() / 3), boundary(2 * text.len() / 3))
+    else {
+        return;
+    };
+
+    let mut source = Source::detached(text);
+    source.edit(start..end, "");
+    let mut found = source.root().clone();
+    let mut expected = parse(source.text());
+    found.synthesize(Span::detached());
+    expected.synthesize(Span::detached());
+    assert_eq!(found, expected, "incremental reparse must match full parse");
+}
+
+/// Subset arbitrary font data to every other glyph, with all post-processing
+/// of the subset enabled.
+pub fn fuzz_subset(data: &[u8]) {
+    let Some(font) = Font::new(data.into(), 0) else { return };
+    let glyphs: Vec<u16> = (0..font.ttf().number_of_glyphs()).step_by(2).collect();
+    let options = PdfOptions {
+        glyph_names: true,
+        strip_hinting: true,
+        minimize_names: true,
+        embed_restricted_fonts: true,
+    };
+    let _ = subset_font(&font, &glyphs, options);
+
+    // Don't let the memoization cache grow across runs.
+    comemo::evict(0);
+}
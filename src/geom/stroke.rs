@@ -186,7 +186,7 @@ impl Resolve for PartialStroke {
     }
 }
 
-impl Fold for PartialStroke<Abs> {
+impl<T> Fold for PartialStroke<T> {
     type Output = Self;
 
     fn fold(self, outer: Self::Output) -> Self::Output {
@@ -68,6 +68,25 @@ impl Transform {
     pub fn post_concat(self, next: Self) -> Self {
         next.pre_concat(self)
     }
+
+    /// The inverse transformation, if there is one.
+    pub fn invert(self) -> Option<Self> {
+        let (sx, ky, kx, sy) =
+            (self.sx.get(), self.ky.get(), self.kx.get(), self.sy.get());
+        let det = sx * sy - kx * ky;
+        if det.abs() < 1e-12 {
+            return None;
+        }
+
+        Some(Self {
+            sx: Ratio::new(sy / det),
+            ky: Ratio::new(-ky / det),
+            kx: Ratio::new(-kx / det),
+            sy: Ratio::new(sx / det),
+            tx: (self.ty * kx - self.tx * sy) / det,
+            ty: (self.tx * ky - self.ty * sx) / det,
+        })
+    }
 }
 
 impl Default for Transform {
@@ -75,3 +94,19 @@ impl Default for Transform {
         Self::identity()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_transform_invert() {
+        let ts = Transform::rotate(Angle::deg(30.0))
+            .pre_concat(Transform::scale(Ratio::new(2.0), Ratio::new(0.5)))
+            .post_concat(Transform::translate(Abs::pt(10.0), Abs::pt(-4.0)));
+        let p = Point::new(Abs::pt(3.0), Abs::pt(7.0));
+        let q = p.transform(ts).transform(ts.invert().unwrap());
+        assert!(p.x.approx_eq(q.x) && p.y.approx_eq(q.y));
+        assert_eq!(Transform::scale(Ratio::zero(), Ratio::one()).invert(), None);
+    }
+}
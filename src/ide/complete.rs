@@ -657,11 +657,13 @@ fn param_completions(
         }
 
         if param.named {
+            let docs = plain_docs_sentence(param.docs);
+            let types = param_types(&param.cast);
             ctx.completions.push(Completion {
                 kind: CompletionKind::Param,
                 label: param.name.into(),
                 apply: Some(eco_format!("{}: ${{}}", param.name)),
-                detail: Some(plain_docs_sentence(param.docs)),
+                detail: Some(eco_format!("{docs} (accepts {types})")),
             });
         }
 
@@ -675,6 +677,31 @@ fn param_completions(
     }
 }
 
+/// Describe the types a parameter accepts, e.g. "length or auto".
+fn param_types(cast: &CastInfo) -> String {
+    fn accumulate(info: &CastInfo, types: &mut Vec<&'static str>) {
+        let ty = match info {
+            CastInfo::Any => "anything",
+            CastInfo::Value(value, _) => value.type_name(),
+            CastInfo::Type(ty) => *ty,
+            CastInfo::Union(options) => {
+                for option in options {
+                    accumulate(option, types);
+                }
+                return;
+            }
+        };
+
+        if !types.contains(&ty) {
+            types.push(ty);
+        }
+    }
+
+    let mut types = vec![];
+    accumulate(cast, &mut types);
+    separated_list(&types, "or")
+}
+
 /// Add completions for the values of a named function parameter.
 fn named_param_value_completions(
     ctx: &mut CompletionContext,
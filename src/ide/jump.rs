@@ -5,7 +5,8 @@ use ecow::EcoString;
 use crate::doc::{Destination, Frame, FrameItem, Meta, Position};
 use crate::file::FileId;
 use crate::geom::{Geometry, Point, Size};
-use crate::model::Introspector;
+use crate::model::{Introspector, Label};
+use crate::syntax::ast::{self, AstNode};
 use crate::syntax::{LinkedNode, Source, Span, SyntaxKind};
 use crate::World;
 
@@ -58,10 +59,9 @@ pub fn jump_from_click(
     for (mut pos, item) in frame.items().rev() {
         match item {
             FrameItem::Group(group) => {
-                // TODO: Handle transformation.
-                if let Some(span) =
-                    jump_from_click(world, frames, &group.frame, click - pos)
-                {
+                let Some(inverse) = group.transform.invert() else { continue };
+                let click = (click - pos).transform(inverse);
+                if let Some(span) = jump_from_click(world, frames, &group.frame, click) {
                     return Some(span);
                 }
             }
@@ -140,13 +140,61 @@ pub fn jump_from_cursor(
     None
 }
 
+/// Find the definition of the reference, label or identifier at the cursor.
+///
+/// References and labels jump to the labelled element in the document.
+/// Identifiers jump to the closest preceding `let` binding that defines them in
+/// the same source file.
+pub fn jump_to_definition(
+    frames: &[Frame],
+    source: &Source,
+    cursor: usize,
+) -> Option<Jump> {
+    let leaf = LinkedNode::new(source.root()).leaf_at(cursor)?;
+    match leaf.kind() {
+        SyntaxKind::RefMarker | SyntaxKind::Label => {
+            let text = leaf.text();
+            let name = match leaf.kind() {
+                SyntaxKind::RefMarker => &text[1..],
+                _ => &text[1..text.len() - 1],
+            };
+
+            let label = Label(name.into());
+            let introspector = Introspector::new(frames);
+            let elem = introspector.all().find(|elem| elem.label() == Some(&label))?;
+            Some(Jump::Position(introspector.position(elem.location()?)))
+        }
+        SyntaxKind::Ident | SyntaxKind::MathIdent => {
+            let name = leaf.text();
+            let mut ancestor = Some(leaf.clone());
+            while let Some(node) = &ancestor {
+                let mut sibling = Some(node.clone());
+                while let Some(node) = &sibling {
+                    if let Some(binding) = node.cast::<ast::LetBinding>() {
+                        let idents = binding.kind().idents();
+                        if let Some(ident) =
+                            idents.iter().find(|ident| ident.get() == name)
+                        {
+                            let offset = source.find(ident.span())?.offset();
+                            return Some(Jump::Source(source.id(), offset));
+                        }
+                    }
+                    sibling = node.prev_sibling();
+                }
+                ancestor = node.parent().cloned();
+            }
+            None
+        }
+        _ => None,
+    }
+}
+
 /// Find the position of a span in a frame.
 fn find_in_frame(frame: &Frame, span: Span) -> Option<Point> {
     for (mut pos, item) in frame.items() {
         if let FrameItem::Group(group) = item {
-            // TODO: Handle transformation.
             if let Some(point) = find_in_frame(&group.frame, span) {
-                return Some(point + pos);
+                return Some(point.transform(group.transform) + pos);
             }
         }
 
@@ -5,12 +5,14 @@ mod complete;
 mod highlight;
 mod jump;
 mod tooltip;
+mod words;
 
 pub use self::analyze::analyze_labels;
 pub use self::complete::{autocomplete, Completion, CompletionKind};
 pub use self::highlight::{highlight, highlight_html, Tag};
-pub use self::jump::{jump_from_click, jump_from_cursor, Jump};
+pub use self::jump::{jump_from_click, jump_from_cursor, jump_to_definition, Jump};
 pub use self::tooltip::{tooltip, Tooltip};
+pub use self::words::{words, Word};
 
 use std::fmt::Write;
 
@@ -0,0 +1,99 @@
+use std::ops::Range;
+
+use ecow::EcoString;
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::doc::{Frame, FrameItem, Glyph, Lang, TextItem};
+use crate::file::FileId;
+use crate::syntax::SyntaxKind;
+use crate::World;
+
+/// A word in a finished document.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Word {
+    /// The word as it appears in the document, that is, after smart quotes,
+    /// escapes and shorthands were resolved.
+    pub text: EcoString,
+    /// The natural language the word is set in.
+    pub lang: Lang,
+    /// The file and byte range the word stems from, if it stems from source
+    /// code at all.
+    pub source: Option<(FileId, Range<usize>)>,
+}
+
+/// Extract the words of a document together with their languages and source
+/// locations.
+///
+/// This is meant for spell checkers: Words are split according to the Unicode
+/// word boundary rules, in the order in which they appear in the frames. A
+/// word that was hyphenated at the end of a line yields two words.
+pub fn words(world: &dyn World, frames: &[Frame]) -> Vec<Word> {
+    let mut words = vec![];
+    for frame in frames {
+        collect(world, frame, &mut words);
+    }
+    words
+}
+
+/// Collect the words in a frame and its subframes.
+fn collect(world: &dyn World, frame: &Frame, words: &mut Vec<Word>) {
+    for (_, item) in frame.items() {
+        match item {
+            FrameItem::Group(group) => collect(world, &group.frame, words),
+            FrameItem::Text(text) => {
+                for (offset, word) in text.text.unicode_word_indices() {
+                    let range = offset..offset + word.len();
+                    words.push(Word {
+                        text: word.into(),
+                        lang: text.lang,
+                        source: locate(world, text, range),
+                    });
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Find the source range of a byte range in a text item's text.
+fn locate(
+    world: &dyn World,
+    text: &TextItem,
+    range: Range<usize>,
+) -> Option<(FileId, Range<usize>)> {
+    let mut glyphs = text.glyphs.iter().filter(|glyph| {
+        let r = glyph.range();
+        r.start < range.end && range.start < r.end
+    });
+
+    let first = glyphs.clone().min_by_key(|glyph| glyph.range.start)?;
+    let last = glyphs.max_by_key(|glyph| glyph.range.start)?;
+    let (id, start) = position(world, first, false)?;
+    let (end_id, end) = position(world, last, true)?;
+    (id == end_id && start <= end).then_some((id, start..end))
+}
+
+/// Determine the source position at which a glyph starts or ends.
+fn position(world: &dyn World, glyph: &Glyph, end: bool) -> Option<(FileId, usize)> {
+    let (span, span_offset) = glyph.span;
+    if span.is_detached() {
+        return None;
+    }
+
+    let source = world.source(span.id()).ok()?;
+    let node = source.find(span)?;
+    let range = node.range();
+    let offset = if node.kind() == SyntaxKind::Text {
+        let mut offset = range.start + usize::from(span_offset);
+        if end {
+            offset += glyph.range().len();
+        }
+        offset.min(range.end)
+    } else if end {
+        range.end
+    } else {
+        range.start
+    };
+
+    Some((span.id(), offset))
+}
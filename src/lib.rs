@@ -47,6 +47,8 @@ pub mod doc;
 pub mod export;
 pub mod file;
 pub mod font;
+#[doc(hidden)]
+pub mod fuzz;
 pub mod geom;
 pub mod ide;
 pub mod image;
@@ -64,10 +66,15 @@ use crate::syntax::Source;
 use crate::util::Bytes;
 
 /// Compile a source file into a fully layouted document.
-#[tracing::instrument(skip(world))]
 pub fn compile(world: &dyn World) -> SourceResult<Document> {
+    compile_with(world, &mut Tracer::default())
+}
+
+/// Compile a source file into a fully layouted document, collecting the
+/// warnings raised during compilation in the `tracer`.
+#[tracing::instrument(skip(world, tracer))]
+pub fn compile_with(world: &dyn World, tracer: &mut Tracer) -> SourceResult<Document> {
     let route = Route::default();
-    let mut tracer = Tracer::default();
 
     // Call `track` just once to keep comemo's ID stable.
     let world = world.track();
@@ -14,7 +14,7 @@ use crate::doc::{Frame, FrameItem, Meta, Position};
 use crate::eval::{cast, Value};
 use crate::geom::{Point, Transform};
 use crate::model::Label;
-use crate::util::NonZeroExt;
+use crate::util::{hash128, NonZeroExt};
 
 /// Identifies the location of an element in the document.
 ///
@@ -38,6 +38,14 @@ impl Location {
         self.variant = n;
         self
     }
+
+    /// Produce a location that is distinct from all locations of other
+    /// documents. This is used when the pages of multiple documents are
+    /// merged.
+    pub fn rebase(mut self, document: usize) -> Self {
+        self.hash = hash128(&(self.hash, document));
+        self
+    }
 }
 
 impl Debug for Location {
@@ -28,7 +28,7 @@ use std::mem::ManuallyDrop;
 
 use comemo::{Track, Tracked, TrackedMut, Validate};
 
-use crate::diag::{SourceError, SourceResult};
+use crate::diag::{Severity, SourceError, SourceResult};
 use crate::doc::Document;
 use crate::eval::Tracer;
 use crate::World;
@@ -90,9 +90,18 @@ pub fn typeset(
     // Drop the introspector.
     ManuallyDrop::into_inner(introspector);
 
-    // Promote delayed errors.
-    if !delayed.0.is_empty() {
-        return Err(Box::new(delayed.0));
+    // Report the warnings of the final iteration and promote delayed errors.
+    let (warnings, errors): (Vec<_>, Vec<_>) = delayed
+        .0
+        .into_iter()
+        .partition(|error| error.severity == Severity::Warning);
+
+    for warning in warnings {
+        tracer.warn(warning);
+    }
+
+    if !errors.is_empty() {
+        return Err(Box::new(errors));
     }
 
     Ok(document)
@@ -108,7 +117,8 @@ pub struct Vt<'a> {
     pub introspector: Tracked<'a, Introspector>,
     /// Provides stable identities to elements.
     pub locator: &'a mut Locator<'a>,
-    /// Delayed errors that do not immediately terminate execution.
+    /// Delayed errors and warnings, which do not immediately terminate
+    /// execution.
     pub delayed: TrackedMut<'a, DelayedErrors>,
     /// The tracer for inspection of the values an expression produces.
     pub tracer: TrackedMut<'a, Tracer>,
@@ -133,16 +143,31 @@ impl Vt<'_> {
             }
         }
     }
+
+    /// Raise a warning.
+    ///
+    /// Like a delayed error, the warning is only reported if it is still raised
+    /// in the final iteration of the introspection loop.
+    pub fn warn(&mut self, warning: SourceError) {
+        self.delayed.push(warning);
+    }
 }
 
-/// Holds delayed errors.
+/// Holds delayed errors and warnings.
 #[derive(Default, Clone)]
 pub struct DelayedErrors(Vec<SourceError>);
 
+impl DelayedErrors {
+    /// The delayed errors without the warnings.
+    pub fn into_errors(self) -> impl Iterator<Item = SourceError> {
+        self.0.into_iter().filter(|error| error.severity == Severity::Error)
+    }
+}
+
 #[comemo::track]
 impl DelayedErrors {
     /// Push a delayed error.
-    fn push(&mut self, error: SourceError) {
+    pub fn push(&mut self, error: SourceError) {
         self.0.push(error);
     }
 }
@@ -175,12 +175,13 @@ impl Reflect for LocatableSelector {
         CastInfo::Union(vec![
             CastInfo::Type("function"),
             CastInfo::Type("label"),
+            CastInfo::Type("location"),
             CastInfo::Type("selector"),
         ])
     }
 
     fn castable(value: &Value) -> bool {
-        matches!(value.type_name(), "function" | "label" | "selector")
+        matches!(value.type_name(), "function" | "label" | "location" | "selector")
     }
 }
 
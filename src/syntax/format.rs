@@ -0,0 +1,202 @@
+use super::{SyntaxKind, SyntaxNode};
+
+/// Format a parsed source file canonically.
+///
+/// Returns `None` if the syntax tree contains errors.
+///
+/// Formatting only touches whitespace that is insignificant for the result:
+/// Runs of spaces are collapsed into one, trailing whitespace is removed, more
+/// than one blank line in a row is merged into one and the file ends with
+/// exactly one newline. Indentation, comments, raw text and strings are kept
+/// verbatim.
+///
+/// Argument lists, arrays, dictionaries and parameter lists in code that would
+/// extend a line beyond 80 characters are wrapped with one item per line.
+pub fn format(root: &SyntaxNode) -> Option<String> {
+    if root.erroneous() {
+        return None;
+    }
+
+    let mut output = String::new();
+    write(root, &mut output, false);
+
+    let len = output.trim_end().len();
+    output.truncate(len);
+    if !output.is_empty() {
+        output.push('\n');
+    }
+
+    Some(output)
+}
+
+/// The maximum line width before lists in code are wrapped.
+const MAX_WIDTH: usize = 80;
+
+/// Write the formatted text of a node.
+fn write(node: &SyntaxNode, output: &mut String, math: bool) {
+    match node.kind() {
+        SyntaxKind::Space | SyntaxKind::Parbreak => whitespace(node.text(), output),
+        SyntaxKind::Args | SyntaxKind::Array | SyntaxKind::Dict | SyntaxKind::Params
+            if !math && overflows(node, output) =>
+        {
+            wrap(node, output)
+        }
+        _ if node.children().len() == 0 => output.push_str(node.text()),
+        kind => {
+            let math = math || kind == SyntaxKind::Equation;
+            for child in node.children() {
+                write(child, output, math);
+            }
+        }
+    }
+}
+
+/// Whether a parenthesized list would extend the current line beyond the
+/// maximum width.
+fn overflows(node: &SyntaxNode, output: &str) -> bool {
+    // Lists without items, like the empty dictionary, and lists with
+    // comments between their items are left alone.
+    let mut items = node
+        .children()
+        .skip_while(|child| child.kind() != SyntaxKind::LeftParen)
+        .take_while(|child| child.kind() != SyntaxKind::RightParen);
+    if !items.any(is_item)
+        || node.children().any(|child| {
+            matches!(child.kind(), SyntaxKind::LineComment | SyntaxKind::BlockComment)
+        })
+    {
+        return false;
+    }
+
+    let mut flat = String::new();
+    for child in node.children() {
+        write(child, &mut flat, false);
+    }
+
+    let line = output.rfind('\n').map_or(0, |i| i + 1);
+    let width = flat.find('\n').unwrap_or(flat.len());
+    output[line..].chars().count() + flat[..width].chars().count() > MAX_WIDTH
+}
+
+/// Whether a child of a list is one of its items.
+fn is_item(child: &SyntaxNode) -> bool {
+    !matches!(
+        child.kind(),
+        SyntaxKind::LeftParen
+            | SyntaxKind::RightParen
+            | SyntaxKind::Comma
+            | SyntaxKind::Colon
+            | SyntaxKind::Space
+    )
+}
+
+/// Write a parenthesized list with one item per line, indented one level
+/// deeper than the current line.
+fn wrap(node: &SyntaxNode, output: &mut String) {
+    let line = output.rfind('\n').map_or(0, |i| i + 1);
+    let indent: String = output[line..]
+        .chars()
+        .take_while(|&c| c == ' ' || c == '\t')
+        .collect();
+
+    let mut inside = false;
+    for child in node.children() {
+        match child.kind() {
+            SyntaxKind::LeftParen if !inside => {
+                output.push('(');
+                inside = true;
+            }
+            SyntaxKind::RightParen if inside => {
+                output.push('\n');
+                output.push_str(&indent);
+                output.push(')');
+                inside = false;
+            }
+            _ if inside && !is_item(child) => {}
+            _ if inside => {
+                output.push('\n');
+                output.push_str(&indent);
+                output.push_str("  ");
+                write(child, output, false);
+                output.push(',');
+            }
+            _ => write(child, output, false),
+        }
+    }
+}
+
+/// Write a normalized run of whitespace.
+fn whitespace(text: &str, output: &mut String) {
+    // Leave unusual whitespace like carriage returns alone.
+    if !text.chars().all(|c| matches!(c, ' ' | '\t' | '\n')) {
+        output.push_str(text);
+        return;
+    }
+
+    let newlines = text.matches('\n').count();
+    if newlines == 0 {
+        output.push(' ');
+        return;
+    }
+
+    // Remove trailing whitespace from the line that is ending.
+    let len = output.trim_end_matches(|c: char| c == ' ' || c == '\t').len();
+    output.truncate(len);
+
+    for _ in 0..newlines.min(2) {
+        output.push('\n');
+    }
+
+    // Keep the indentation of the next line.
+    let indent = text.rfind('\n').map_or("", |i| &text[i + 1..]);
+    output.push_str(indent);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::syntax::parse;
+
+    #[track_caller]
+    fn test(text: &str, expected: &str) {
+        assert_eq!(format(&parse(text)).as_deref(), Some(expected));
+    }
+
+    #[test]
+    fn test_format_spaces() {
+        test("Hello   world", "Hello world\n");
+        test("#let x  =  (1,   2)", "#let x = (1, 2)\n");
+        test("$a  +   b$", "$a + b$\n");
+    }
+
+    #[test]
+    fn test_format_lines() {
+        test("First   \nSecond\t\n", "First\nSecond\n");
+        test("A\n\n\n\nB\n\n\n", "A\n\nB\n");
+        test("#{\n    let x = 1\n\n\n    x\n}", "#{\n    let x = 1\n\n    x\n}\n");
+    }
+
+    #[test]
+    fn test_format_wrapping() {
+        test(
+            "#let colors = (red, green, blue, yellow, purple, orange, black, white, gray, maroon)",
+            "#let colors = (\n  red,\n  green,\n  blue,\n  yellow,\n  purple,\n  orange,\n  black,\n  white,\n  gray,\n  maroon,\n)\n",
+        );
+        test(
+            "#{\n  rect(width: 100%, height: 2cm, fill: gradient, stroke: 2pt + red, radius: 40pt)[Body]\n}",
+            "#{\n  rect(\n    width: 100%,\n    height: 2cm,\n    fill: gradient,\n    stroke: 2pt + red,\n    radius: 40pt,\n  )[Body]\n}\n",
+        );
+        test("#f(a, b)", "#f(a, b)\n");
+    }
+
+    #[test]
+    fn test_format_verbatim() {
+        test("`a   b`  // c   d", "`a   b` // c   d\n");
+        test("#\"a   b\"", "#\"a   b\"\n");
+    }
+
+    #[test]
+    fn test_format_errors() {
+        assert_eq!(format(&parse("#let x = (")), None);
+    }
+}
@@ -2,6 +2,7 @@
 
 pub mod ast;
 
+mod format;
 mod kind;
 mod lexer;
 mod node;
@@ -10,6 +11,7 @@ mod reparser;
 mod source;
 mod span;
 
+pub use self::format::format;
 pub use self::kind::SyntaxKind;
 pub use self::lexer::{is_ident, is_newline};
 pub use self::node::{LinkedChildren, LinkedNode, SyntaxNode};
@@ -0,0 +1,245 @@
+//! Tests for the Rust API and for layout details that the reference tests
+//! can't observe.
+
+use std::path::Path;
+
+use comemo::Prehashed;
+use typst::diag::FileResult;
+use typst::doc::{Document, Frame, FrameItem, Lang};
+use typst::eval::{Datetime, Library, Tracer};
+use typst::file::FileId;
+use typst::font::{Font, FontBook};
+use typst::geom::{Abs, Color, Geometry, Length, Point, RgbaColor};
+use typst::ide::{words, Word};
+use typst::model::{StyleChain, Styles};
+use typst::syntax::Source;
+use typst::util::Bytes;
+use typst::World;
+use typst_library::text::{measure_text, TextElem};
+
+const FONTS: &[&[u8]] = &[
+    include_bytes!("../../assets/fonts/LinLibertine_R.ttf"),
+    include_bytes!("../../assets/fonts/LinLibertine_RB.ttf"),
+    include_bytes!("../../assets/fonts/LinLibertine_RI.ttf"),
+];
+
+#[test]
+fn test_words() {
+    let text = r#"Hello "wörld" -- #text(lang: "de")[Haus]"#;
+    let world = ApiWorld::new(text);
+    let document = typst::compile(&world).unwrap();
+    let id = world.source.id();
+
+    let word = |text: &str, lang, start: usize| Word {
+        text: text.into(),
+        lang,
+        source: Some((id, start..start + text.len())),
+    };
+
+    assert_eq!(
+        words(&world, &document.pages),
+        [
+            word("Hello", Lang::ENGLISH, 0),
+            word("wörld", Lang::ENGLISH, 7),
+            word("Haus", Lang::GERMAN, text.find("Haus").unwrap()),
+        ]
+    );
+}
+
+#[test]
+fn test_repeat_gap_and_justify() {
+    // Compile a repeated 10pt line in a 100pt box and return the positions of
+    // the instances in points.
+    fn positions(setup: &str, args: &str) -> Vec<f64> {
+        let document = compile(&format!(
+            "#set page(width: 100pt, height: auto, margin: 0pt)\n{setup}\n\
+             #box(width: 100pt, repeat({args}, line(length: 10pt)))"
+        ));
+        flatten(&document.pages[0])
+            .into_iter()
+            .filter_map(|(pos, item)| match item {
+                FrameItem::Shape(shape, _) => match shape.geometry {
+                    Geometry::Line(_) => Some((pos.x.to_pt() * 100.0).round() / 100.0),
+                    _ => None,
+                },
+                _ => None,
+            })
+            .collect()
+    }
+
+    // Seven instances with a 4pt gap leave 6pt that justification distributes
+    // over the six gaps.
+    let steps = |start: f64, step: f64| -> Vec<f64> {
+        (0..7).map(|i| start + i as f64 * step).collect()
+    };
+    assert_eq!(positions("", "gap: 4pt"), steps(0.0, 15.0));
+    assert_eq!(positions("", "gap: 4pt, justify: false"), steps(0.0, 14.0));
+    assert_eq!(
+        positions("#set align(right)", "gap: 4pt, justify: false"),
+        steps(6.0, 14.0),
+    );
+}
+
+#[test]
+fn test_highlight() {
+    let document = compile(
+        "#set page(width: 60pt, height: auto, margin: 0pt)\n\
+         #highlight(extent: 2pt)[Highlighted text]",
+    );
+    let items = flatten(&document.pages[0]);
+
+    // The highlight follows the text across the line break and is painted
+    // below it.
+    let rects: Vec<_> = items
+        .iter()
+        .enumerate()
+        .filter_map(|(i, (pos, item))| match item {
+            FrameItem::Shape(shape, _) => match shape.geometry {
+                Geometry::Rect(size) => Some((i, *pos, size, shape.fill.clone())),
+                _ => None,
+            },
+            _ => None,
+        })
+        .collect();
+    let texts: Vec<_> = items
+        .iter()
+        .enumerate()
+        .filter_map(|(i, (pos, item))| match item {
+            FrameItem::Text(text) => Some((i, *pos, text.width())),
+            _ => None,
+        })
+        .collect();
+
+    assert_eq!(rects.len(), 2);
+    assert_eq!(texts.len(), 2);
+    for ((i, rect_pos, size, fill), (j, text_pos, width)) in rects.into_iter().zip(texts)
+    {
+        assert!(i < j);
+        assert_eq!(
+            fill,
+            Some(Color::Rgba(RgbaColor::new(0xFF, 0xFD, 0x11, 0xA1)).into())
+        );
+        assert!((rect_pos.x + Abs::pt(2.0)).approx_eq(text_pos.x));
+        assert!(size.x.approx_eq(width + Abs::pt(4.0)));
+        assert!(rect_pos.y < text_pos.y);
+    }
+}
+
+#[test]
+fn test_measure_text() {
+    let world = ApiWorld::new("");
+    let styles = StyleChain::new(&world.library.styles);
+    let metrics = measure_text(&world, "Hello, world", styles);
+
+    assert!(metrics.ascent > Abs::zero());
+
+    // The clusters cover the text without gaps and add up to the width.
+    let mut cursor = 0;
+    for (range, _) in &metrics.clusters {
+        assert_eq!(range.start, cursor);
+        cursor = range.end;
+    }
+    assert_eq!(cursor, "Hello, world".len());
+
+    let sum = metrics.clusters.iter().map(|&(_, advance)| advance).sum::<Abs>();
+    assert!(sum.approx_eq(metrics.width));
+}
+
+#[test]
+fn test_measure_text_respects_styles() {
+    let world = ApiWorld::new("");
+    let base = StyleChain::new(&world.library.styles);
+    let plain = measure_text(&world, "Typst", base);
+
+    let tracked = Styles::from(TextElem::set_tracking(Length::from(Abs::pt(2.0))));
+    let spaced = measure_text(&world, "Typst", base.chain(&tracked));
+    assert!(spaced.width > plain.width);
+    assert_eq!(spaced.clusters.len(), plain.clusters.len());
+}
+
+#[test]
+fn test_shaping_stats() {
+    let world = ApiWorld::new("Hello\n\nHello\n\nWorld");
+    let mut tracer = Tracer::default();
+    typst::compile_with(&world, &mut tracer).unwrap();
+
+    // The repeated word is shaped only once.
+    let stats = tracer.shaping_stats();
+    assert_eq!(stats.distinct, 2);
+    assert!(stats.runs >= 3);
+}
+
+/// Compile a document from source code.
+fn compile(text: &str) -> Document {
+    typst::compile(&ApiWorld::new(text)).unwrap()
+}
+
+/// Collect the items of a frame and its groups with their positions relative
+/// to the frame, in painting order. Group transforms are ignored.
+fn flatten(frame: &Frame) -> Vec<(Point, FrameItem)> {
+    let mut items = vec![];
+    for (pos, item) in frame.items() {
+        match item {
+            FrameItem::Group(group) => items.extend(
+                flatten(&group.frame)
+                    .into_iter()
+                    .map(|(inner, item)| (*pos + inner, item)),
+            ),
+            item => items.push((*pos, item.clone())),
+        }
+    }
+    items
+}
+
+struct ApiWorld {
+    library: Prehashed<Library>,
+    book: Prehashed<FontBook>,
+    fonts: Vec<Font>,
+    source: Source,
+}
+
+impl ApiWorld {
+    fn new(text: &str) -> Self {
+        let fonts: Vec<_> =
+            FONTS.iter().map(|&data| Font::new(data.into(), 0).unwrap()).collect();
+        let book = FontBook::from_fonts(&fonts);
+
+        Self {
+            library: Prehashed::new(typst_library::build()),
+            book: Prehashed::new(book),
+            fonts,
+            source: Source::new(FileId::new(None, Path::new("/main.typ")), text.into()),
+        }
+    }
+}
+
+impl World for ApiWorld {
+    fn library(&self) -> &Prehashed<Library> {
+        &self.library
+    }
+
+    fn book(&self) -> &Prehashed<FontBook> {
+        &self.book
+    }
+
+    fn main(&self) -> Source {
+        self.source.clone()
+    }
+
+    fn source(&self, id: FileId) -> FileResult<Source> {
+        assert_eq!(id, self.source.id());
+        Ok(self.source.clone())
+    }
+
+    fn file(&self, _: FileId) -> FileResult<Bytes> {
+        unimplemented!()
+    }
+
+    fn font(&self, index: usize) -> Option<Font> {
+        self.fonts.get(index).cloned()
+    }
+
+    fn today(&self, _: Option<i64>) -> Option<Datetime> {
+        unimplemented!()
+    }
+}
@@ -11,7 +11,22 @@ use typst::World;
 use unscanny::Scanner;
 
 const TEXT: &str = include_str!("../typ/compiler/bench.typ");
-const FONT: &[u8] = include_bytes!("../../assets/fonts/LinLibertine_R.ttf");
+const FONTS: &[&[u8]] = &[
+    include_bytes!("../../assets/fonts/LinLibertine_R.ttf"),
+    include_bytes!("../../assets/fonts/LinLibertine_RB.ttf"),
+    include_bytes!("../../assets/fonts/LinLibertine_RI.ttf"),
+    include_bytes!("../../assets/fonts/DejaVuSansMono.ttf"),
+    include_bytes!("../../assets/fonts/IBMPlexSans-Regular.ttf"),
+    include_bytes!("../../assets/fonts/InriaSerif-Regular.ttf"),
+];
+
+/// A document that embeds many fonts into a PDF.
+const MULTI_FONT_TEXT: &str = r#"
+#for font in ("Linux Libertine", "DejaVu Sans Mono", "IBM Plex Sans", "Inria Serif") {
+  text(font: font, lorem(200))
+}
+*#lorem(200)* _#lorem(200)_
+"#;
 
 main!(
     bench_decode,
@@ -22,6 +37,7 @@ main!(
     bench_typeset,
     bench_compile,
     bench_render,
+    bench_pdf_multi_font,
 );
 
 fn bench_decode(iai: &mut Iai) {
@@ -93,23 +109,34 @@ fn bench_render(iai: &mut Iai) {
     iai.run(|| typst::export::render(&document.pages[0], 1.0, Color::WHITE))
 }
 
+fn bench_pdf_multi_font(iai: &mut Iai) {
+    let world = BenchWorld::with_text(MULTI_FONT_TEXT);
+    let document = typst::compile(&world).unwrap();
+    iai.run(|| typst::export::pdf(&document).unwrap())
+}
+
 struct BenchWorld {
     library: Prehashed<Library>,
     book: Prehashed<FontBook>,
-    font: Font,
+    fonts: Vec<Font>,
     source: Source,
 }
 
 impl BenchWorld {
     fn new() -> Self {
-        let font = Font::new(FONT.into(), 0).unwrap();
-        let book = FontBook::from_fonts([&font]);
+        Self::with_text(TEXT)
+    }
+
+    fn with_text(text: &str) -> Self {
+        let fonts: Vec<_> =
+            FONTS.iter().map(|&data| Font::new(data.into(), 0).unwrap()).collect();
+        let book = FontBook::from_fonts(&fonts);
 
         Self {
             library: Prehashed::new(typst_library::build()),
             book: Prehashed::new(book),
-            font,
-            source: Source::detached(TEXT),
+            fonts,
+            source: Source::detached(text),
         }
     }
 
@@ -139,8 +166,8 @@ impl World for BenchWorld {
         unimplemented!()
     }
 
-    fn font(&self, _: usize) -> Option<Font> {
-        Some(self.font.clone())
+    fn font(&self, index: usize) -> Option<Font> {
+        self.fonts.get(index).cloned()
     }
 
     fn today(&self, _: Option<i64>) -> Option<Datetime> {
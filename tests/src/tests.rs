@@ -21,11 +21,11 @@ use typst::file::FileId;
 use unscanny::Scanner;
 use walkdir::WalkDir;
 
-use typst::diag::{bail, FileError, FileResult, StrResult};
+use typst::diag::{bail, FileError, FileResult, Severity, StrResult};
 use typst::doc::{Document, Frame, FrameItem, Meta};
 use typst::eval::{eco_format, func, Datetime, Library, NoneValue, Value};
 use typst::font::{Font, FontBook};
-use typst::geom::{Abs, Color, RgbaColor, Smart};
+use typst::geom::{Abs, Color, Geometry, RgbaColor, Smart};
 use typst::syntax::{Source, Span, SyntaxNode};
 use typst::util::{Bytes, PathExt};
 use typst::World;
@@ -405,7 +405,7 @@ fn test(
     let document = Document { pages: frames, ..Default::default() };
     if compare_ever {
         if let Some(pdf_path) = pdf_path {
-            let pdf_data = typst::export::pdf(&document);
+            let pdf_data = typst::export::pdf(&document).unwrap();
             fs::create_dir_all(pdf_path.parent().unwrap()).unwrap();
             fs::write(pdf_path, pdf_data).unwrap();
         }
@@ -520,30 +520,69 @@ fn test_part(
         writeln!(output, "Model:\n{:#?}\n", module.content()).unwrap();
     }
 
-    let (mut frames, errors) = match typst::compile(world) {
+    let mut tracer = typst::eval::Tracer::default();
+    let (mut frames, errors) = match typst::compile_with(world, &mut tracer) {
         Ok(document) => (document.pages, vec![]),
         Err(errors) => (vec![], *errors),
     };
 
+    // Compare the semantic roles attached to the frames.
+    if let Some(expected) = &metadata.roles {
+        let mut roles = vec![];
+        for frame in &frames {
+            collect_roles(frame, &mut roles);
+        }
+
+        let roles = roles.join(", ");
+        if &roles != expected {
+            writeln!(output, "  Subtest {i} does not match expected roles.").unwrap();
+            writeln!(output, "    Expected | {expected}").unwrap();
+            writeln!(output, "    Found    | {roles}").unwrap();
+            ok = false;
+        }
+    }
+
+    // Compare the thicknesses of the stroked lines in the frames.
+    if let Some(expected) = &metadata.lines {
+        let mut lines = vec![];
+        for frame in &frames {
+            collect_lines(frame, &mut lines);
+        }
+
+        let lines = summarize_lines(lines);
+        if &lines != expected {
+            writeln!(output, "  Subtest {i} does not match expected lines.").unwrap();
+            writeln!(output, "    Expected | {expected}").unwrap();
+            writeln!(output, "    Found    | {lines}").unwrap();
+            ok = false;
+        }
+    }
+
     // Don't retain frames if we don't wanna compare with reference images.
     if !compare_ref {
         frames.clear();
     }
 
-    // Map errors to range and message format, discard traces and errors from
-    // other files, collect hints.
+    // Map errors and warnings to range and message format, discard traces and
+    // errors from other files, collect hints.
     //
     // This has one caveat: due to the format of the expected hints, we can not
     // verify if a hint belongs to a error or not. That should be irrelevant
     // however, as the line of the hint is still verified.
-    let actual_errors_and_hints: HashSet<UserOutput> = errors
-        .into_iter()
+    let actual_errors_and_hints: HashSet<UserOutput> = tracer
+        .warnings()
+        .iter()
+        .cloned()
+        .chain(errors)
         .inspect(|error| assert!(!error.span.is_detached()))
         .filter(|error| error.span.id() == source.id())
         .flat_map(|error| {
             let range = error.span.range(world);
-            let output_error =
-                UserOutput::Error(range.clone(), error.message.replace('\\', "/"));
+            let message = error.message.replace('\\', "/");
+            let output_error = match error.severity {
+                Severity::Error => UserOutput::Error(range.clone(), message),
+                Severity::Warning => UserOutput::Warning(range.clone(), message),
+            };
             let hints = error
                 .hints
                 .iter()
@@ -593,6 +632,7 @@ fn print_user_output(
 ) {
     let (range, message) = match &user_output {
         UserOutput::Error(r, m) => (r, m),
+        UserOutput::Warning(r, m) => (r, m),
         UserOutput::Hint(r, m) => (r, m),
     };
 
@@ -602,6 +642,7 @@ fn print_user_output(
     let end_col = 1 + source.byte_to_column(range.end).unwrap();
     let kind = match user_output {
         UserOutput::Error(_, _) => "Error",
+        UserOutput::Warning(_, _) => "Warning",
         UserOutput::Hint(_, _) => "Hint",
     };
     writeln!(output, "{kind}: {start_line}:{start_col}-{end_line}:{end_col}: {message}")
@@ -616,11 +657,14 @@ struct TestConfiguration {
 struct TestPartMetadata {
     part_configuration: TestConfiguration,
     invariants: HashSet<UserOutput>,
+    roles: Option<String>,
+    lines: Option<String>,
 }
 
 #[derive(PartialEq, Eq, Debug, Hash)]
 enum UserOutput {
     Error(Range<usize>, String),
+    Warning(Range<usize>, String),
     Hint(Range<usize>, String),
 }
 
@@ -628,6 +672,7 @@ impl UserOutput {
     fn start(&self) -> usize {
         match self {
             UserOutput::Error(r, _) => r.start,
+            UserOutput::Warning(r, _) => r.start,
             UserOutput::Hint(r, _) => r.start,
         }
     }
@@ -636,6 +681,10 @@ impl UserOutput {
         UserOutput::Error(range, message)
     }
 
+    fn warning(range: Range<usize>, message: String) -> UserOutput {
+        UserOutput::Warning(range, message)
+    }
+
     fn hint(range: Range<usize>, message: String) -> UserOutput {
         UserOutput::Hint(range, message)
     }
@@ -645,11 +694,15 @@ fn parse_part_metadata(source: &Source) -> TestPartMetadata {
     let mut compare_ref = None;
     let mut validate_hints = None;
     let mut expectations = HashSet::default();
+    let mut roles = None;
+    let mut stroked = None;
 
     let lines: Vec<_> = source.text().lines().map(str::trim).collect();
     for (i, line) in lines.iter().enumerate() {
         compare_ref = get_flag_metadata(line, "Ref").or(compare_ref);
         validate_hints = get_flag_metadata(line, "Hints").or(validate_hints);
+        roles = get_metadata(line, "Roles").map(str::to_string).or(roles);
+        stroked = get_metadata(line, "Lines").map(str::to_string).or(stroked);
 
         fn num(s: &mut Scanner) -> usize {
             s.eat_while(char::is_numeric).parse().unwrap()
@@ -667,12 +720,18 @@ fn parse_part_metadata(source: &Source) -> TestPartMetadata {
         };
 
         let error_factory: fn(Range<usize>, String) -> UserOutput = UserOutput::error;
+        let warning_factory: fn(Range<usize>, String) -> UserOutput = UserOutput::warning;
         let hint_factory: fn(Range<usize>, String) -> UserOutput = UserOutput::hint;
 
         let error_metadata = get_metadata(line, "Error").map(|s| (s, error_factory));
+        let get_warning_metadata =
+            || get_metadata(line, "Warning").map(|s| (s, warning_factory));
         let get_hint_metadata = || get_metadata(line, "Hint").map(|s| (s, hint_factory));
 
-        if let Some((expectation, factory)) = error_metadata.or_else(get_hint_metadata) {
+        if let Some((expectation, factory)) = error_metadata
+            .or_else(get_warning_metadata)
+            .or_else(get_hint_metadata)
+        {
             let mut s = Scanner::new(expectation);
             let start = pos(&mut s);
             let end = if s.eat_if('-') { pos(&mut s) } else { start };
@@ -685,6 +744,8 @@ fn parse_part_metadata(source: &Source) -> TestPartMetadata {
     TestPartMetadata {
         part_configuration: TestConfiguration { compare_ref, validate_hints },
         invariants: expectations,
+        roles,
+        lines: stroked,
     }
 }
 
@@ -884,6 +945,48 @@ fn render(frames: &[Frame]) -> sk::Pixmap {
     canvas
 }
 
+/// Collect the semantic roles attached to a frame in document order.
+fn collect_roles(frame: &Frame, roles: &mut Vec<String>) {
+    for (_, item) in frame.items() {
+        match item {
+            FrameItem::Group(group) => collect_roles(&group.frame, roles),
+            FrameItem::Meta(Meta::Role(role), _) => roles.push(format!("{role:?}")),
+            _ => {}
+        }
+    }
+}
+
+/// Collect the thicknesses of the stroked lines in a frame.
+fn collect_lines(frame: &Frame, lines: &mut Vec<Abs>) {
+    for (_, item) in frame.items() {
+        match item {
+            FrameItem::Group(group) => collect_lines(&group.frame, lines),
+            FrameItem::Shape(shape, _) if matches!(shape.geometry, Geometry::Line(_)) => {
+                lines.extend(shape.stroke.as_ref().map(|stroke| stroke.thickness));
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Summarize line thicknesses as counts per thickness, e.g. `1pt x 3, 2pt x 1`.
+fn summarize_lines(mut lines: Vec<Abs>) -> String {
+    lines.sort();
+    let mut counts: Vec<(Abs, usize)> = vec![];
+    for thickness in lines {
+        match counts.last_mut() {
+            Some((last, count)) if *last == thickness => *count += 1,
+            _ => counts.push((thickness, 1)),
+        }
+    }
+
+    counts
+        .iter()
+        .map(|(thickness, count)| format!("{thickness:?} x {count}"))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
 /// Draw extra boxes for links so we can see whether they are there.
 fn render_links(canvas: &mut sk::Pixmap, ts: sk::Transform, frame: &Frame) {
     for (pos, item) in frame.items() {
@@ -53,3 +53,21 @@
 // Test content fields method.
 #test([a].fields(), (text: "a"))
 #test([a *b*].fields(),  (children: ([a], [ ], strong[b])))
+
+---
+// Test length unit conversions.
+#test((500.934pt).pt(), 500.934)
+#test((3.3453cm).cm(), 3.3453)
+#test((4.3452mm).mm(), 4.3452)
+#test((5.345in).inches(), 5.345)
+#test((72pt).inches(), 1.0)
+
+---
+// Error: 2-18 cannot convert a length with non-zero em units (1pt + 2em) to pt
+#(1pt + 2em).pt()
+
+---
+// Test angle unit conversions.
+#test((90deg).deg(), 90.0)
+#test((1.5rad).rad(), 1.5)
+#test(calc.round((180deg).rad(), digits: 4), 3.1416)
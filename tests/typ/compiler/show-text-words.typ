@@ -0,0 +1,26 @@
+// Test text show rules that match across lines in the source.
+// Ref: false
+
+---
+#let matches = state("matches", 0)
+#show "New York": it => {
+  matches.update(n => n + 1)
+  it
+}
+
+I love New York and New
+York loves me. Newer York does not count, but New   York does.
+
+#locate(loc => test(matches.final(loc), 3))
+
+---
+#let matches = state("matches", ())
+#show regex("\d+\s+\w+"): it => {
+  matches.update(m => m + (it.text,))
+  it
+}
+
+We have 3 apples and 12
+pears.
+
+#locate(loc => test(matches.final(loc), ("3 apples", "12 pears")))
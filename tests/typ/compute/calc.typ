@@ -200,6 +200,45 @@
 // Error: 16-19 cannot compare 1pt with 1em
 #calc.max(1em, 1pt)
 
+---
+// Test the `clamp` function.
+#test(calc.clamp(5, 0, 10), 5)
+#test(calc.clamp(-1, 0, 10), 0)
+#test(calc.clamp(5, 0.0, 3), 3.0)
+#test(calc.clamp(12pt, 2pt, 8pt), 8pt)
+#test(calc.clamp(1cm, 2pt, 8cm), 1cm)
+#test(calc.clamp(20%, 0%, 50%), 20%)
+
+---
+// Error: 20-21 max must be greater than or equal to min
+#calc.clamp(5, 10, 0)
+
+---
+// Error: 24-27 max must be greater than or equal to min
+#calc.clamp(5pt, 10pt, 0pt)
+
+---
+// Error: 13-16 cannot compare 1em with 2pt
+#calc.clamp(1em, 2pt, 3pt)
+
+---
+// Test the `random` and `uuid` functions.
+#test(calc.random(1), calc.random(1))
+#test(calc.random(1) != calc.random(2), true)
+#test(calc.random(1) != calc.random(1, seed: 1), true)
+#test(calc.random("hi"), calc.random("hi"))
+#test(calc.random([*hi*]), calc.random([*hi*]))
+#test(calc.random() != calc.random(), true)
+#let xs = range(2).map(_ => calc.random())
+#test(xs.first() != xs.last(), true)
+#let ys = for i in range(2) { (calc.random(),) }
+#test(ys.first() != ys.last(), true)
+#test(range(100).map(calc.random).all(x => x >= 0 and x < 1), true)
+#test(calc.uuid("a"), calc.uuid("a"))
+#test(calc.uuid("a") != calc.uuid("b"), true)
+#test(calc.uuid().len(), 36)
+#test(calc.uuid().at(14), "4")
+
 ---
 // Test the `range` function.
 #test(range(4), (0, 1, 2, 3))
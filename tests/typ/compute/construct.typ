@@ -160,6 +160,16 @@
 #test(datetime.today(offset: auto).display(), "1970-01-01")
 #test(datetime.today(offset: 2).display(), "1970-01-01")
 
+---
+// Test comparing datetimes.
+#test(datetime(year: 2023, month: 4, day: 29) < datetime(year: 2023, month: 5, day: 1), true)
+#test(datetime(hour: 14, minute: 0, second: 0) > datetime(hour: 9, minute: 30, second: 0), true)
+#test(datetime(year: 2000, month: 1, day: 1) == datetime(year: 2000, month: 1, day: 1), true)
+
+---
+// Error: 3-85 cannot compare datetime(year: 2023, month: 4, day: 29) with datetime(hour: 14, minute: 0, second: 0)
+#(datetime(year: 2023, month: 4, day: 29) < datetime(hour: 14, minute: 0, second: 0))
+
 ---
 // Error: 10-12 at least one of date or time must be fully specified
 #datetime()
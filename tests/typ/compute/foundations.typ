@@ -120,3 +120,8 @@ _No relative giraffe!_
 ---
 // Error: 7-12 expected semicolon or line break
 #eval("1 2")
+
+---
+// Test that no inputs are passed by default.
+#test(sys.inputs, (:))
+#test(sys.inputs.at("draft", default: "false"), "false")
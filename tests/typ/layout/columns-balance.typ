@@ -0,0 +1,39 @@
+// Test balanced columns.
+// Ref: false
+
+---
+// Short content is distributed over both columns instead of staying in the
+// first one.
+#set page(width: 10cm, height: 10cm, margin: 0pt)
+#columns(2, balance: true)[
+  #for i in range(6) [Line #i \ ]
+  #locate(loc => test(loc.position().x > 5cm, true))
+]
+
+---
+// Introspection inside balanced columns sees each element once.
+#set page(width: 10cm, height: 10cm, margin: 0pt)
+#set heading(numbering: "1.")
+#columns(2, balance: true)[
+  = First <first>
+  #lorem(15)
+  = Second <second>
+  #lorem(15)
+]
+
+#locate(loc => {
+  let second = query(<second>, loc).first()
+  test(counter(heading).at(second.location()), (2,))
+  test(query(heading, loc).len(), 2)
+})
+
+---
+// When the content breaks across pages, only the last page is balanced.
+#set page(width: 10cm, height: 4cm, margin: 0pt)
+#columns(2, balance: true)[
+  #for i in range(20) [Line #i \ ]
+  #locate(loc => {
+    test(loc.page(), 2)
+    test(loc.position().x > 5cm, true)
+  })
+]
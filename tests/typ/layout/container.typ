@@ -48,3 +48,45 @@ First!
   But, soft! what light through yonder window breaks? It is the east, and Juliet
   is the sun.
 ]
+
+---
+// Error: 2-60 content does not fit into the box
+#box(width: 1cm, height: 1cm, overflow: "error", lorem(20))
+
+---
+// Shrinking content only fixes the size along the box's sized axes. Along the
+// others, the box takes the size of the scaled content.
+// Ref: false
+#style(styles => {
+  let content = block(width: 4cm, height: 2cm)
+  let size(..args) = measure(box(overflow: "shrink", ..args, content), styles)
+  test(size(width: 2cm), (width: 2cm, height: 1cm))
+  test(size(height: 1cm), (width: 2cm, height: 1cm))
+  test(size(width: 2cm, height: 2cm), (width: 2cm, height: 2cm))
+})
+
+---
+// Shrinking text reduces the text size until the content fits into the box,
+// but keeps the size of content that already fits.
+// Ref: false
+#set page(width: 5cm, height: 5cm, margin: 0pt)
+#set text(size: 10pt)
+#let em = state("em", ())
+#let probe = style(styles => {
+  let size = measure(box(width: 1em), styles).width
+  em.update(list => list + (size,))
+})
+
+#box(width: 3cm, height: 1cm, overflow: "shrink-text")[
+  #lorem(30) #probe
+  #locate(loc => test(loc.position().y < 1cm, true))
+]
+
+#box(width: 3cm, height: 1cm, overflow: "shrink-text")[A #probe]
+
+#locate(loc => {
+  let (shrunk, fits) = em.final(loc)
+  test(shrunk < 10pt, true)
+  test(shrunk >= 2.5pt, true)
+  test(fits, 10pt)
+})
@@ -0,0 +1,40 @@
+// Test unbreakable grid rows.
+// Ref: false
+
+---
+// By default, a row that doesn't fit starts in the remaining space.
+#set page(width: 4cm, height: 3cm, margin: 0pt)
+#let on(page) = locate(loc => test(loc.position().page, page))
+#grid(
+  block(height: 2cm),
+  block(height: 2cm, on(1)),
+)
+
+---
+// An unbreakable row is moved to the next page as a whole.
+#set page(width: 4cm, height: 3cm, margin: 0pt)
+#let on(page) = locate(loc => test(loc.position().page, page))
+#grid(
+  breakable: false,
+  block(height: 2cm, on(1)),
+  block(height: 2cm, on(2)),
+  block(height: 0.5cm, on(2)),
+)
+
+---
+// The same holds for tables.
+#set page(width: 4cm, height: 3cm, margin: 0pt)
+#let on(page) = locate(loc => test(loc.position().page, page))
+#table(
+  breakable: false,
+  inset: 0pt,
+  block(height: 2cm),
+  block(height: 2cm, on(2)),
+)
+
+---
+// A row that is taller than a full page is still split.
+#set page(width: 4cm, height: 2cm, margin: 0pt)
+// Warning: 25-43 row is too tall to fit into one region
+// Hint: 25-43 it is split although the grid is not breakable
+#grid(breakable: false, block(height: 3cm))
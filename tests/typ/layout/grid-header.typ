@@ -0,0 +1,52 @@
+// Test repeated header and footer rows.
+// Ref: false
+
+---
+// Rows continued on a new page start below the repeated header, also when
+// a single-line row moves to the next page as a whole.
+#set page(width: 4cm, height: 2.5cm, margin: 0pt)
+#let check = locate(loc => {
+  let pos = loc.position()
+  if pos.page > 1 {
+    test(pos.y > 0.9cm, true)
+  }
+})
+
+#grid(
+  header-rows: 1,
+  block(height: 1cm)[Header],
+  ..range(8).map(_ => [Row #check]),
+)
+
+---
+// The same for relative rows.
+#set page(width: 4cm, height: 2.5cm, margin: 0pt)
+#let check = locate(loc => {
+  let pos = loc.position()
+  if pos.page > 1 {
+    test(pos.y > 0.9cm, true)
+  }
+})
+
+#grid(
+  header-rows: 1,
+  rows: 1cm,
+  [Header],
+  ..range(4).map(_ => check),
+)
+
+---
+// The footer reserves space on each page and closes each part of the grid.
+#set page(width: 4cm, height: 3cm, margin: 0pt)
+#let approx(a, b) = calc.abs((a - b) / 1pt) < 0.01
+
+#grid(
+  footer-rows: 1,
+  ..range(5).map(_ => block(height: 1cm)),
+  block(height: 1cm, locate(loc => test(loc.position().page, 3))),
+  block(height: 0.5cm, locate(loc => {
+    let pos = loc.position()
+    test(pos.page, 1)
+    test(approx(pos.y, 2cm), true)
+  })),
+)
@@ -0,0 +1,51 @@
+// Test minmax tracks and auto-filled columns.
+// Ref: false
+
+---
+#style(styles => {
+  let size(it) = measure(it, styles)
+
+  // The minimum wins over small content.
+  test(size(grid(columns: minmax(2cm, auto), [A])).width, 2cm)
+
+  // A relative maximum caps the content.
+  test(size(grid(columns: minmax(1cm, 2cm), lorem(10))).width, 2cm)
+
+  // Without any remaining space, a fractional maximum keeps the minimum.
+  test(size(grid(columns: minmax(2cm, 1fr), [A])).width, 2cm)
+
+  // Rows are kept within their bounds.
+  test(size(grid(rows: minmax(1cm, auto), [A])).height, 1cm)
+  test(size(grid(rows: minmax(0pt, 5pt), block(height: 1cm))).height, 5pt)
+})
+
+---
+#style(styles => {
+  let cells = range(6).map(_ => block(height: 1cm))
+  let height(width, ..args) = measure(
+    box(width: width, grid(auto-fill: true, ..args, ..cells)),
+    styles,
+  ).height
+
+  let approx(a, b) = test(calc.abs((a - b) / 1pt) < 0.01, true)
+
+  approx(height(10cm, columns: 3cm), 2cm)
+  approx(height(10cm, columns: 3cm, column-gutter: 1cm), 3cm)
+  approx(height(10cm, columns: minmax(4cm, 1fr)), 3cm)
+  approx(height(2cm, columns: 3cm), 6cm)
+})
+
+---
+#table(columns: minmax(1cm, 1fr), auto-fill: true, ..range(8).map(str))
+
+---
+// Error: 1-47 auto-fill requires all columns to have a fixed or minimum size
+#grid(columns: (auto, 1cm), auto-fill: true)[A]
+
+---
+// Error: 13-29 minmax sizing is only supported for grid tracks
+#box(width: minmax(1cm, 1fr))[A]
+
+---
+// Error: 17-34 minmax sizing is only supported for grid tracks
+#set box(width: minmax(1cm, auto))
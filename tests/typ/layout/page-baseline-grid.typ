@@ -0,0 +1,18 @@
+// Test the baseline grid.
+
+---
+// All lines end up on the grid, regardless of the spacing and headings
+// between them.
+#set page(width: 160pt, height: 200pt, baseline-grid: 20pt)
+A \ B
+#v(7pt)
+C
+= Heading
+#text(size: 8pt)[D]
+
+---
+// Without a grid, the lines stay where they are.
+#set page(width: 160pt, height: 100pt)
+A
+#v(7pt)
+B
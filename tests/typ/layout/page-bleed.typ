@@ -0,0 +1,20 @@
+// Test page bleed and crop marks.
+// Ref: false
+
+---
+// The page grows by the bleed on each side.
+#set page(width: 4cm, height: 4cm, margin: 0pt, bleed: 3mm, fill: aqua)
+#let approx(a, b) = calc.abs((a - b) / 1pt) < 0.01
+#locate(loc => test(approx(loc.position().x, 3mm), true))
+
+---
+// Crop marks take up additional space outside of the bleed.
+#set page(width: 4cm, height: 4cm, margin: 0pt, bleed: 3mm, marks: true)
+#let approx(a, b) = calc.abs((a - b) / 1pt) < 0.01
+#locate(loc => test(approx(loc.position().x, 3mm + 18pt), true))
+
+---
+// Marks work without bleed.
+#set page(width: 4cm, height: 4cm, margin: 0pt, marks: true)
+#let approx(a, b) = calc.abs((a - b) / 1pt) < 0.01
+#locate(loc => test(approx(loc.position().x, 18pt), true))
@@ -40,3 +40,20 @@
   h(1em)
   place(left, rect(width: 80pt, stroke: blue))
 })
+
+---
+// Error: 18-31 page property `width` cannot differ on the first page
+#set page(first: (width: 10pt))
+
+---
+// Error: 2-39 the first page must leave the same width for its content as the other pages
+#page(first: (margin: (left: 0pt)))[A]
+
+---
+// The first page properties apply to the document's first page and to the
+// first page of a page call, but not after a pagebreak.
+#set page(height: 40pt, fill: aqua, first: (fill: yellow))
+Title
+#pagebreak()
+Chapter
+#page[Appendix]
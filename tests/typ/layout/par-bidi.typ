@@ -9,6 +9,8 @@
 ---
 // Test that consecutive, embedded  LTR runs stay LTR.
 // Here, we have two runs: "A" and italic "B".
+// Warning: 31-32 font family "PT Sans" has no italic variant
+// Hint: 31-32 the closest available variant is used instead
 #let content = par[أنت A#emph[B]مطرC]
 #set text(font: ("PT Sans", "Noto Sans Arabic"))
 #text(lang: "ar", content)
@@ -53,3 +55,15 @@ Lריווח #h(1cm) R
 
 // Error: 16-19 text direction must be horizontal
 #set text(dir: ttb)
+
+---
+// Test setting a vertical paragraph direction.
+// Ref: false
+
+// Error: 14-17 text direction must be horizontal
+#set par(dir: ttb)
+
+---
+// Ref: false
+// Error: 19-22 text direction must be horizontal
+#bidi-isolate(dir: btt)[Hi]
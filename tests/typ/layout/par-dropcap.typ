@@ -0,0 +1,23 @@
+// Test drop caps.
+
+---
+// The lines next to the drop cap are shortened, the ones below it are not.
+#set page(width: 150pt)
+#dropcap[T]his is a paragraph with a drop cap that spans three lines and
+wraps around it. #lorem(12)
+
+---
+// Fewer lines and no gap.
+#set page(width: 150pt)
+#dropcap(lines: 2, gap: 0pt)[O]nce upon a time, there was a paragraph with a
+small drop cap. #lorem(8)
+
+---
+// A drop cap with a different font size and color.
+#set page(width: 150pt)
+#dropcap(text(fill: eastern)[W])hen the drop cap is styled, the paragraph still
+flows around it. #lorem(6)
+
+---
+// Error: 4-14 drop cap must be at the start of a paragraph
+A #dropcap[B] C
@@ -0,0 +1,20 @@
+// Test styling the first line of a paragraph.
+// Ref: false
+
+---
+// The first line is styled, the following ones are not.
+#style(styles => {
+  let height(body) = measure(box(width: 4cm, body), styles).height
+  let plain = height[A \ B \ C]
+  let styled = height[#set par(first-line: (size: 20pt)); A \ B \ C]
+  test(styled > plain, true)
+  test(styled < plain + 20pt, true)
+})
+
+---
+// Layout settles even if the end of the first line moves back and forth
+// when it is styled.
+#set page(width: 4cm, height: auto, margin: 0pt)
+#set par(first-line: (size: 1.4em, tracking: 0.5pt))
+Lorem ipsum dolor sit amet, consectetur adipiscing elit.
+#locate(loc => test(loc.position().page, 1))
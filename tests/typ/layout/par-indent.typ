@@ -23,6 +23,8 @@ starts a paragraph, also with indent.
 #set text(8pt, lang: "ar", font: ("Noto Sans Arabic", "Linux Libertine"))
 #set par(leading: 8pt)
 
+// Warning: 3-9 font family "Noto Sans Arabic" has no bold variant
+// Hint: 3-9 the closest available variant is used instead
 = Arabic
 دع النص يمطر عليك
 
@@ -0,0 +1,19 @@
+// Test the configurable number of orphan lines.
+
+---
+// The paragraph's first three lines are kept together and move to the next
+// page.
+#set page(height: 100pt)
+#set par(orphans: 3)
+#v(40pt)
+#lorem(30)
+
+---
+// A heading moves to the next page unless enough lines of its paragraph fit
+// below it.
+#set page(height: 120pt)
+#set par(orphans: 3)
+#v(50pt)
+
+= Heading
+#lorem(20)
@@ -0,0 +1,29 @@
+// Test warnings for overfull and underfull lines.
+// Ref: false
+
+---
+// A line that is laid out several times is reported once.
+// Hints: false
+#set page(width: 3cm, margin: 0pt)
+// Warning: 14-48 line is overfull
+#let word = [Supercalifragilisticexpialidocious]
+#word
+
+#word
+
+#locate(loc => word)
+
+---
+// Each line is reported on its own.
+// Hints: false
+#set page(width: 3.5cm, margin: 0pt)
+#set par(justify: true, linebreaks: "simple")
+#set text(hyphenate: false)
+// Warning: 1-41 line is underfull
+// Warning: 1-41 line is overfull
+aa bb Supercalifragilisticexpialidocious
+
+---
+// Lines that fit are not reported.
+#set page(width: 3cm, margin: 0pt)
+A few short words that wrap nicely.
@@ -0,0 +1,28 @@
+// Test tab stops.
+
+---
+// Without tab stops, tabs are shaped like any other character.
+A#"\t"B
+
+---
+// Tabs advance to the next stop at a regular interval.
+#set par(tab-stops: 3em)
+A#"\t"B#"\t"C \
+Long#"\t"D
+
+---
+// Tabs advance to explicit stops and stop advancing behind the last one.
+#set page(width: 160pt)
+#set par(tab-stops: (3em, 7em))
+Name#"\t"Typst#"\t"(tool)#"\t"end \
+Kind#"\t"Software
+
+---
+// The measured width of a line includes the same advances it is laid out
+// with.
+#set par(tab-stops: 3cm)
+#let approx(a, b) = calc.abs((a - b) / 1pt) < 0.01
+#style(styles => {
+  let line = measure(box[A#"\t"B], styles).width
+  test(approx(line, 3cm + measure([B], styles).width), true)
+})
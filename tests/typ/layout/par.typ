@@ -34,3 +34,7 @@ fn main() {}
 - List
 
 Paragraph
+
+---
+// Error: 22-30 unexpected argument: foo
+#set par(first-line: (foo: 1))
@@ -0,0 +1,17 @@
+// Test pauses.
+// Ref: false
+
+---
+// Each pause adds a page.
+First
+#pause
+Second
+#pause
+Third
+#locate(loc => test(counter(page).final(loc), (3,)))
+
+---
+// Pauses in nested content.
+- One
+- Two #pause Three
+#locate(loc => test(counter(page).final(loc), (2,)))
@@ -0,0 +1,23 @@
+// Test text wrapping around placed content.
+
+---
+// Lines next to the placed content leave room for it and its clearance, the
+// ones below it don't.
+#set page(width: 200pt)
+#place(left, wrap: true, rect(width: 60pt, height: 30pt, fill: aqua))
+#lorem(30)
+
+---
+// Content on the right shortens the lines at their end.
+#set page(width: 200pt)
+#place(right, wrap: true, clearance: 10pt, rect(width: 60pt, height: 30pt, fill: aqua))
+#lorem(30)
+
+---
+// Without wrapping or a horizontal side, the text ignores the placed content.
+#set page(width: 200pt)
+#place(left, rect(width: 60pt, height: 30pt, fill: aqua.lighten(50%)))
+#lorem(10)
+
+#place(center, wrap: true, rect(width: 60pt, height: 30pt, fill: aqua.lighten(50%)))
+#lorem(10)
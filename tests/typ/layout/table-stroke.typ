@@ -0,0 +1,23 @@
+// Test per-cell table strokes and collapsed borders.
+// Ref: false
+
+---
+// Borders shared by two cells are only drawn once.
+// Lines: 1pt x 12
+#table(columns: 2, stroke: (col, row) => 1pt, [A], [B], [C], [D])
+
+---
+// Where two strokes meet, the thicker one wins.
+// Lines: 1pt x 3, 2pt x 3
+#table(columns: 2, stroke: (col, row) => if col == 0 { 1pt } else { 2pt }, [A], [B])
+
+---
+// The same with an array of strokes for the columns.
+// Lines: 1pt x 3, 2pt x 3
+#table(columns: 2, stroke: (1pt, 2pt), [A], [B])
+
+---
+// Cells without a stroke only get the borders of their neighbours.
+// Lines: 2pt x 4
+#table(columns: 2, stroke: (col, row) => if col == 0 { 2pt }, [A], [B])
+
@@ -0,0 +1,11 @@
+// Test equation number alignment.
+// Ref: false
+
+---
+#set math.equation(numbering: "(1)", number-align: center)
+// Error: 1-8 equation numbers cannot be centered
+$ a^2 $
+
+---
+// Error: 34-37 alignment must be horizontal
+#set math.equation(number-align: top)
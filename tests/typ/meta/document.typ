@@ -16,6 +16,17 @@ What's up?
 #set document(author: (123,))
 What's up?
 
+---
+// Ref: false
+#set document(
+  keywords: ("typesetting", "markup"),
+  date: datetime(year: 2023, month: 6, day: 14),
+)
+
+---
+// Error: 34-35 expected datetime or none, found integer
+#set document(keywords: "a", date: 1)
+
 ---
 Hello
 
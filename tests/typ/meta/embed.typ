@@ -0,0 +1,15 @@
+// Test embedding files into the PDF.
+// Ref: false
+
+---
+#embed("/files/data.csv", description: "Raw data")
+#locate(loc => {
+  let files = query(embed, loc)
+  test(files.len(), 1)
+  test(files.first().path, "/files/data.csv")
+  test(files.first().description, "Raw data")
+})
+
+---
+// Error: 8-28 file not found (searched at typ/meta/does-not-exist.csv)
+#embed("does-not-exist.csv")
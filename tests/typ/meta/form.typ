@@ -0,0 +1,12 @@
+// Test form fields.
+// Ref: false
+
+---
+#textfield("name", value: "Jane")
+#checkbox("terms", checked: true)
+#dropdown("size", ("S", "M", "L"), selected: 1)
+#signature("signature")
+
+---
+// Error: 2-43 selected option is out of bounds
+#dropdown("size", ("A", "B"), selected: 2)
@@ -42,7 +42,11 @@ multiline.
 )
 
 = Heading
+// Warning: 7-14 font family "Roboto" has no bold variant
+// Hint: 7-14 the closest available variant is used instead
 ===== Heading 🌍
+// Warning: 20-27 font family "Roboto" has no bold variant
+// Hint: 20-27 the closest available variant is used instead
 #heading(level: 5)[Heading]
 
 ---
@@ -0,0 +1,23 @@
+// Test index entries.
+// Ref: false
+
+---
+#index-entry("Typst")
+#index-entry("typesetting")
+#locate(loc => {
+  let entries = query(index-entry, loc)
+  test(entries.map(entry => entry.term), ("Typst", "typesetting"))
+  test(entries.first().location().page(), 1)
+})
+
+---
+// Terms are grouped by their lowercase initial and sorted by code point.
+#let letters = state("letters", ())
+#show strong: it => letters.update(l => l + (it.body.text,)) + it
+#index(title: none)
+#index-entry("beta")
+#index-entry("Alpha")
+#index-entry("alpha")
+#index-entry("Zeta")
+#index-entry("élan")
+#locate(loc => test(letters.final(loc), ("A", "B", "Z", "É")))
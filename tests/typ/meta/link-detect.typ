@@ -0,0 +1,26 @@
+// Test detection of bare URLs and email addresses.
+// Ref: false
+
+---
+#let found = state("found", ())
+#set link(detect: true)
+#show link: it => found.update(list => list + (it.dest,)) + it.body
+
+Write to hello\@typst.app or visit www.typst.app.
+#"Mail (info@example.com), or https://example.com."
+
+No links: www. and example.com.
+
+#locate(loc => test(found.final(loc), (
+  "mailto:hello@typst.app",
+  "https://www.typst.app",
+  "mailto:info@example.com",
+  "https://example.com",
+)))
+
+---
+// Detection is off by default.
+#let found = state("found", 0)
+#show link: it => found.update(n => n + 1) + it.body
+Visit www.typst.app.
+#locate(loc => test(found.final(loc), 0))
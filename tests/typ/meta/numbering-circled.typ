@@ -0,0 +1,10 @@
+// Test circled numbering.
+// Ref: false
+
+---
+#test(numbering("①", 0), "⓪")
+#test(numbering("①", 3), "③")
+#test(numbering("①", 21), "㉑")
+#test(numbering("①", 50), "㊿")
+#test(numbering("①", 51), "51")
+#test(numbering("①.①", 1, 12), "①.⑫")
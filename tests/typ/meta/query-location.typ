@@ -0,0 +1,12 @@
+// Test querying for a location.
+// Ref: false
+
+---
+= Introduction <intro>
+#locate(loc => {
+  let intro = query(<intro>, loc).first()
+  let found = query(intro.location(), loc)
+  test(found.len(), 1)
+  test(found.first().location(), intro.location())
+  test(found.first().location().page(), 1)
+})
@@ -0,0 +1,21 @@
+// Test that semantic roles are attached once to the frames of their elements
+// instead of to every piece of their content.
+// Ref: false
+
+---
+// Roles: Heading(1), ListItem, ListItem, EnumItem
+= A *heading* with #box[styled] text
+- One *bold* item
+- Two #box[items]
++ Three
+
+---
+// Roles: TableCell { x: 0, y: 0 }, TableCell { x: 1, y: 0 }, TableCell { x: 0, y: 1 }
+#table(columns: 2, [*A* and _B_], [C], [D #box[E]])
+
+---
+// Roles are replaced along with the element's appearance.
+// Roles: ListItem
+#show heading: it => it.body
+= Heading
+- Item
@@ -0,0 +1,38 @@
+// Test finding the current element on a page.
+// Ref: false
+
+---
+#set page(height: 100pt)
+#let title(elem) = if elem != none { elem.body.text }
+
+#locate(loc => {
+  test(running(heading, loc), none)
+  test(running(heading, loc, mode: "first"), none)
+})
+
+#pagebreak()
+#locate(loc => {
+  test(title(running(heading, loc)), "Beta")
+  test(title(running(heading, loc, mode: "first")), "Alpha")
+})
+
+= Alpha
+= Beta
+
+#pagebreak()
+#locate(loc => {
+  test(title(running(heading, loc)), "Beta")
+  test(title(running(heading, loc, mode: "first")), "Beta")
+  test(running(heading.where(level: 2), loc), none)
+})
+
+#pagebreak()
+= Gamma
+#locate(loc => {
+  test(title(running(heading, loc)), "Gamma")
+  test(title(running(heading, loc, mode: "first")), "Gamma")
+})
+
+---
+// Error: 44-52 expected "first" or "last"
+#locate(loc => running(heading, loc, mode: "middle"))
@@ -61,3 +61,7 @@ fi vs. #text(features: (liga: 0))[No fi]
 ---
 // Error: 21-35 expected string, found boolean
 #set text(features: ("tag", false))
+
+---
+// Error: 21-33 expected integer, found string
+#set text(features: (liga: "on"))
@@ -58,3 +58,32 @@ Emoji: 🐪, 🌋, 🏞
 ---
 // Error: 11-31 unexpected argument: something
 #set text(something: "invalid")
+
+---
+// Error: 24-43 unknown script: klingon
+#set text(script-font: (klingon: "Ubuntu"))
+
+---
+// Warn once for each piece of text that lacks a variant, no matter how often
+// it is shown.
+// Ref: false
+#set text(font: "IBM Plex Sans")
+// Warning: 21-28 font family "IBM Plex Sans" has no italic variant
+// Hint: 21-28 the closest available variant is used instead
+#let slanted = emph[Slanted]
+#slanted #slanted
+
+// No warning when the primary font has the variant.
+#set text(font: ("Linux Libertine", "IBM Plex Sans"))
+#slanted
+
+---
+// Warn about characters that no font contains.
+// Ref: false
+#set text(font: "PT Sans", fallback: false)
+// Warning: 1-4 no font contains 中 (U+4E2D)
+// Hint: 1-4 the characters are shown as boxes instead
+Ab中
+
+// Whitespace is never reported.
+A#"\u{00A0}"B
@@ -33,10 +33,12 @@ It's a #emph[Tree]beard.
 
 ---
 // Test shy hyphens.
+// Hints: false
 #set text(lang: "de", hyphenate: true)
 #grid(
   columns: 2 * (20pt,),
   gutter: 20pt,
+  // Warning: 4-13 line is overfull
   [Barankauf],
   [Bar-?ankauf],
 )
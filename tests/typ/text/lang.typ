@@ -2,10 +2,14 @@
 
 ---
 // Ensure that setting the language does have effects.
+// Hints: false
 #set text(hyphenate: true)
 #grid(
   columns: 2 * (20pt,),
   gutter: 1fr,
+  // Warning: 20-21 line is overfull
+  // Warning: 21-40 line is overfull
+  // Warning: 1:21-1:40 line is overfull
   text(lang: "en")["Eingabeaufforderung"],
   text(lang: "de")["Eingabeaufforderung"],
 )
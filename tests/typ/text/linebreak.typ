@@ -6,6 +6,8 @@ This is a spaceexceedinglylongy.
 
 ---
 // Test two overlong words in a row.
+// Hints: false
+// Warning: 1-63 line is overfull
 Supercalifragilisticexpialidocious Expialigoricmetrioxidation.
 
 ---
@@ -29,7 +31,10 @@ Trailing break \ \
 
 ---
 // Test justified breaks.
+// Hints: false
 #set par(justify: true)
+// Warning: 1-12 line is underfull
+// Warning: 1:1-1:36 line is underfull
 With a soft #linebreak(justify: true)
 break you can force a break without #linebreak(justify: true)
 breaking justification. #linebreak(justify: false)
@@ -46,6 +51,8 @@ Second part
 
 ---
 // Test linebreak for East Asian languages
+// Hints: false
+// Warning: 1-272 line is overfull
 ทีวีตรวจทานนอร์ทแฟรีเลคเชอร์โกลด์อัลบัมเชอร์รี่เย้วสโตร์กฤษณ์เคลมเยอบีร่าพ่อค้าบลูเบอร์รี่สหัสวรรษโฮปแคนูโยโย่จูนสตรอว์เบอร์รีซื่อบื้อเยนแบ็กโฮเป็นไงโดนัททอมสเตริโอแคนูวิทย์แดรี่โดนัทวิทย์แอปพริคอทเซอร์ไพรส์ไฮบริดกิฟท์อินเตอร์โซนเซอร์วิสเทียมทานโคโยตี้ม็อบเที่ยงคืนบุญคุณ
 
 
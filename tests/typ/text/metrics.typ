@@ -0,0 +1,18 @@
+// Test font metrics.
+// Ref: false
+
+---
+#style(styles => {
+  let metrics = font-metrics(styles)
+  test(metrics.descender < 0pt, true)
+  test(metrics.x-height < metrics.cap-height, true)
+  test(metrics.cap-height < metrics.ascender, true)
+  test(metrics.underline.position < 0pt, true)
+  test(metrics.underline.thickness > 0pt, true)
+})
+
+---
+// Metrics scale with the text size.
+#text(20pt, style(styles => {
+  test(font-metrics(styles).ascender > 12pt, true)
+}))
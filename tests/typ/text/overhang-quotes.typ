@@ -0,0 +1,21 @@
+// Test hanging quotation marks.
+// Ref: false
+
+---
+// Quotes only hang into the margin when enabled.
+#set page(width: 5cm, margin: 0pt)
+#set align(end)
+#let xs = state("xs", ())
+#let mark = locate(loc => xs.update(v => v + (loc.position().x,)))
+
+#text(overhang: false)[A#mark”]
+
+A#mark”
+
+#text(overhang-quotes: true)[A#mark”]
+
+#locate(loc => {
+  let (off, default, quotes) = xs.final(loc)
+  test(default, off)
+  test(quotes > default, true)
+})
@@ -0,0 +1,35 @@
+// Test ruby annotations.
+
+---
+// The annotation widens the box only if it is wider than the base text.
+#style(styles => {
+  let size(body) = measure(body, styles)
+  let approx(a, b) = test(calc.abs((a - b) / 1pt) < 0.01, true)
+
+  let base = size[漢字]
+  let note = size(text(size: 5.5pt)[かんじ])
+  let ruby = size(ruby[漢字][かんじ])
+  approx(ruby.width, base.width)
+  approx(ruby.height, base.height + 1.1pt + note.height)
+
+  // A wider annotation overhangs the adjacent text by up to a quarter em on
+  // each side.
+  let note = size(text(size: 5.5pt)[a long annotation])
+  approx(size(ruby[A][a long annotation]).width, note.width - 5.5pt)
+  approx(size(ruby(overhang: 0pt)[A][a long annotation]).width, note.width)
+})
+
+---
+// The annotation sits above the base text.
+#set text(lang: "ja")
+#ruby[漢字][かんじ]を#ruby[読][よ]む
+
+---
+// An annotation that is wider than its base text overhangs the adjacent
+// text and the base text is centered below it.
+A#ruby[B][a long annotation]C \
+A#ruby(overhang: 0pt)[B][a long annotation]C
+
+---
+// A larger annotation with a wider gap.
+#ruby(size: 0.7em, gap: 3pt)[漢字][かんじ]
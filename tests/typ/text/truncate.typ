@@ -0,0 +1,22 @@
+// Test truncating content to a maximum width.
+// Ref: false
+
+---
+#style(styles => {
+  let long = [A rather long chapter title that does not fit]
+  let width(it) = measure(it, styles).width
+  test(width(truncate(3cm, long)) <= 3cm, true)
+  test(width(truncate(3cm, long)) > 2cm, true)
+  test(width(truncate(3cm, ellipsis: "...", long)) <= 3cm, true)
+})
+
+---
+// Content that fits is kept as is.
+#style(styles => {
+  let short = [Short]
+  test(measure(truncate(3cm, short), styles), measure(short, styles))
+})
+
+---
+// Error: 10-15 expected length, found string
+#truncate("3cm")[Title]
@@ -0,0 +1,18 @@
+// Test whitespace handling modes.
+// Ref: false
+
+---
+#style(styles => {
+  let size(mode, body) = measure(text(whitespace: mode, body), styles)
+  let line = size("pre-wrap", "a").height
+
+  // Preserved spaces keep their width and don't turn into other characters.
+  test(size("preserve", "a  b").width, size("pre-wrap", "a  b").width)
+  test(size("collapse", "a  b").width, size("pre-wrap", "a b").width)
+
+  // Preserved text only breaks at newlines.
+  let narrow(mode, body) = size(mode, box(width: 1cm, body)).height
+  test(narrow("preserve", "aaa bbb ccc") == line, true)
+  test(narrow("pre-wrap", "aaa bbb ccc") > line, true)
+  test(narrow("preserve", "a\nb") > line, true)
+})
@@ -0,0 +1,10 @@
+// Test plot errors.
+// Ref: false
+
+---
+// Error: 7-19 data point must contain exactly two entries
+#plot(((1, 2, 3),))
+
+---
+// Error: 13-18 expected "line", "bar", or "scatter"
+#plot(kind: "pie", ())
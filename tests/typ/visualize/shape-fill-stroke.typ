@@ -45,6 +45,8 @@
 #set text(font: "Roboto")
 #square(
   stroke: (left: red, top: yellow, right: green, bottom: blue),
+  // Warning: 40-41 font family "Roboto" has no bold variant
+  // Hint: 40-41 the closest available variant is used instead
   radius: 100%, align(center+horizon)[*G*],
   inset: 8pt
 )